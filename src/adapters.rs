@@ -1,5 +1,8 @@
 //! This module exposes the API client adapters which are used to interact with different
 //! data sources for fetching pokédex information.
 
+pub mod battle;
+pub mod item;
+mod pokeapi;
 pub mod pokedex;
 pub mod translation;
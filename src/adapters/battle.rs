@@ -0,0 +1,51 @@
+//! This module provides the API client adapter for fetching battle related data,
+//! such as abilities, moves and types, from an external API.
+
+#[cfg(test)]
+mod mock;
+mod pokeapi;
+
+#[cfg(test)]
+pub use self::mock::MockBattleAdapter;
+pub use self::pokeapi::PokeApiBattleAdapter;
+use crate::model::{Ability, Move, PokemonType};
+
+/// The result type for [`BattleAdapter`] operations
+pub type BattleAdapterResult<T> = Result<T, BattleAdapterError>;
+
+/// Errors that can occur while interacting with the [`BattleAdapter`]
+#[derive(Debug, thiserror::Error)]
+pub enum BattleAdapterError {
+    #[error("Resource not found")]
+    NotFound,
+    #[error("Network error: {0}")]
+    NetworkError(String),
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+    #[error("Parse error: {0}")]
+    ParseError(String),
+    #[error("Unexpected response from the API: {0}")]
+    UnexpectedResponse(String),
+}
+
+/// The BattleAdapter trait defines the interface for fetching abilities, moves and types
+/// from an external API.
+pub trait BattleAdapter: Send + Sync {
+    /// fetch an [`Ability`] by its name from the external API
+    fn fetch_ability_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = BattleAdapterResult<Ability>> + Send;
+
+    /// fetch a [`Move`] by its name from the external API
+    fn fetch_move_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = BattleAdapterResult<Move>> + Send;
+
+    /// fetch a [`PokemonType`] by its name from the external API
+    fn fetch_type_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = BattleAdapterResult<PokemonType>> + Send;
+}
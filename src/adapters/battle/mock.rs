@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use crate::adapters::battle::{BattleAdapter, BattleAdapterError, BattleAdapterResult};
+use crate::model::{Ability, Move, PokemonType};
+
+/// Mock implementation of the BattleAdapter for testing purposes.
+///
+/// It simulates fetching abilities, moves and types without making actual network requests.
+/// It returns the predefined data registered with the given name, or
+/// [`BattleAdapterError::NotFound`] if there is none.
+#[derive(Default)]
+pub struct MockBattleAdapter {
+    abilities: HashMap<String, Ability>,
+    moves: HashMap<String, Move>,
+    types: HashMap<String, PokemonType>,
+}
+
+impl MockBattleAdapter {
+    /// Creates a new instance of the [`MockBattleAdapter`] without any data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the given [`Ability`] into the mock.
+    pub fn with_ability(mut self, ability: Ability) -> Self {
+        self.abilities.insert(ability.name.clone(), ability);
+        self
+    }
+
+    /// Registers the given [`Move`] into the mock.
+    pub fn with_move(mut self, pokemon_move: Move) -> Self {
+        self.moves.insert(pokemon_move.name.clone(), pokemon_move);
+        self
+    }
+
+    /// Registers the given [`PokemonType`] into the mock.
+    pub fn with_type(mut self, pokemon_type: PokemonType) -> Self {
+        self.types.insert(pokemon_type.name.clone(), pokemon_type);
+        self
+    }
+}
+
+impl BattleAdapter for MockBattleAdapter {
+    async fn fetch_ability_by_name(&self, name: &str) -> BattleAdapterResult<Ability> {
+        self.abilities
+            .get(name)
+            .cloned()
+            .ok_or(BattleAdapterError::NotFound)
+    }
+
+    async fn fetch_move_by_name(&self, name: &str) -> BattleAdapterResult<Move> {
+        self.moves
+            .get(name)
+            .cloned()
+            .ok_or(BattleAdapterError::NotFound)
+    }
+
+    async fn fetch_type_by_name(&self, name: &str) -> BattleAdapterResult<PokemonType> {
+        self.types
+            .get(name)
+            .cloned()
+            .ok_or(BattleAdapterError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::model::{DamageClass, DamageRelations};
+
+    #[tokio::test]
+    async fn test_should_fetch_mocked_ability_by_name() {
+        let ability = Ability {
+            name: "static".to_string(),
+            effect: "May paralyze on contact.".to_string(),
+            short_effect: "Paralyzes on contact.".to_string(),
+        };
+        let adapter = MockBattleAdapter::new().with_ability(ability.clone());
+
+        assert_eq!(
+            adapter.fetch_ability_by_name("static").await.unwrap(),
+            ability
+        );
+        assert!(matches!(
+            adapter.fetch_ability_by_name("levitate").await,
+            Err(BattleAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_mocked_move_by_name() {
+        let pokemon_move = Move {
+            name: "thunderbolt".to_string(),
+            move_type: "electric".to_string(),
+            damage_class: DamageClass::Special,
+            power: Some(90),
+            accuracy: Some(100),
+            pp: Some(15),
+            priority: 0,
+            effect: "May paralyze the target.".to_string(),
+            short_effect: "May paralyze.".to_string(),
+        };
+        let adapter = MockBattleAdapter::new().with_move(pokemon_move.clone());
+
+        assert_eq!(
+            adapter.fetch_move_by_name("thunderbolt").await.unwrap(),
+            pokemon_move
+        );
+        assert!(matches!(
+            adapter.fetch_move_by_name("surf").await,
+            Err(BattleAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_mocked_type_by_name() {
+        let pokemon_type = PokemonType {
+            name: "electric".to_string(),
+            damage_relations: DamageRelations::default(),
+            past_damage_relations: vec![],
+        };
+        let adapter = MockBattleAdapter::new().with_type(pokemon_type.clone());
+
+        assert_eq!(
+            adapter.fetch_type_by_name("electric").await.unwrap(),
+            pokemon_type
+        );
+        assert!(matches!(
+            adapter.fetch_type_by_name("water").await,
+            Err(BattleAdapterError::NotFound)
+        ));
+    }
+}
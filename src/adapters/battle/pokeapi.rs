@@ -0,0 +1,556 @@
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::adapters::battle::pokeapi::ability::AbilityResponse;
+use crate::adapters::battle::pokeapi::moves::MoveResponse;
+use crate::adapters::battle::pokeapi::resource::{
+    EffectEntry, FlavorTextEntry, LANGUAGE_ENGLISH, NamedApiResource,
+};
+use crate::adapters::battle::pokeapi::types::{TypeRelations, TypeResponse};
+use crate::adapters::battle::{BattleAdapter, BattleAdapterError, BattleAdapterResult};
+use crate::adapters::pokeapi;
+use crate::log;
+use crate::model::{Ability, DamageClass, DamageRelations, Move, PastDamageRelations, PokemonType};
+use crate::monitoring::observe_upstream;
+
+mod ability;
+mod moves;
+mod resource;
+mod types;
+
+const DEFAULT_API_TIMEOUT: Duration = Duration::from_secs(30);
+/// The name of the adapter in the metrics.
+const ADAPTER_NAME: &str = "battle";
+
+/// PokeApiBattleAdapter is an adapter for the abilities, moves and types of the PokeAPI service.
+#[derive(Clone, Debug)]
+pub struct PokeApiBattleAdapter {
+    client: reqwest::Client,
+    endpoint: Url,
+    timeout: Duration,
+}
+
+impl Default for PokeApiBattleAdapter {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: Url::parse("https://pokeapi.co/api/v2/").expect("correct URL"),
+            timeout: DEFAULT_API_TIMEOUT,
+        }
+    }
+}
+
+impl PokeApiBattleAdapter {
+    /// Creates a new [`PokeApiBattleAdapter`] with the given endpoint [`Url`] and timeout.
+    pub fn new(endpoint: impl Into<Url>, timeout: Duration) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: endpoint.into(),
+            timeout,
+        }
+    }
+
+    /// Builds the URL for fetching the resource with the given name from the given collection.
+    #[inline]
+    fn resource_endpoint(&self, collection: &str, name: &str) -> BattleAdapterResult<Url> {
+        self.endpoint
+            .join(&format!("{collection}/"))
+            .and_then(|url| url.join(name))
+            .map_err(|err| BattleAdapterError::ParseError(err.to_string()))
+    }
+
+    /// Fetches the resource at the given [`Url`] and deserializes it.
+    async fn fetch<R>(&self, url: Url) -> BattleAdapterResult<R>
+    where
+        R: DeserializeOwned + std::fmt::Debug,
+    {
+        observe_upstream(ADAPTER_NAME, async {
+            let response = self
+                .client
+                .get(url.clone())
+                .headers(log::trace_context_headers())
+                .timeout(self.timeout)
+                .send()
+                .await
+                .map_err(BattleAdapterError::from)?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                debug!("Resource '{url}' not found");
+                return Err(BattleAdapterError::NotFound);
+            }
+            if !response.status().is_success() {
+                error!(
+                    "Failed to fetch '{url}': HTTP {status}",
+                    status = response.status()
+                );
+                return Err(BattleAdapterError::UnexpectedResponse(format!(
+                    "HTTP {}",
+                    response.status()
+                )));
+            }
+
+            // parse response
+            debug!("Parsing response for '{url}'");
+            let resource: R = response
+                .json()
+                .await
+                .map_err(|err| BattleAdapterError::ParseError(err.to_string()))?;
+            trace!("Parsed resource data: {resource:?}");
+
+            Ok(resource)
+        })
+        .await
+    }
+
+    /// Extracts the [`Ability`] data from the given ability response.
+    fn get_ability_data(ability: AbilityResponse) -> BattleAdapterResult<Ability> {
+        let (effect, short_effect) =
+            Self::english_effect(ability.effect_entries, ability.flavor_text_entries, None)?;
+
+        Ok(Ability {
+            name: ability.name,
+            effect,
+            short_effect,
+        })
+    }
+
+    /// Extracts the [`Move`] data from the given move response.
+    fn get_move_data(pokemon_move: MoveResponse) -> BattleAdapterResult<Move> {
+        let damage_class = match pokemon_move.damage_class.name.as_str() {
+            "physical" => DamageClass::Physical,
+            "special" => DamageClass::Special,
+            "status" => DamageClass::Status,
+            other => {
+                return Err(BattleAdapterError::UnexpectedResponse(format!(
+                    "Unknown damage class: {other}"
+                )));
+            }
+        };
+        let (effect, short_effect) = Self::english_effect(
+            pokemon_move.effect_entries,
+            pokemon_move.flavor_text_entries,
+            pokemon_move.effect_chance,
+        )?;
+
+        Ok(Move {
+            name: pokemon_move.name,
+            move_type: pokemon_move.move_type.name,
+            damage_class,
+            power: pokemon_move.power,
+            accuracy: pokemon_move.accuracy,
+            pp: pokemon_move.pp,
+            priority: pokemon_move.priority,
+            effect,
+            short_effect,
+        })
+    }
+
+    /// Extracts the [`PokemonType`] data from the given type response.
+    fn get_type_data(pokemon_type: TypeResponse) -> BattleAdapterResult<PokemonType> {
+        let mut past_damage_relations = pokemon_type
+            .past_damage_relations
+            .into_iter()
+            .map(|past| {
+                Ok(PastDamageRelations {
+                    generation: parse_generation(&past.generation.name)?,
+                    damage_relations: past.damage_relations.into(),
+                })
+            })
+            .collect::<BattleAdapterResult<Vec<_>>>()?;
+        past_damage_relations.sort_by_key(|past| past.generation);
+
+        Ok(PokemonType {
+            name: pokemon_type.name,
+            damage_relations: pokemon_type.damage_relations.into(),
+            past_damage_relations,
+        })
+    }
+
+    /// Gets the English effect and short effect, replacing the effect chance placeholder and
+    /// cleaning up whitespaces.
+    ///
+    /// Many recent abilities and moves have no effect entries, so the latest English flavor text
+    /// is used instead.
+    fn english_effect(
+        effect_entries: Vec<EffectEntry>,
+        flavor_text_entries: Vec<FlavorTextEntry>,
+        effect_chance: Option<u8>,
+    ) -> BattleAdapterResult<(String, String)> {
+        let clean = |text: &str| {
+            let text = match effect_chance {
+                Some(chance) => text.replace("$effect_chance", &chance.to_string()),
+                None => text.to_string(),
+            };
+            text.split_whitespace().collect::<Vec<_>>().join(" ")
+        };
+
+        if let Some(entry) = effect_entries
+            .into_iter()
+            .find(|entry| entry.language.name == LANGUAGE_ENGLISH)
+        {
+            return Ok((clean(&entry.effect), clean(&entry.short_effect)));
+        }
+
+        flavor_text_entries
+            .into_iter()
+            .rfind(|entry| entry.language.name == LANGUAGE_ENGLISH)
+            .map(|entry| {
+                let text = clean(&entry.flavor_text);
+                (text.clone(), text)
+            })
+            .ok_or_else(|| {
+                BattleAdapterError::UnexpectedResponse("No English effect found".to_string())
+            })
+    }
+}
+
+impl From<TypeRelations> for DamageRelations {
+    fn from(relations: TypeRelations) -> Self {
+        let names = |resources: Vec<NamedApiResource>| {
+            resources
+                .into_iter()
+                .map(|resource| resource.name)
+                .collect()
+        };
+
+        Self {
+            no_damage_from: names(relations.no_damage_from),
+            half_damage_from: names(relations.half_damage_from),
+            double_damage_from: names(relations.double_damage_from),
+            no_damage_to: names(relations.no_damage_to),
+            half_damage_to: names(relations.half_damage_to),
+            double_damage_to: names(relations.double_damage_to),
+        }
+    }
+}
+
+/// Parses a PokeAPI generation name (e.g. `generation-iv`) into the generation number.
+fn parse_generation(name: &str) -> BattleAdapterResult<u8> {
+    pokeapi::parse_generation(name).ok_or_else(|| {
+        BattleAdapterError::UnexpectedResponse(format!("Invalid generation: {name}"))
+    })
+}
+
+impl From<reqwest::Error> for BattleAdapterError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err.to_string())
+        } else {
+            Self::NetworkError(err.to_string())
+        }
+    }
+}
+
+impl BattleAdapter for PokeApiBattleAdapter {
+    async fn fetch_ability_by_name(&self, name: &str) -> BattleAdapterResult<Ability> {
+        let url = self.resource_endpoint("ability", name)?;
+        let ability = Self::get_ability_data(self.fetch(url).await?)?;
+        debug!("Found ability: {ability:?}");
+        Ok(ability)
+    }
+
+    async fn fetch_move_by_name(&self, name: &str) -> BattleAdapterResult<Move> {
+        let url = self.resource_endpoint("move", name)?;
+        let pokemon_move = Self::get_move_data(self.fetch(url).await?)?;
+        debug!("Found move: {pokemon_move:?}");
+        Ok(pokemon_move)
+    }
+
+    async fn fetch_type_by_name(&self, name: &str) -> BattleAdapterResult<PokemonType> {
+        let url = self.resource_endpoint("type", name)?;
+        let pokemon_type = Self::get_type_data(self.fetch(url).await?)?;
+        debug!("Found type: {pokemon_type:?}");
+        Ok(pokemon_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::battle::pokeapi::types::TypeRelationsPast;
+
+    #[test]
+    fn test_should_build_default_adapter() {
+        let adapter = PokeApiBattleAdapter::default();
+        assert_eq!(adapter.endpoint.as_str(), "https://pokeapi.co/api/v2/");
+    }
+
+    #[test]
+    fn test_should_build_custom_adapter() {
+        let custom_url = Url::parse("https://custom-pokeapi.com/api/").unwrap();
+        let adapter = PokeApiBattleAdapter::new(custom_url.clone(), Duration::from_secs(10));
+        assert_eq!(adapter.endpoint, custom_url);
+        assert_eq!(adapter.timeout, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_should_get_resource_url() {
+        let adapter = PokeApiBattleAdapter::default();
+        assert_eq!(
+            adapter
+                .resource_endpoint("move", "thunderbolt")
+                .unwrap()
+                .as_str(),
+            "https://pokeapi.co/api/v2/move/thunderbolt"
+        );
+        assert_eq!(
+            adapter
+                .resource_endpoint("ability", "static")
+                .unwrap()
+                .as_str(),
+            "https://pokeapi.co/api/v2/ability/static"
+        );
+        assert_eq!(
+            adapter
+                .resource_endpoint("type", "electric")
+                .unwrap()
+                .as_str(),
+            "https://pokeapi.co/api/v2/type/electric"
+        );
+    }
+
+    #[test]
+    fn test_should_parse_generation() {
+        assert_eq!(parse_generation("generation-i").unwrap(), 1);
+        assert_eq!(parse_generation("generation-iv").unwrap(), 4);
+        assert_eq!(parse_generation("generation-v").unwrap(), 5);
+        assert_eq!(parse_generation("generation-viii").unwrap(), 8);
+        assert_eq!(parse_generation("generation-ix").unwrap(), 9);
+        assert!(parse_generation("generation-z").is_err());
+        assert!(parse_generation("gen-1").is_err());
+        assert!(parse_generation("generation-").is_err());
+    }
+
+    #[test]
+    fn test_should_get_ability_data() {
+        let ability = PokeApiBattleAdapter::get_ability_data(AbilityResponse {
+            name: "static".to_string(),
+            effect_entries: vec![
+                effect_entry("Paralizza.", "Paralizza.", "it"),
+                effect_entry(
+                    "Whenever a move makes contact\nwith this Pokémon, the move's user has a 30% chance of being paralyzed.",
+                    "Has a 30% chance of paralyzing attacking Pokémon on contact.",
+                    LANGUAGE_ENGLISH,
+                ),
+            ],
+            flavor_text_entries: vec![],
+        })
+        .unwrap();
+        assert_eq!(ability.name, "static");
+        assert_eq!(
+            ability.effect,
+            "Whenever a move makes contact with this Pokémon, the move's user has a 30% chance of being paralyzed."
+        );
+        assert_eq!(
+            ability.short_effect,
+            "Has a 30% chance of paralyzing attacking Pokémon on contact."
+        );
+    }
+
+    #[test]
+    fn test_should_get_move_data() {
+        let pokemon_move = PokeApiBattleAdapter::get_move_data(MoveResponse {
+            name: "thunderbolt".to_string(),
+            accuracy: Some(100),
+            damage_class: named("special"),
+            effect_chance: Some(10),
+            effect_entries: vec![effect_entry(
+                "Inflicts regular damage. Has a $effect_chance% chance to paralyze the target.",
+                "Has a $effect_chance% chance to paralyze the target.",
+                LANGUAGE_ENGLISH,
+            )],
+            flavor_text_entries: vec![],
+            power: Some(90),
+            pp: Some(15),
+            priority: 0,
+            move_type: named("electric"),
+        })
+        .unwrap();
+        assert_eq!(pokemon_move.name, "thunderbolt");
+        assert_eq!(pokemon_move.move_type, "electric");
+        assert_eq!(pokemon_move.damage_class, DamageClass::Special);
+        assert_eq!(pokemon_move.power, Some(90));
+        assert_eq!(pokemon_move.accuracy, Some(100));
+        assert_eq!(pokemon_move.pp, Some(15));
+        assert_eq!(pokemon_move.priority, 0);
+        assert_eq!(
+            pokemon_move.effect,
+            "Inflicts regular damage. Has a 10% chance to paralyze the target."
+        );
+        assert_eq!(
+            pokemon_move.short_effect,
+            "Has a 10% chance to paralyze the target."
+        );
+    }
+
+    #[test]
+    fn test_should_get_move_data_from_flavor_text() {
+        let pokemon_move = PokeApiBattleAdapter::get_move_data(MoveResponse {
+            name: "tera-starstorm".to_string(),
+            accuracy: Some(100),
+            damage_class: named("special"),
+            effect_chance: None,
+            effect_entries: vec![],
+            flavor_text_entries: vec![
+                flavor_text_entry("An old\ndescription.", LANGUAGE_ENGLISH),
+                flavor_text_entry("A starry\nattack.", LANGUAGE_ENGLISH),
+                flavor_text_entry("Un attacco stellare.", "it"),
+            ],
+            power: Some(120),
+            pp: Some(5),
+            priority: 0,
+            move_type: named("normal"),
+        })
+        .unwrap();
+        assert_eq!(pokemon_move.effect, "A starry attack.");
+        assert_eq!(pokemon_move.short_effect, "A starry attack.");
+    }
+
+    #[test]
+    fn test_should_not_get_ability_data_without_english_effect() {
+        let result = PokeApiBattleAdapter::get_ability_data(AbilityResponse {
+            name: "static".to_string(),
+            effect_entries: vec![effect_entry("Paralizza.", "Paralizza.", "it")],
+            flavor_text_entries: vec![flavor_text_entry("Paralizza.", "it")],
+        });
+        assert!(matches!(
+            result,
+            Err(BattleAdapterError::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_should_not_get_move_data_with_unknown_damage_class() {
+        let result = PokeApiBattleAdapter::get_move_data(MoveResponse {
+            name: "thunderbolt".to_string(),
+            accuracy: Some(100),
+            damage_class: named("magic"),
+            effect_chance: None,
+            effect_entries: vec![effect_entry("effect", "short", LANGUAGE_ENGLISH)],
+            flavor_text_entries: vec![],
+            power: Some(90),
+            pp: Some(15),
+            priority: 0,
+            move_type: named("electric"),
+        });
+        assert!(matches!(
+            result,
+            Err(BattleAdapterError::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_should_get_type_data() {
+        let pokemon_type = PokeApiBattleAdapter::get_type_data(TypeResponse {
+            name: "fire".to_string(),
+            damage_relations: relations(&["steel"]),
+            past_damage_relations: vec![
+                TypeRelationsPast {
+                    generation: named("generation-v"),
+                    damage_relations: relations(&["steel", "bug"]),
+                },
+                TypeRelationsPast {
+                    generation: named("generation-i"),
+                    damage_relations: relations(&[]),
+                },
+            ],
+        })
+        .unwrap();
+        assert_eq!(pokemon_type.name, "fire");
+        assert_eq!(
+            pokemon_type.damage_relations.double_damage_to,
+            vec!["steel".to_string()]
+        );
+        assert_eq!(pokemon_type.past_damage_relations.len(), 2);
+        assert_eq!(pokemon_type.past_damage_relations[0].generation, 1);
+        assert_eq!(pokemon_type.past_damage_relations[1].generation, 5);
+        assert_eq!(
+            pokemon_type.past_damage_relations[1]
+                .damage_relations
+                .double_damage_to,
+            vec!["steel".to_string(), "bug".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_move_by_name() {
+        let adapter = PokeApiBattleAdapter::default();
+        let pokemon_move = adapter.fetch_move_by_name("thunderbolt").await.unwrap();
+        assert_eq!(pokemon_move.name, "thunderbolt");
+        assert_eq!(pokemon_move.move_type, "electric");
+        assert_eq!(pokemon_move.damage_class, DamageClass::Special);
+        assert_eq!(pokemon_move.power, Some(90));
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_ability_by_name() {
+        let adapter = PokeApiBattleAdapter::default();
+        let ability = adapter.fetch_ability_by_name("static").await.unwrap();
+        assert_eq!(ability.name, "static");
+        assert!(!ability.effect.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_type_by_name() {
+        let adapter = PokeApiBattleAdapter::default();
+        let pokemon_type = adapter.fetch_type_by_name("fire").await.unwrap();
+        assert_eq!(pokemon_type.name, "fire");
+        assert!(
+            pokemon_type
+                .damage_relations
+                .double_damage_to
+                .contains(&"grass".to_string())
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_return_error_on_unexisting_move() {
+        let adapter = PokeApiBattleAdapter::default();
+        let result = adapter
+            .fetch_move_by_name("nonexistentmove")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(result, BattleAdapterError::NotFound),
+            "expected NotFound error, got {result:?}"
+        );
+    }
+
+    fn named(name: &str) -> NamedApiResource {
+        NamedApiResource {
+            name: name.to_string(),
+        }
+    }
+
+    fn effect_entry(effect: &str, short_effect: &str, language: &str) -> EffectEntry {
+        EffectEntry {
+            effect: effect.to_string(),
+            short_effect: short_effect.to_string(),
+            language: named(language),
+        }
+    }
+
+    fn flavor_text_entry(flavor_text: &str, language: &str) -> FlavorTextEntry {
+        FlavorTextEntry {
+            flavor_text: flavor_text.to_string(),
+            language: named(language),
+        }
+    }
+
+    fn relations(double_damage_to: &[&str]) -> TypeRelations {
+        TypeRelations {
+            no_damage_to: vec![],
+            half_damage_to: vec![],
+            double_damage_to: double_damage_to.iter().map(|name| named(name)).collect(),
+            no_damage_from: vec![],
+            half_damage_from: vec![],
+            double_damage_from: vec![],
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use super::resource::{EffectEntry, FlavorTextEntry};
+
+/// This struct represents an ability returned by the `api/v2/ability/:ability` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct AbilityResponse {
+    pub name: String,
+    pub effect_entries: Vec<EffectEntry>,
+    pub flavor_text_entries: Vec<FlavorTextEntry>,
+}
@@ -0,0 +1,19 @@
+use super::resource::{EffectEntry, FlavorTextEntry, NamedApiResource};
+
+/// This struct represents a move returned by the `api/v2/move/:move` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct MoveResponse {
+    pub name: String,
+    pub accuracy: Option<u8>,
+    pub damage_class: NamedApiResource,
+    pub effect_chance: Option<u8>,
+    pub effect_entries: Vec<EffectEntry>,
+    pub flavor_text_entries: Vec<FlavorTextEntry>,
+    pub power: Option<u16>,
+    pub pp: Option<u8>,
+    pub priority: i8,
+    #[serde(rename = "type")]
+    pub move_type: NamedApiResource,
+}
@@ -0,0 +1,26 @@
+pub const LANGUAGE_ENGLISH: &str = "en";
+
+/// This struct represents a reference to another resource returned by the PokeAPI.
+///
+/// Only the name is relevant for the application, so the URL is discarded.
+#[derive(Debug, serde::Deserialize)]
+pub struct NamedApiResource {
+    pub name: String,
+}
+
+/// This struct represents an effect description of an ability or of a move in a given language.
+#[derive(Debug, serde::Deserialize)]
+pub struct EffectEntry {
+    pub effect: String,
+    pub short_effect: String,
+    pub language: NamedApiResource,
+}
+
+/// This struct represents the description of an ability or of a move in a game, in a given language.
+///
+/// It is used when there is no effect description, as for many recent abilities and moves.
+#[derive(Debug, serde::Deserialize)]
+pub struct FlavorTextEntry {
+    pub flavor_text: String,
+    pub language: NamedApiResource,
+}
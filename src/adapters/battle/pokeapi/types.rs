@@ -0,0 +1,31 @@
+use super::resource::NamedApiResource;
+
+/// This struct represents a type returned by the `api/v2/type/:type` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TypeResponse {
+    pub name: String,
+    pub damage_relations: TypeRelations,
+    pub past_damage_relations: Vec<TypeRelationsPast>,
+}
+
+/// This struct represents the damage relations of a type with the other types.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TypeRelations {
+    pub no_damage_to: Vec<NamedApiResource>,
+    pub half_damage_to: Vec<NamedApiResource>,
+    pub double_damage_to: Vec<NamedApiResource>,
+    pub no_damage_from: Vec<NamedApiResource>,
+    pub half_damage_from: Vec<NamedApiResource>,
+    pub double_damage_from: Vec<NamedApiResource>,
+}
+
+/// This struct represents the damage relations a type had up to the given generation.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct TypeRelationsPast {
+    pub generation: NamedApiResource,
+    pub damage_relations: TypeRelations,
+}
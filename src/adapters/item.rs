@@ -0,0 +1,44 @@
+//! This module provides the API client adapter for fetching items and berries from an external API.
+
+#[cfg(test)]
+mod mock;
+mod pokeapi;
+
+#[cfg(test)]
+pub use self::mock::MockItemAdapter;
+pub use self::pokeapi::PokeApiItemAdapter;
+use crate::model::{Berry, Item};
+
+/// The result type for [`ItemAdapter`] operations
+pub type ItemAdapterResult<T> = Result<T, ItemAdapterError>;
+
+/// Errors that can occur while interacting with the [`ItemAdapter`]
+#[derive(Debug, thiserror::Error)]
+pub enum ItemAdapterError {
+    #[error("Resource not found")]
+    NotFound,
+    #[error("Network error: {0}")]
+    NetworkError(String),
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+    #[error("Parse error: {0}")]
+    ParseError(String),
+    #[error("Unexpected response from the API: {0}")]
+    UnexpectedResponse(String),
+}
+
+/// The ItemAdapter trait defines the interface for fetching items and berries
+/// from an external API.
+pub trait ItemAdapter: Send + Sync {
+    /// fetch an [`Item`] by its name from the external API
+    fn fetch_item_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = ItemAdapterResult<Item>> + Send;
+
+    /// fetch a [`Berry`] by its name from the external API
+    fn fetch_berry_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = ItemAdapterResult<Berry>> + Send;
+}
@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use crate::adapters::item::{ItemAdapter, ItemAdapterError, ItemAdapterResult};
+use crate::model::{Berry, Item};
+
+/// Mock implementation of the ItemAdapter for testing purposes.
+///
+/// It simulates fetching items and berries without making actual network requests.
+/// It returns the predefined data registered with the given name, or
+/// [`ItemAdapterError::NotFound`] if there is none.
+#[derive(Default)]
+pub struct MockItemAdapter {
+    items: HashMap<String, Item>,
+    berries: HashMap<String, Berry>,
+}
+
+impl MockItemAdapter {
+    /// Creates a new instance of the [`MockItemAdapter`] without any data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the given [`Item`] into the mock.
+    pub fn with_item(mut self, item: Item) -> Self {
+        self.items.insert(item.name.clone(), item);
+        self
+    }
+
+    /// Registers the given [`Berry`] into the mock.
+    pub fn with_berry(mut self, berry: Berry) -> Self {
+        self.berries.insert(berry.name.clone(), berry);
+        self
+    }
+}
+
+impl ItemAdapter for MockItemAdapter {
+    async fn fetch_item_by_name(&self, name: &str) -> ItemAdapterResult<Item> {
+        self.items
+            .get(name)
+            .cloned()
+            .ok_or(ItemAdapterError::NotFound)
+    }
+
+    async fn fetch_berry_by_name(&self, name: &str) -> ItemAdapterResult<Berry> {
+        self.berries
+            .get(name)
+            .cloned()
+            .ok_or(ItemAdapterError::NotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::model::BerryFlavor;
+
+    #[tokio::test]
+    async fn test_should_fetch_mocked_item_by_name() {
+        let item = Item {
+            name: "potion".to_string(),
+            category: "healing".to_string(),
+            cost: 200,
+            fling_power: Some(30),
+            fling_effect: None,
+            attributes: vec!["consumable".to_string()],
+            effect: "Restores 20 HP.".to_string(),
+            short_effect: "Restores 20 HP.".to_string(),
+        };
+        let adapter = MockItemAdapter::new().with_item(item.clone());
+
+        assert_eq!(adapter.fetch_item_by_name("potion").await.unwrap(), item);
+        assert!(matches!(
+            adapter.fetch_item_by_name("elixir").await,
+            Err(ItemAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_mocked_berry_by_name() {
+        let berry = Berry {
+            name: "cheri".to_string(),
+            item: "cheri-berry".to_string(),
+            firmness: "soft".to_string(),
+            flavors: vec![BerryFlavor {
+                name: "spicy".to_string(),
+                potency: 10,
+            }],
+            natural_gift_type: "fire".to_string(),
+            natural_gift_power: 60,
+            size: 20,
+            smoothness: 25,
+            growth_time: 3,
+            max_harvest: 5,
+            effect: "Cures paralysis.".to_string(),
+            short_effect: "Cures paralysis.".to_string(),
+        };
+        let adapter = MockItemAdapter::new().with_berry(berry.clone());
+
+        assert_eq!(adapter.fetch_berry_by_name("cheri").await.unwrap(), berry);
+        assert!(matches!(
+            adapter.fetch_berry_by_name("oran").await,
+            Err(ItemAdapterError::NotFound)
+        ));
+    }
+}
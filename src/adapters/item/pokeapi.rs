@@ -0,0 +1,454 @@
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::adapters::item::pokeapi::berry::BerryResponse;
+use crate::adapters::item::pokeapi::item::{ItemEffectEntry, ItemFlavorTextEntry, ItemResponse};
+use crate::adapters::item::pokeapi::resource::LANGUAGE_ENGLISH;
+use crate::adapters::item::{ItemAdapter, ItemAdapterError, ItemAdapterResult};
+use crate::log;
+use crate::model::{Berry, BerryFlavor, Item};
+use crate::monitoring::observe_upstream;
+
+mod berry;
+mod item;
+mod resource;
+
+const DEFAULT_API_TIMEOUT: Duration = Duration::from_secs(30);
+/// The name of the adapter in the metrics.
+const ADAPTER_NAME: &str = "item";
+
+/// PokeApiItemAdapter is an adapter for the items and berries of the PokeAPI service.
+#[derive(Clone, Debug)]
+pub struct PokeApiItemAdapter {
+    client: reqwest::Client,
+    endpoint: Url,
+    timeout: Duration,
+}
+
+impl Default for PokeApiItemAdapter {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: Url::parse("https://pokeapi.co/api/v2/").expect("correct URL"),
+            timeout: DEFAULT_API_TIMEOUT,
+        }
+    }
+}
+
+impl PokeApiItemAdapter {
+    /// Creates a new [`PokeApiItemAdapter`] with the given endpoint [`Url`] and timeout.
+    pub fn new(endpoint: impl Into<Url>, timeout: Duration) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: endpoint.into(),
+            timeout,
+        }
+    }
+
+    /// Builds the URL for fetching the resource with the given name from the given collection.
+    #[inline]
+    fn resource_endpoint(&self, collection: &str, name: &str) -> ItemAdapterResult<Url> {
+        self.endpoint
+            .join(&format!("{collection}/"))
+            .and_then(|url| url.join(name))
+            .map_err(|err| ItemAdapterError::ParseError(err.to_string()))
+    }
+
+    /// Fetches the resource at the given [`Url`] and deserializes it.
+    async fn fetch<R>(&self, url: Url) -> ItemAdapterResult<R>
+    where
+        R: DeserializeOwned + std::fmt::Debug,
+    {
+        observe_upstream(ADAPTER_NAME, async {
+            let response = self
+                .client
+                .get(url.clone())
+                .headers(log::trace_context_headers())
+                .timeout(self.timeout)
+                .send()
+                .await
+                .map_err(ItemAdapterError::from)?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                error!("Resource '{url}' not found");
+                return Err(ItemAdapterError::NotFound);
+            }
+            if !response.status().is_success() {
+                error!(
+                    "Failed to fetch '{url}': HTTP {status}",
+                    status = response.status()
+                );
+                return Err(ItemAdapterError::UnexpectedResponse(format!(
+                    "HTTP {}",
+                    response.status()
+                )));
+            }
+
+            // parse response
+            debug!("Parsing response for '{url}'");
+            let resource: R = response
+                .json()
+                .await
+                .map_err(|err| ItemAdapterError::ParseError(err.to_string()))?;
+            trace!("Parsed resource data: {resource:?}");
+
+            Ok(resource)
+        })
+        .await
+    }
+
+    /// Extracts the [`Item`] data from the given item response.
+    fn get_item_data(item: ItemResponse) -> ItemAdapterResult<Item> {
+        let (effect, short_effect) =
+            Self::english_effect(item.effect_entries, item.flavor_text_entries)?;
+
+        Ok(Item {
+            name: item.name,
+            category: item.category.name,
+            cost: item.cost,
+            fling_power: item.fling_power,
+            fling_effect: item.fling_effect.map(|effect| effect.name),
+            attributes: item
+                .attributes
+                .into_iter()
+                .map(|attribute| attribute.name)
+                .collect(),
+            effect,
+            short_effect,
+        })
+    }
+
+    /// Extracts the [`Berry`] data from the given berry response and the [`Item`] of the berry.
+    fn get_berry_data(berry: BerryResponse, item: Item) -> Berry {
+        Berry {
+            name: berry.name,
+            item: item.name,
+            firmness: berry.firmness.name,
+            flavors: berry
+                .flavors
+                .into_iter()
+                .map(|flavor| BerryFlavor {
+                    name: flavor.flavor.name,
+                    potency: flavor.potency,
+                })
+                .collect(),
+            natural_gift_type: berry.natural_gift_type.name,
+            natural_gift_power: berry.natural_gift_power,
+            size: berry.size,
+            smoothness: berry.smoothness,
+            growth_time: berry.growth_time,
+            max_harvest: berry.max_harvest,
+            effect: item.effect,
+            short_effect: item.short_effect,
+        }
+    }
+
+    /// Gets the English effect and short effect, cleaning up their whitespaces.
+    ///
+    /// Many recent items have no effect entries, so the latest English flavor text is used instead.
+    fn english_effect(
+        effect_entries: Vec<ItemEffectEntry>,
+        flavor_text_entries: Vec<ItemFlavorTextEntry>,
+    ) -> ItemAdapterResult<(String, String)> {
+        if let Some(entry) = effect_entries
+            .into_iter()
+            .find(|entry| entry.language.name == LANGUAGE_ENGLISH)
+        {
+            return Ok((
+                normalize_text(&entry.effect),
+                normalize_text(&entry.short_effect),
+            ));
+        }
+
+        flavor_text_entries
+            .into_iter()
+            .rfind(|entry| entry.language.name == LANGUAGE_ENGLISH)
+            .map(|entry| {
+                let text = normalize_text(&entry.text);
+                (text.clone(), text)
+            })
+            .ok_or_else(|| {
+                ItemAdapterError::UnexpectedResponse("No English effect found".to_string())
+            })
+    }
+}
+
+/// Collapses the whitespaces of an effect text, which are used by the PokeAPI for layout purposes.
+///
+/// e.g. `Used on a party Pokémon\n:   Restores 20 HP.` becomes `Used on a party Pokémon: Restores 20 HP.`
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace(" :", ":")
+}
+
+impl From<reqwest::Error> for ItemAdapterError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err.to_string())
+        } else {
+            Self::NetworkError(err.to_string())
+        }
+    }
+}
+
+impl ItemAdapter for PokeApiItemAdapter {
+    async fn fetch_item_by_name(&self, name: &str) -> ItemAdapterResult<Item> {
+        let url = self.resource_endpoint("item", name)?;
+        let item = Self::get_item_data(self.fetch(url).await?)?;
+        debug!("Found item: {item:?}");
+        Ok(item)
+    }
+
+    async fn fetch_berry_by_name(&self, name: &str) -> ItemAdapterResult<Berry> {
+        let url = self.resource_endpoint("berry", name)?;
+        let berry: BerryResponse = self.fetch(url).await?;
+        // the effect of the berry is described by its item
+        let item = self.fetch_item_by_name(&berry.item.name).await?;
+
+        let berry = Self::get_berry_data(berry, item);
+        debug!("Found berry: {berry:?}");
+        Ok(berry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::item::pokeapi::berry::BerryFlavorMap;
+    use crate::adapters::item::pokeapi::resource::NamedApiResource;
+
+    #[test]
+    fn test_should_build_default_adapter() {
+        let adapter = PokeApiItemAdapter::default();
+        assert_eq!(adapter.endpoint.as_str(), "https://pokeapi.co/api/v2/");
+    }
+
+    #[test]
+    fn test_should_build_custom_adapter() {
+        let custom_url = Url::parse("https://custom-pokeapi.com/api/").unwrap();
+        let adapter = PokeApiItemAdapter::new(custom_url.clone(), Duration::from_secs(10));
+        assert_eq!(adapter.endpoint, custom_url);
+        assert_eq!(adapter.timeout, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_should_get_resource_url() {
+        let adapter = PokeApiItemAdapter::default();
+        assert_eq!(
+            adapter
+                .resource_endpoint("item", "potion")
+                .unwrap()
+                .as_str(),
+            "https://pokeapi.co/api/v2/item/potion"
+        );
+        assert_eq!(
+            adapter
+                .resource_endpoint("berry", "cheri")
+                .unwrap()
+                .as_str(),
+            "https://pokeapi.co/api/v2/berry/cheri"
+        );
+    }
+
+    #[test]
+    fn test_should_normalize_text() {
+        assert_eq!(
+            normalize_text("Used on a party Pokémon\n:   Restores 20 HP."),
+            "Used on a party Pokémon: Restores 20 HP."
+        );
+        assert_eq!(normalize_text("  Restores\n20 HP. "), "Restores 20 HP.");
+    }
+
+    #[test]
+    fn test_should_get_item_data() {
+        let item = PokeApiItemAdapter::get_item_data(ItemResponse {
+            name: "potion".to_string(),
+            cost: 200,
+            fling_power: Some(30),
+            fling_effect: None,
+            category: named("healing"),
+            attributes: vec![named("countable"), named("consumable")],
+            effect_entries: vec![
+                ItemEffectEntry {
+                    effect: "Ripristina 20 PS.".to_string(),
+                    short_effect: "Ripristina 20 PS.".to_string(),
+                    language: named("it"),
+                },
+                ItemEffectEntry {
+                    effect: "Used on a party Pokémon\n:   Restores 20 HP.".to_string(),
+                    short_effect: "Restores 20 HP.".to_string(),
+                    language: named(LANGUAGE_ENGLISH),
+                },
+            ],
+            flavor_text_entries: vec![],
+        })
+        .unwrap();
+
+        assert_eq!(
+            item,
+            Item {
+                name: "potion".to_string(),
+                category: "healing".to_string(),
+                cost: 200,
+                fling_power: Some(30),
+                fling_effect: None,
+                attributes: vec!["countable".to_string(), "consumable".to_string()],
+                effect: "Used on a party Pokémon: Restores 20 HP.".to_string(),
+                short_effect: "Restores 20 HP.".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_get_item_data_from_flavor_text() {
+        let item = PokeApiItemAdapter::get_item_data(ItemResponse {
+            name: "ability-shield".to_string(),
+            cost: 20000,
+            fling_power: None,
+            fling_effect: None,
+            category: named("held-items"),
+            attributes: vec![],
+            effect_entries: vec![],
+            flavor_text_entries: vec![
+                ItemFlavorTextEntry {
+                    text: "An old\ndescription.".to_string(),
+                    language: named(LANGUAGE_ENGLISH),
+                },
+                ItemFlavorTextEntry {
+                    text: "A protective\nshield.".to_string(),
+                    language: named(LANGUAGE_ENGLISH),
+                },
+                ItemFlavorTextEntry {
+                    text: "Uno scudo.".to_string(),
+                    language: named("it"),
+                },
+            ],
+        })
+        .unwrap();
+
+        assert_eq!(item.effect, "A protective shield.");
+        assert_eq!(item.short_effect, "A protective shield.");
+    }
+
+    #[test]
+    fn test_should_not_get_item_data_without_english_effect() {
+        let result = PokeApiItemAdapter::get_item_data(ItemResponse {
+            name: "potion".to_string(),
+            cost: 200,
+            fling_power: None,
+            fling_effect: None,
+            category: named("healing"),
+            attributes: vec![],
+            effect_entries: vec![],
+            flavor_text_entries: vec![],
+        });
+
+        assert!(matches!(
+            result,
+            Err(ItemAdapterError::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_should_get_berry_data() {
+        let item = Item {
+            name: "cheri-berry".to_string(),
+            category: "medicine".to_string(),
+            cost: 80,
+            fling_power: Some(10),
+            fling_effect: None,
+            attributes: vec!["holdable".to_string()],
+            effect: "Held in battle: cures paralysis.".to_string(),
+            short_effect: "Cures paralysis.".to_string(),
+        };
+        let berry = PokeApiItemAdapter::get_berry_data(
+            BerryResponse {
+                name: "cheri".to_string(),
+                growth_time: 3,
+                max_harvest: 5,
+                natural_gift_power: 60,
+                natural_gift_type: named("fire"),
+                size: 20,
+                smoothness: 25,
+                firmness: named("soft"),
+                flavors: vec![
+                    BerryFlavorMap {
+                        potency: 10,
+                        flavor: named("spicy"),
+                    },
+                    BerryFlavorMap {
+                        potency: 0,
+                        flavor: named("dry"),
+                    },
+                ],
+                item: named("cheri-berry"),
+            },
+            item,
+        );
+
+        assert_eq!(
+            berry,
+            Berry {
+                name: "cheri".to_string(),
+                item: "cheri-berry".to_string(),
+                firmness: "soft".to_string(),
+                flavors: vec![
+                    BerryFlavor {
+                        name: "spicy".to_string(),
+                        potency: 10,
+                    },
+                    BerryFlavor {
+                        name: "dry".to_string(),
+                        potency: 0,
+                    },
+                ],
+                natural_gift_type: "fire".to_string(),
+                natural_gift_power: 60,
+                size: 20,
+                smoothness: 25,
+                growth_time: 3,
+                max_harvest: 5,
+                effect: "Held in battle: cures paralysis.".to_string(),
+                short_effect: "Cures paralysis.".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_item_by_name() {
+        let adapter = PokeApiItemAdapter::default();
+        let item = adapter.fetch_item_by_name("potion").await.unwrap();
+        assert_eq!(item.name, "potion");
+        assert_eq!(item.category, "healing");
+        assert_eq!(item.cost, 200);
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_berry_by_name() {
+        let adapter = PokeApiItemAdapter::default();
+        let berry = adapter.fetch_berry_by_name("cheri").await.unwrap();
+        assert_eq!(berry.item, "cheri-berry");
+        assert_eq!(berry.firmness, "soft");
+        assert_eq!(berry.flavors.len(), 5);
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_return_error_on_unexisting_item() {
+        let adapter = PokeApiItemAdapter::default();
+        let result = adapter.fetch_item_by_name("missingno").await;
+        assert!(matches!(result, Err(ItemAdapterError::NotFound)));
+    }
+
+    fn named(name: &str) -> NamedApiResource {
+        NamedApiResource {
+            name: name.to_string(),
+        }
+    }
+}
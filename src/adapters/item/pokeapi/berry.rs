@@ -0,0 +1,25 @@
+use super::resource::NamedApiResource;
+
+/// This struct represents a berry returned by the `api/v2/berry/:berry` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct BerryResponse {
+    pub name: String,
+    pub growth_time: u8,
+    pub max_harvest: u8,
+    pub natural_gift_power: u8,
+    pub natural_gift_type: NamedApiResource,
+    pub size: u16,
+    pub smoothness: u8,
+    pub firmness: NamedApiResource,
+    pub flavors: Vec<BerryFlavorMap>,
+    pub item: NamedApiResource,
+}
+
+/// This struct represents the potency of a flavor of a berry.
+#[derive(Debug, serde::Deserialize)]
+pub struct BerryFlavorMap {
+    pub potency: u8,
+    pub flavor: NamedApiResource,
+}
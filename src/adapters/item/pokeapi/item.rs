@@ -0,0 +1,33 @@
+use super::resource::NamedApiResource;
+
+/// This struct represents an item returned by the `api/v2/item/:item` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ItemResponse {
+    pub name: String,
+    pub cost: u32,
+    pub fling_power: Option<u8>,
+    pub fling_effect: Option<NamedApiResource>,
+    pub category: NamedApiResource,
+    pub attributes: Vec<NamedApiResource>,
+    pub effect_entries: Vec<ItemEffectEntry>,
+    pub flavor_text_entries: Vec<ItemFlavorTextEntry>,
+}
+
+/// This struct represents an effect description of an item in a given language.
+#[derive(Debug, serde::Deserialize)]
+pub struct ItemEffectEntry {
+    pub effect: String,
+    pub short_effect: String,
+    pub language: NamedApiResource,
+}
+
+/// This struct represents the description of an item in a game, in a given language.
+///
+/// It is used when the item has no effect description, as for most recent items.
+#[derive(Debug, serde::Deserialize)]
+pub struct ItemFlavorTextEntry {
+    pub text: String,
+    pub language: NamedApiResource,
+}
@@ -0,0 +1,9 @@
+pub const LANGUAGE_ENGLISH: &str = "en";
+
+/// This struct represents a reference to another resource returned by the PokeAPI.
+///
+/// Only the name is relevant for the application, so the URL is discarded.
+#[derive(Debug, serde::Deserialize)]
+pub struct NamedApiResource {
+    pub name: String,
+}
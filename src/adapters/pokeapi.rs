@@ -0,0 +1,46 @@
+//! This module provides the helpers shared by the adapters for the PokeAPI service.
+
+/// Parses a PokeAPI generation name (e.g. `generation-iv`) into the generation number.
+///
+/// Returns `None` if the name is not a valid generation name.
+pub fn parse_generation(name: &str) -> Option<u8> {
+    let numeral = name.strip_prefix("generation-")?;
+
+    let mut generation: i32 = 0;
+    let mut last = 0;
+    for digit in numeral.chars().rev() {
+        let value = match digit {
+            'i' => 1,
+            'v' => 5,
+            'x' => 10,
+            _ => return None,
+        };
+        if value < last {
+            generation -= value;
+        } else {
+            generation += value;
+            last = value;
+        }
+    }
+
+    u8::try_from(generation)
+        .ok()
+        .filter(|generation| *generation > 0)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_parse_generation() {
+        assert_eq!(parse_generation("generation-i"), Some(1));
+        assert_eq!(parse_generation("generation-iv"), Some(4));
+        assert_eq!(parse_generation("generation-ix"), Some(9));
+        assert_eq!(parse_generation("generation-xiv"), Some(14));
+        assert_eq!(parse_generation("generation-z"), None);
+        assert_eq!(parse_generation("gen-1"), None);
+        assert_eq!(parse_generation("generation-"), None);
+    }
+}
@@ -8,18 +8,22 @@ mod pokeapi;
 #[cfg(test)]
 pub use self::mock::MockPokedexAdapter;
 pub use self::pokeapi::PokeApiAdapter;
-use crate::model::Pokemon;
+use crate::model::{
+    Generation, Pokedex, Pokemon, PokemonEncounters, SpeciesTexts, SpriteStream, SpriteVariant,
+};
 
 /// The result type for PokedexAdapter operations
 pub type PokedexAdapterResult<T> = Result<T, PokedexAdapterError>;
 
 /// Errors that can occur while interacting with the PokedexAdapter
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum PokedexAdapterError {
     #[error("Pokémon not found")]
     NotFound,
     #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("Request timed out: {0}")]
+    Timeout(String),
     #[error("Parse error: {0}")]
     ParseError(String),
     #[error("Unexpected response from the API: {0}")]
@@ -34,4 +38,57 @@ pub trait PokedexAdapter: Send + Sync {
         &self,
         name: &str,
     ) -> impl Future<Output = PokedexAdapterResult<Pokemon>> + Send;
+
+    /// fetch the [`PokemonEncounters`] of a Pokémon by its name from the external API
+    fn fetch_encounters_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = PokedexAdapterResult<PokemonEncounters>> + Send;
+
+    /// fetch the image of the given variant of a Pokémon by its name from the external API,
+    /// streaming its content as a [`SpriteStream`].
+    ///
+    /// Returns `None` if the Pokémon has no image for the given variant.
+    fn fetch_sprite_by_name(
+        &self,
+        name: &str,
+        variant: SpriteVariant,
+    ) -> impl Future<Output = PokedexAdapterResult<Option<SpriteStream>>> + Send;
+
+    /// fetch the [`SpeciesTexts`] of a Pokémon species by its name from the external API
+    fn fetch_species_texts_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = PokedexAdapterResult<SpeciesTexts>> + Send;
+
+    /// fetch the names of all the Pokémon species and forms from the external API
+    fn fetch_pokemon_names(&self)
+    -> impl Future<Output = PokedexAdapterResult<Vec<String>>> + Send;
+
+    /// fetch a [`Generation`] by its number from the external API
+    fn fetch_generation_by_id(
+        &self,
+        id: u8,
+    ) -> impl Future<Output = PokedexAdapterResult<Generation>> + Send;
+
+    /// fetch a [`Pokedex`] by its name from the external API
+    fn fetch_pokedex_by_name(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = PokedexAdapterResult<Pokedex>> + Send;
+
+    /// fetch the names of the Pokémon of the given type from the external API
+    fn fetch_pokemon_names_by_type(
+        &self,
+        pokemon_type: &str,
+    ) -> impl Future<Output = PokedexAdapterResult<Vec<String>>> + Send;
+
+    /// fetch the names of the Pokémon species living in the given habitat from the external API
+    fn fetch_species_names_by_habitat(
+        &self,
+        habitat: &str,
+    ) -> impl Future<Output = PokedexAdapterResult<Vec<String>>> + Send;
+
+    /// check that the external API is reachable and responding
+    fn health_check(&self) -> impl Future<Output = PokedexAdapterResult<()>> + Send;
 }
@@ -1,15 +1,69 @@
-use crate::adapters::pokedex::{PokedexAdapter, PokedexAdapterResult};
-use crate::model::Pokemon;
+use std::collections::HashMap;
+
+use crate::adapters::pokedex::{PokedexAdapter, PokedexAdapterError, PokedexAdapterResult};
+use crate::model::{
+    Generation, Pokedex, Pokemon, PokemonEncounters, SpeciesTexts, Sprite, SpriteStream,
+    SpriteVariant,
+};
+
+/// The types of the PokeAPI.
+const TYPES: [&str; 18] = [
+    "normal", "fighting", "flying", "poison", "ground", "rock", "bug", "ghost", "steel", "fire",
+    "water", "grass", "electric", "psychic", "ice", "dragon", "dark", "fairy",
+];
+/// The habitats of the PokeAPI.
+const HABITATS: [&str; 9] = [
+    "cave",
+    "forest",
+    "grassland",
+    "mountain",
+    "rare",
+    "rough-terrain",
+    "sea",
+    "urban",
+    "waters-edge",
+];
 
 /// Mock implementation of the PokedexAdapter for testing purposes.
 ///
 /// It simulates fetching Pokémon data without making actual network requests.
-/// It always returns the data for a predefined [`Pokemon`].
-pub struct MockPokedexAdapter(Option<Pokemon>);
+/// It returns the [`Pokemon`] registered with the requested name if any,
+/// otherwise it always returns the data for a predefined [`Pokemon`].
+/// The encounters of a Pokémon are empty and its sprites are missing unless registered,
+/// its species texts are made up of its description unless registered,
+/// the listed names are the ones of the known Pokémon and of their forms,
+/// while generations and pokedexes are found only if registered.
+/// The Pokémon of a type or living in a habitat are the known ones matching it,
+/// as long as it's one of the types or habitats of the PokeAPI.
+/// Fetching a Pokémon registered as failing returns its error,
+/// and the health check succeeds unless an error is set.
+pub struct MockPokedexAdapter {
+    /// the [`Pokemon`] returned for names which are not registered
+    pokemon: Option<Pokemon>,
+    /// the registered [`Pokemon`], by name
+    registered_pokemon: HashMap<String, Pokemon>,
+    /// the errors returned when fetching the failing Pokémon, by name
+    failures: HashMap<String, PokedexAdapterError>,
+    /// the registered [`PokemonEncounters`], by Pokémon name
+    encounters: HashMap<String, PokemonEncounters>,
+    /// the registered [`Sprite`]s, by Pokémon name and variant
+    sprites: HashMap<(String, SpriteVariant), Sprite>,
+    /// the registered [`SpeciesTexts`], by species name
+    species_texts: HashMap<String, SpeciesTexts>,
+    /// the registered [`Generation`]s, by id
+    generations: HashMap<u8, Generation>,
+    /// the registered [`Pokedex`]es, by name
+    pokedexes: HashMap<String, Pokedex>,
+    /// the error returned by the health check, if unhealthy
+    health_error: Option<PokedexAdapterError>,
+}
 
 impl From<Pokemon> for MockPokedexAdapter {
     fn from(pokemon: Pokemon) -> Self {
-        MockPokedexAdapter(Some(pokemon))
+        MockPokedexAdapter {
+            pokemon: Some(pokemon),
+            ..Self::not_found()
+        }
     }
 }
 
@@ -21,23 +75,201 @@ impl MockPokedexAdapter {
 
     /// Creates a new instance of the [`MockPokedexAdapter`] which simulates a "not found" scenario.
     pub fn not_found() -> Self {
-        Self(None)
+        Self {
+            pokemon: None,
+            registered_pokemon: HashMap::new(),
+            failures: HashMap::new(),
+            encounters: HashMap::new(),
+            sprites: HashMap::new(),
+            species_texts: HashMap::new(),
+            generations: HashMap::new(),
+            pokedexes: HashMap::new(),
+            health_error: None,
+        }
+    }
+
+    /// Registers the given [`Pokemon`], which is returned when fetched by its name.
+    pub fn with_pokemon(mut self, pokemon: Pokemon) -> Self {
+        self.registered_pokemon
+            .insert(pokemon.name.clone(), pokemon);
+        self
+    }
+
+    /// Registers the given error, which is returned when fetching the Pokémon with the given name.
+    pub fn with_failure(mut self, name: &str, err: PokedexAdapterError) -> Self {
+        self.failures.insert(name.to_string(), err);
+        self
+    }
+
+    /// Registers the given [`PokemonEncounters`], which are returned when fetched by the Pokémon name.
+    pub fn with_encounters(mut self, encounters: PokemonEncounters) -> Self {
+        self.encounters.insert(encounters.name.clone(), encounters);
+        self
+    }
+
+    /// Registers the given [`Sprite`], which is returned when fetched by the Pokémon name and variant.
+    pub fn with_sprite(mut self, name: &str, variant: SpriteVariant, sprite: Sprite) -> Self {
+        self.sprites.insert((name.to_string(), variant), sprite);
+        self
+    }
+
+    /// Registers the given [`SpeciesTexts`], which are returned when fetched by the species name.
+    pub fn with_species_texts(mut self, species_texts: SpeciesTexts) -> Self {
+        self.species_texts
+            .insert(species_texts.name.clone(), species_texts);
+        self
+    }
+
+    /// Registers the given [`Generation`], which is returned when fetched by its id.
+    pub fn with_generation(mut self, generation: Generation) -> Self {
+        self.generations.insert(generation.id, generation);
+        self
+    }
+
+    /// Registers the given [`Pokedex`], which is returned when fetched by its name.
+    pub fn with_pokedex(mut self, pokedex: Pokedex) -> Self {
+        self.pokedexes.insert(pokedex.name.clone(), pokedex);
+        self
+    }
+
+    /// Returns the default and the registered [`Pokemon`].
+    fn known_pokemon(&self) -> impl Iterator<Item = &Pokemon> {
+        self.pokemon.iter().chain(self.registered_pokemon.values())
+    }
+
+    /// Makes the health check fail with the given error.
+    pub fn with_health_error(mut self, err: PokedexAdapterError) -> Self {
+        self.health_error = Some(err);
+        self
     }
 }
 
 impl PokedexAdapter for MockPokedexAdapter {
-    async fn fetch_pokemon_by_name(&self, _name: &str) -> PokedexAdapterResult<Pokemon> {
-        match &self.0 {
+    async fn fetch_pokemon_by_name(&self, name: &str) -> PokedexAdapterResult<Pokemon> {
+        if let Some(err) = self.failures.get(name) {
+            return Err(err.clone());
+        }
+        match self.registered_pokemon.get(name).or(self.pokemon.as_ref()) {
             Some(pokemon) => Ok(pokemon.clone()),
             None => Err(crate::adapters::pokedex::PokedexAdapterError::NotFound),
         }
     }
+
+    async fn fetch_encounters_by_name(
+        &self,
+        name: &str,
+    ) -> PokedexAdapterResult<PokemonEncounters> {
+        if let Some(encounters) = self.encounters.get(name) {
+            return Ok(encounters.clone());
+        }
+
+        self.fetch_pokemon_by_name(name)
+            .await
+            .map(|_| PokemonEncounters {
+                name: name.to_string(),
+                versions: vec![],
+            })
+    }
+
+    async fn fetch_sprite_by_name(
+        &self,
+        name: &str,
+        variant: SpriteVariant,
+    ) -> PokedexAdapterResult<Option<SpriteStream>> {
+        self.fetch_pokemon_by_name(name).await?;
+
+        Ok(self
+            .sprites
+            .get(&(name.to_string(), variant))
+            .cloned()
+            .map(SpriteStream::from))
+    }
+
+    async fn fetch_species_texts_by_name(&self, name: &str) -> PokedexAdapterResult<SpeciesTexts> {
+        if let Some(species_texts) = self.species_texts.get(name) {
+            return Ok(species_texts.clone());
+        }
+
+        self.fetch_pokemon_by_name(name)
+            .await
+            .map(|pokemon| SpeciesTexts {
+                name: name.to_string(),
+                genus: None,
+                flavor_texts: vec![pokemon.description],
+            })
+    }
+
+    async fn fetch_pokemon_names(&self) -> PokedexAdapterResult<Vec<String>> {
+        let mut names: Vec<String> = self
+            .pokemon
+            .iter()
+            .chain(self.registered_pokemon.values())
+            .flat_map(|pokemon| std::iter::once(&pokemon.name).chain(&pokemon.forms))
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+
+        Ok(names)
+    }
+
+    async fn fetch_generation_by_id(&self, id: u8) -> PokedexAdapterResult<Generation> {
+        self.generations
+            .get(&id)
+            .cloned()
+            .ok_or(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+    }
+
+    async fn fetch_pokedex_by_name(&self, name: &str) -> PokedexAdapterResult<Pokedex> {
+        self.pokedexes
+            .get(name)
+            .cloned()
+            .ok_or(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+    }
+
+    async fn fetch_pokemon_names_by_type(
+        &self,
+        pokemon_type: &str,
+    ) -> PokedexAdapterResult<Vec<String>> {
+        if !TYPES.contains(&pokemon_type) {
+            return Err(PokedexAdapterError::NotFound);
+        }
+
+        Ok(self
+            .known_pokemon()
+            .filter(|pokemon| pokemon.types.iter().any(|name| name == pokemon_type))
+            .map(|pokemon| pokemon.name.clone())
+            .collect())
+    }
+
+    async fn fetch_species_names_by_habitat(
+        &self,
+        habitat: &str,
+    ) -> PokedexAdapterResult<Vec<String>> {
+        if !HABITATS.contains(&habitat) {
+            return Err(PokedexAdapterError::NotFound);
+        }
+
+        Ok(self
+            .known_pokemon()
+            .filter(|pokemon| pokemon.habitat.as_deref() == Some(habitat))
+            .map(|pokemon| pokemon.name.clone())
+            .collect())
+    }
+
+    async fn health_check(&self) -> PokedexAdapterResult<()> {
+        match &self.health_error {
+            Some(err) => Err(err.clone()),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::model::{PokedexEntry, Stats, VersionEncounters};
 
     #[test]
     fn test_should_construct_mock_pokedex_adapter() {
@@ -46,11 +278,18 @@ mod tests {
             description: "An Electric-type Pokémon".to_string(),
             habitat: Some("Emerald Forest".to_string()),
             is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: "Pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Stats::default(),
         };
 
         let adapter = MockPokedexAdapter::new(mocked_pokemon.clone());
 
-        assert_eq!(adapter.0, Some(mocked_pokemon));
+        assert_eq!(adapter.pokemon, Some(mocked_pokemon));
     }
 
     #[test]
@@ -60,11 +299,18 @@ mod tests {
             description: "An Electric-type Pokémon".to_string(),
             habitat: Some("Emerald Forest".to_string()),
             is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: "Pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Stats::default(),
         };
 
         let adapter: MockPokedexAdapter = mocked_pokemon.clone().into();
 
-        assert_eq!(adapter.0, Some(mocked_pokemon));
+        assert_eq!(adapter.pokemon, Some(mocked_pokemon));
     }
 
     #[tokio::test]
@@ -74,6 +320,13 @@ mod tests {
             description: "An Electric-type Pokémon".to_string(),
             habitat: Some("Emerald Forest".to_string()),
             is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: "Pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Stats::default(),
         };
 
         let adapter = MockPokedexAdapter::new(mocked_pokemon.clone());
@@ -83,6 +336,297 @@ mod tests {
         assert_eq!(fetched_pokemon, mocked_pokemon);
     }
 
+    #[tokio::test]
+    async fn test_should_fetch_registered_pokemon_by_name() {
+        let mocked_pokemon = Pokemon {
+            name: "Pikachu".to_string(),
+            description: "An Electric-type Pokémon".to_string(),
+            habitat: Some("Emerald Forest".to_string()),
+            is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: "Pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Stats::default(),
+        };
+        let registered_pokemon = Pokemon {
+            name: "Raichu".to_string(),
+            ..mocked_pokemon.clone()
+        };
+
+        let adapter = MockPokedexAdapter::not_found().with_pokemon(registered_pokemon.clone());
+        assert_eq!(
+            adapter.fetch_pokemon_by_name("Raichu").await.unwrap(),
+            registered_pokemon
+        );
+        assert!(matches!(
+            adapter.fetch_pokemon_by_name("Pikachu").await,
+            Err(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+        ));
+
+        let adapter = MockPokedexAdapter::new(mocked_pokemon.clone());
+        assert_eq!(
+            adapter.fetch_pokemon_by_name("Pichu").await.unwrap(),
+            mocked_pokemon
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_encounters_by_name() {
+        let mocked_pokemon = Pokemon {
+            name: "Pikachu".to_string(),
+            description: "An Electric-type Pokémon".to_string(),
+            habitat: Some("Emerald Forest".to_string()),
+            is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: "Pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Stats::default(),
+        };
+        let encounters = PokemonEncounters {
+            name: "Pikachu".to_string(),
+            versions: vec![VersionEncounters {
+                version: "red".to_string(),
+                encounters: vec![],
+            }],
+        };
+
+        let adapter = MockPokedexAdapter::new(mocked_pokemon).with_encounters(encounters.clone());
+        assert_eq!(
+            adapter.fetch_encounters_by_name("Pikachu").await.unwrap(),
+            encounters
+        );
+        assert!(
+            adapter
+                .fetch_encounters_by_name("Raichu")
+                .await
+                .unwrap()
+                .versions
+                .is_empty()
+        );
+
+        let adapter = MockPokedexAdapter::not_found();
+        assert!(matches!(
+            adapter.fetch_encounters_by_name("Pikachu").await,
+            Err(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_sprite_by_name() {
+        let mocked_pokemon = Pokemon {
+            name: "Pikachu".to_string(),
+            description: "An Electric-type Pokémon".to_string(),
+            habitat: Some("Emerald Forest".to_string()),
+            is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: "Pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Stats::default(),
+        };
+        let sprite = Sprite {
+            content_type: "image/png".to_string(),
+            data: vec![0x89, b'P', b'N', b'G'],
+        };
+
+        let adapter = MockPokedexAdapter::new(mocked_pokemon).with_sprite(
+            "Pikachu",
+            SpriteVariant::Shiny,
+            sprite.clone(),
+        );
+        assert_eq!(
+            adapter
+                .fetch_sprite_by_name("Pikachu", SpriteVariant::Shiny)
+                .await
+                .unwrap()
+                .unwrap()
+                .collect()
+                .await
+                .unwrap(),
+            sprite
+        );
+        assert!(
+            adapter
+                .fetch_sprite_by_name("Pikachu", SpriteVariant::FrontDefault)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let adapter = MockPokedexAdapter::not_found();
+        assert!(matches!(
+            adapter
+                .fetch_sprite_by_name("Pikachu", SpriteVariant::Shiny)
+                .await,
+            Err(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_species_texts_by_name() {
+        let mocked_pokemon = Pokemon {
+            name: "Pikachu".to_string(),
+            description: "An Electric-type Pokémon".to_string(),
+            habitat: Some("Emerald Forest".to_string()),
+            is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: "Pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Stats::default(),
+        };
+        let species_texts = SpeciesTexts {
+            name: "Raichu".to_string(),
+            genus: Some("Mouse Pokémon".to_string()),
+            flavor_texts: vec!["Its long tail serves as a ground.".to_string()],
+        };
+
+        let adapter =
+            MockPokedexAdapter::new(mocked_pokemon).with_species_texts(species_texts.clone());
+        assert_eq!(
+            adapter.fetch_species_texts_by_name("Raichu").await.unwrap(),
+            species_texts
+        );
+        assert_eq!(
+            adapter
+                .fetch_species_texts_by_name("Pikachu")
+                .await
+                .unwrap(),
+            SpeciesTexts {
+                name: "Pikachu".to_string(),
+                genus: None,
+                flavor_texts: vec!["An Electric-type Pokémon".to_string()],
+            }
+        );
+
+        let adapter = MockPokedexAdapter::not_found();
+        assert!(matches!(
+            adapter.fetch_species_texts_by_name("Pikachu").await,
+            Err(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_pokemon_names() {
+        let vulpix = Pokemon {
+            name: "vulpix".to_string(),
+            description: "A fox Pokémon".to_string(),
+            habitat: Some("grassland".to_string()),
+            is_legendary: false,
+            generation: 1,
+            height: 6,
+            weight: 99,
+            species: "vulpix".to_string(),
+            forms: vec!["vulpix".to_string(), "vulpix-alola".to_string()],
+            types: vec!["fire".to_string()],
+            stats: Stats::default(),
+        };
+        let ninetales = Pokemon {
+            name: "ninetales".to_string(),
+            forms: vec![],
+            ..vulpix.clone()
+        };
+
+        let adapter = MockPokedexAdapter::new(vulpix).with_pokemon(ninetales);
+        assert_eq!(
+            adapter.fetch_pokemon_names().await.unwrap(),
+            vec!["ninetales", "vulpix", "vulpix-alola"]
+        );
+
+        let adapter = MockPokedexAdapter::not_found();
+        assert!(adapter.fetch_pokemon_names().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_names_by_type_and_habitat() {
+        let vulpix = Pokemon {
+            name: "vulpix".to_string(),
+            description: "A fox Pokémon".to_string(),
+            habitat: Some("grassland".to_string()),
+            is_legendary: false,
+            generation: 1,
+            height: 6,
+            weight: 99,
+            species: "vulpix".to_string(),
+            forms: vec![],
+            types: vec!["fire".to_string()],
+            stats: Stats::default(),
+        };
+
+        let adapter = MockPokedexAdapter::not_found().with_pokemon(vulpix);
+        assert_eq!(
+            adapter.fetch_pokemon_names_by_type("fire").await.unwrap(),
+            vec!["vulpix"]
+        );
+        assert!(
+            adapter
+                .fetch_pokemon_names_by_type("water")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            adapter
+                .fetch_species_names_by_habitat("grassland")
+                .await
+                .unwrap(),
+            vec!["vulpix"]
+        );
+        assert!(matches!(
+            adapter.fetch_pokemon_names_by_type("foo").await,
+            Err(PokedexAdapterError::NotFound)
+        ));
+        assert!(matches!(
+            adapter.fetch_species_names_by_habitat("nope").await,
+            Err(PokedexAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fetch_generation_and_pokedex() {
+        let generation = Generation {
+            id: 1,
+            name: "generation-i".to_string(),
+            main_region: "kanto".to_string(),
+            species: vec![PokedexEntry {
+                number: 1,
+                name: "bulbasaur".to_string(),
+            }],
+        };
+        let pokedex = Pokedex {
+            name: "kanto".to_string(),
+            region: Some("kanto".to_string()),
+            species: generation.species.clone(),
+        };
+
+        let adapter = MockPokedexAdapter::not_found()
+            .with_generation(generation.clone())
+            .with_pokedex(pokedex.clone());
+        assert_eq!(adapter.fetch_generation_by_id(1).await.unwrap(), generation);
+        assert_eq!(
+            adapter.fetch_pokedex_by_name("kanto").await.unwrap(),
+            pokedex
+        );
+        assert!(matches!(
+            adapter.fetch_generation_by_id(2).await,
+            Err(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+        ));
+        assert!(matches!(
+            adapter.fetch_pokedex_by_name("galar").await,
+            Err(crate::adapters::pokedex::PokedexAdapterError::NotFound)
+        ));
+    }
+
     #[tokio::test]
     async fn test_should_fetch_not_found() {
         let adapter = MockPokedexAdapter::not_found();
@@ -94,4 +638,16 @@ mod tests {
             Err(crate::adapters::pokedex::PokedexAdapterError::NotFound)
         ));
     }
+
+    #[tokio::test]
+    async fn test_should_check_health() {
+        assert!(MockPokedexAdapter::not_found().health_check().await.is_ok());
+
+        let adapter = MockPokedexAdapter::not_found()
+            .with_health_error(PokedexAdapterError::Timeout("timed out".to_string()));
+        assert!(matches!(
+            adapter.health_check().await,
+            Err(PokedexAdapterError::Timeout(_))
+        ));
+    }
 }
@@ -1,15 +1,42 @@
+use std::io;
 use std::time::Duration;
 
+use bytes::Bytes;
+use futures::stream::{BoxStream, Stream, StreamExt};
 use rand::prelude::IteratorRandom;
+use serde::de::DeserializeOwned;
 use url::Url;
 
+use crate::adapters::pokeapi::parse_generation;
+use crate::adapters::pokedex::pokeapi::encounter::LocationAreaEncounter;
+use crate::adapters::pokedex::pokeapi::pokedex::{
+    GenerationResponse, HabitatResponse, PokedexResponse, ResourceListResponse, SpeciesResource,
+    TypePokemonResponse,
+};
+use crate::adapters::pokedex::pokeapi::pokemon::{PokemonResponse, Sprites};
 use crate::adapters::pokedex::pokeapi::species::{LANGUAGE_ENGLISH, Species};
 use crate::adapters::pokedex::{PokedexAdapter, PokedexAdapterError, PokedexAdapterResult};
-use crate::model::Pokemon;
+use crate::log;
+use crate::model::{
+    Encounter, Generation, Pokedex, PokedexEntry, Pokemon, PokemonEncounters, SpeciesTexts,
+    SpriteStream, SpriteVariant, Stats, VersionEncounters,
+};
+use crate::monitoring::observe_upstream;
 
+mod encounter;
+mod pokedex;
+mod pokemon;
 mod species;
 
 const DEFAULT_API_TIMEOUT: Duration = Duration::from_secs(30);
+/// The name of the adapter in the metrics.
+const ADAPTER_NAME: &str = "pokedex";
+/// The page size used to list all the resources of a collection in a single request.
+const RESOURCE_LIST_LIMIT: u32 = 100_000;
+/// The content type of the images, when not provided by the server.
+const DEFAULT_IMAGE_CONTENT_TYPE: &str = "image/png";
+/// The maximum size of the images, in bytes; the sprites and artworks of the PokeAPI are far smaller.
+const MAX_IMAGE_SIZE: u64 = 8 * 1024 * 1024;
 
 /// PokeApiAdapter is an adapter for the PokeAPI service.
 #[derive(Clone, Debug)]
@@ -42,17 +69,194 @@ impl PokeApiAdapter {
     /// Builds the URL for fetching a Pokémon species by name.
     #[inline]
     fn species_endpoint(&self, name: &str) -> PokedexAdapterResult<Url> {
+        self.resource_endpoint("pokemon-species", name)
+    }
+
+    /// Builds the URL for fetching a Pokémon, or one of its forms, by name.
+    #[inline]
+    fn pokemon_endpoint(&self, name: &str) -> PokedexAdapterResult<Url> {
+        self.resource_endpoint("pokemon", name)
+    }
+
+    /// Builds the URL for fetching a resource of the given collection (e.g. `pokedex`) by name or id.
+    #[inline]
+    fn resource_endpoint(&self, collection: &str, name: &str) -> PokedexAdapterResult<Url> {
         self.endpoint
-            .join("pokemon-species/")
+            .join(&format!("{collection}/"))
             .and_then(|url| url.join(name))
             .map_err(|err| PokedexAdapterError::ParseError(err.to_string()))
     }
 
-    /// Extracts the [`Pokemon`] data from the given species data.
-    fn get_pokemon_data_from_species(
+    /// Builds the URL for listing the first `limit` resources of the given collection (e.g. `pokemon-species`).
+    #[inline]
+    fn resource_list_endpoint(&self, collection: &str, limit: u32) -> PokedexAdapterResult<Url> {
+        let mut url = self
+            .endpoint
+            .join(collection)
+            .map_err(|err| PokedexAdapterError::ParseError(err.to_string()))?;
+        url.query_pairs_mut()
+            .append_pair("limit", &limit.to_string());
+        Ok(url)
+    }
+
+    /// Fetches the [`Species`] and the [`PokemonResponse`] with the given name.
+    ///
+    /// The name is looked up as a species first, resolving to its default variety;
+    /// if no species is found, it is looked up as a form (e.g. `vulpix-alola`), resolving to its species.
+    async fn fetch_species_and_pokemon(
+        &self,
+        name: &str,
+    ) -> PokedexAdapterResult<(Species, PokemonResponse)> {
+        match self.fetch::<Species>(self.species_endpoint(name)?).await {
+            Ok(species) => {
+                let pokemon = self.fetch(Self::default_variety_url(&species)?).await?;
+                Ok((species, pokemon))
+            }
+            Err(PokedexAdapterError::NotFound) => {
+                debug!("No species found for {name}; looking up a form");
+                let pokemon: PokemonResponse = self.fetch(self.pokemon_endpoint(name)?).await?;
+                let species_url = Url::parse(&pokemon.species.url)
+                    .map_err(|err| PokedexAdapterError::ParseError(err.to_string()))?;
+                let species = self.fetch(species_url).await?;
+                Ok((species, pokemon))
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Fetches the resource at the given [`Url`] and deserializes it.
+    async fn fetch<R>(&self, url: Url) -> PokedexAdapterResult<R>
+    where
+        R: DeserializeOwned + std::fmt::Debug,
+    {
+        observe_upstream(ADAPTER_NAME, async {
+            let response = self
+                .client
+                .get(url.clone())
+                .headers(log::trace_context_headers())
+                .timeout(self.timeout)
+                .send()
+                .await
+                .map_err(PokedexAdapterError::from)?;
+
+            // not an error: unknown names are expected, e.g. forms are looked up as species first
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                debug!("Resource '{url}' not found");
+                return Err(PokedexAdapterError::NotFound);
+            }
+            if !response.status().is_success() {
+                error!(
+                    "Failed to fetch '{url}': HTTP {status}",
+                    status = response.status()
+                );
+                return Err(PokedexAdapterError::UnexpectedResponse(format!(
+                    "HTTP {}",
+                    response.status()
+                )));
+            }
+
+            // parse response
+            debug!("Parsing response for '{url}'");
+            let resource: R = response
+                .json()
+                .await
+                .map_err(|err| PokedexAdapterError::ParseError(err.to_string()))?;
+            trace!("Parsed resource data: {resource:?}");
+
+            Ok(resource)
+        })
+        .await
+    }
+
+    /// Fetches the image at the given [`Url`], streaming its content.
+    ///
+    /// Returns `None` if the image doesn't exist.
+    /// Images larger than [`MAX_IMAGE_SIZE`] are rejected, either upfront by their `Content-Length`
+    /// or with an error in the stream once that many bytes have been received.
+    async fn fetch_image(&self, url: Url) -> PokedexAdapterResult<Option<SpriteStream>> {
+        observe_upstream(ADAPTER_NAME, async {
+            let response = self
+                .client
+                .get(url.clone())
+                .headers(log::trace_context_headers())
+                .timeout(self.timeout)
+                .send()
+                .await
+                .map_err(PokedexAdapterError::from)?;
+
+            if response.status() == reqwest::StatusCode::NOT_FOUND {
+                debug!("Image '{url}' not found");
+                return Ok(None);
+            }
+            if !response.status().is_success() {
+                error!(
+                    "Failed to fetch '{url}': HTTP {status}",
+                    status = response.status()
+                );
+                return Err(PokedexAdapterError::UnexpectedResponse(format!(
+                    "HTTP {}",
+                    response.status()
+                )));
+            }
+            if let Some(size) = response
+                .content_length()
+                .filter(|size| *size > MAX_IMAGE_SIZE)
+            {
+                error!("Image '{url}' is too large: {size} bytes");
+                return Err(PokedexAdapterError::UnexpectedResponse(format!(
+                    "Image too large: {size} bytes"
+                )));
+            }
+
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .filter(|value| value.starts_with("image/"))
+                .unwrap_or(DEFAULT_IMAGE_CONTENT_TYPE)
+                .to_string();
+            debug!("Streaming image '{url}' ({content_type})");
+
+            Ok(Some(SpriteStream {
+                content_type,
+                data: limit_image_size(response.bytes_stream(), MAX_IMAGE_SIZE),
+            }))
+        })
+        .await
+    }
+
+    /// Gets the URL of the image of the given variant from the [`Sprites`].
+    fn sprite_url(sprites: Sprites, variant: SpriteVariant) -> Option<String> {
+        match variant {
+            SpriteVariant::FrontDefault => sprites.front_default,
+            SpriteVariant::Shiny => sprites.front_shiny,
+            SpriteVariant::OfficialArtwork => sprites.other.official_artwork.front_default,
+        }
+    }
+
+    /// Gets the [`Url`] of the default variety of the given species.
+    fn default_variety_url(species: &Species) -> PokedexAdapterResult<Url> {
+        let variety = species
+            .varieties
+            .iter()
+            .find(|variety| variety.is_default)
+            .ok_or_else(|| {
+                PokedexAdapterError::UnexpectedResponse("No default variety found".to_string())
+            })?;
+
+        Url::parse(&variety.pokemon.url)
+            .map_err(|err| PokedexAdapterError::ParseError(err.to_string()))
+    }
+
+    /// Extracts the [`Pokemon`] data from the given species and pokemon data.
+    ///
+    /// The description is shared by all the forms of the species,
+    /// while types and stats are the ones of the given form.
+    fn get_pokemon_data(
         &self,
         name: &str,
         species: Species,
+        pokemon: PokemonResponse,
     ) -> PokedexAdapterResult<Pokemon> {
         let mut rng = rand::rng();
         // get a random english description. If none found, return an error.
@@ -67,6 +271,35 @@ impl PokeApiAdapter {
             })?;
         debug!("Found description: {description}");
 
+        let mut stats = Stats::default();
+        for pokemon_stat in pokemon.stats {
+            let stat = match pokemon_stat.stat.name.as_str() {
+                "hp" => &mut stats.hp,
+                "attack" => &mut stats.attack,
+                "defense" => &mut stats.defense,
+                "special-attack" => &mut stats.special_attack,
+                "special-defense" => &mut stats.special_defense,
+                "speed" => &mut stats.speed,
+                _ => continue,
+            };
+            *stat = pokemon_stat.base_stat;
+        }
+
+        // list the forms with the default one first
+        let mut varieties = species.varieties;
+        varieties.sort_by_key(|variety| !variety.is_default);
+        let forms = varieties
+            .into_iter()
+            .map(|variety| variety.pokemon.name)
+            .collect();
+
+        let mut type_slots = pokemon.types;
+        type_slots.sort_by_key(|type_slot| type_slot.slot);
+        let types = type_slots
+            .into_iter()
+            .map(|type_slot| type_slot.pokemon_type.name)
+            .collect();
+
         Ok(Pokemon {
             name: name.to_string(),
             description: description
@@ -76,55 +309,363 @@ impl PokeApiAdapter {
                 .to_string(), // clean up newlines and form feeds
             habitat: species.habitat.map(|habitat| habitat.name),
             is_legendary: species.is_legendary,
+            generation: Self::parse_generation(&species.generation.name)?,
+            species: species.name,
+            forms,
+            types,
+            stats,
+            height: pokemon.height,
+            weight: pokemon.weight,
         })
     }
-}
 
-impl PokedexAdapter for PokeApiAdapter {
-    async fn fetch_pokemon_by_name(&self, name: &str) -> PokedexAdapterResult<Pokemon> {
-        let url = self.species_endpoint(name)?;
-        let response = self
-            .client
-            .get(url)
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|err| PokedexAdapterError::NetworkError(err.to_string()))?;
+    /// Parses a PokeAPI generation name (e.g. `generation-iv`) into the generation number.
+    fn parse_generation(name: &str) -> PokedexAdapterResult<u8> {
+        parse_generation(name).ok_or_else(|| {
+            PokedexAdapterError::UnexpectedResponse(format!("Invalid generation: {name}"))
+        })
+    }
+
+    /// Gets the national dex number of the species from its resource URL
+    /// (e.g. `https://pokeapi.co/api/v2/pokemon-species/25/`).
+    fn species_number(species: &SpeciesResource) -> PokedexAdapterResult<u16> {
+        species
+            .url
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .and_then(|id| id.parse().ok())
+            .ok_or_else(|| {
+                PokedexAdapterError::UnexpectedResponse(format!(
+                    "Invalid species URL: {}",
+                    species.url
+                ))
+            })
+    }
+
+    /// Extracts the [`Generation`] data from the given generation response.
+    fn get_generation_data(generation: GenerationResponse) -> PokedexAdapterResult<Generation> {
+        let mut species = generation
+            .pokemon_species
+            .into_iter()
+            .map(|species| {
+                Ok(PokedexEntry {
+                    number: Self::species_number(&species)?,
+                    name: species.name,
+                })
+            })
+            .collect::<PokedexAdapterResult<Vec<_>>>()?;
+        species.sort_by_key(|entry| entry.number);
+
+        Ok(Generation {
+            id: generation.id,
+            name: generation.name,
+            main_region: generation.main_region.name,
+            species,
+        })
+    }
 
-        if response.status() == reqwest::StatusCode::NOT_FOUND {
-            error!("Pokémon '{name}' not found");
-            return Err(PokedexAdapterError::NotFound);
+    /// Extracts the [`SpeciesTexts`] data from the given species.
+    ///
+    /// The flavor texts are repeated across the game versions, so only the distinct ones are kept,
+    /// with their whitespace normalized.
+    fn get_species_texts_data(species: Species) -> SpeciesTexts {
+        let mut flavor_texts: Vec<String> = Vec::new();
+        for entry in species.flavor_text_entries {
+            if entry.language.name != LANGUAGE_ENGLISH {
+                continue;
+            }
+            let flavor_text = entry
+                .flavor_text
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            if !flavor_texts.contains(&flavor_text) {
+                flavor_texts.push(flavor_text);
+            }
         }
-        if !response.status().is_success() {
-            error!(
-                "Failed to fetch Pokémon '{name}': HTTP {status}",
-                status = response.status()
-            );
-            return Err(PokedexAdapterError::UnexpectedResponse(format!(
-                "HTTP {}",
-                response.status()
-            )));
+
+        SpeciesTexts {
+            name: species.name,
+            genus: species
+                .genera
+                .into_iter()
+                .find(|genus| genus.language.name == LANGUAGE_ENGLISH)
+                .map(|genus| genus.genus),
+            flavor_texts,
         }
+    }
 
-        // parse response
-        debug!("Parsing response for Pokémon '{name}'");
-        let species: Species = response
-            .json()
-            .await
-            .map_err(|err| PokedexAdapterError::ParseError(err.to_string()))?;
-        trace!("Parsed species data: {species:?}");
+    /// Extracts the [`Pokedex`] data from the given pokedex response.
+    fn get_pokedex_data(pokedex: PokedexResponse) -> Pokedex {
+        let mut species: Vec<PokedexEntry> = pokedex
+            .pokemon_entries
+            .into_iter()
+            .map(|entry| PokedexEntry {
+                number: entry.entry_number,
+                name: entry.pokemon_species.name,
+            })
+            .collect();
+        species.sort_by_key(|entry| entry.number);
+
+        Pokedex {
+            name: pokedex.name,
+            region: pokedex.region.map(|region| region.name),
+            species,
+        }
+    }
+
+    /// Groups the given location area encounters by game version into [`PokemonEncounters`].
+    ///
+    /// The encounter slots with the same location, method and conditions are merged,
+    /// summing their chances and widening the level range.
+    fn get_encounters_data(
+        name: &str,
+        location_areas: Vec<LocationAreaEncounter>,
+    ) -> PokemonEncounters {
+        let mut versions: Vec<VersionEncounters> = Vec::new();
+        for location_area in location_areas {
+            for version_detail in location_area.version_details {
+                let version_index = match versions
+                    .iter()
+                    .position(|version| version.version == version_detail.version.name)
+                {
+                    Some(index) => index,
+                    None => {
+                        versions.push(VersionEncounters {
+                            version: version_detail.version.name,
+                            encounters: vec![],
+                        });
+                        versions.len() - 1
+                    }
+                };
+                let encounters = &mut versions[version_index].encounters;
+
+                for detail in version_detail.encounter_details {
+                    let mut conditions: Vec<String> = detail
+                        .condition_values
+                        .into_iter()
+                        .map(|condition| condition.name)
+                        .collect();
+                    conditions.sort();
+
+                    match encounters.iter_mut().find(|encounter| {
+                        encounter.location == location_area.location_area.name
+                            && encounter.method == detail.method.name
+                            && encounter.conditions == conditions
+                    }) {
+                        Some(encounter) => {
+                            encounter.min_level = encounter.min_level.min(detail.min_level);
+                            encounter.max_level = encounter.max_level.max(detail.max_level);
+                            encounter.chance =
+                                encounter.chance.saturating_add(detail.chance).min(100);
+                        }
+                        None => encounters.push(Encounter {
+                            location: location_area.location_area.name.clone(),
+                            method: detail.method.name,
+                            min_level: detail.min_level,
+                            max_level: detail.max_level,
+                            chance: detail.chance.min(100),
+                            conditions,
+                        }),
+                    }
+                }
+            }
+        }
+
+        PokemonEncounters {
+            name: name.to_string(),
+            versions,
+        }
+    }
+}
+
+impl From<reqwest::Error> for PokedexAdapterError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err.to_string())
+        } else {
+            Self::NetworkError(err.to_string())
+        }
+    }
+}
+
+impl PokedexAdapter for PokeApiAdapter {
+    async fn fetch_pokemon_by_name(&self, name: &str) -> PokedexAdapterResult<Pokemon> {
+        let (species, pokemon) = self.fetch_species_and_pokemon(name).await?;
 
         // extract Pokémon data
-        let pokemon = self.get_pokemon_data_from_species(name, species)?;
+        let pokemon = self.get_pokemon_data(name, species, pokemon)?;
         debug!("Found pokemon: {pokemon:?}");
         Ok(pokemon)
     }
+
+    async fn fetch_encounters_by_name(
+        &self,
+        name: &str,
+    ) -> PokedexAdapterResult<PokemonEncounters> {
+        let (_, pokemon) = self.fetch_species_and_pokemon(name).await?;
+        let encounters_url = Url::parse(&pokemon.location_area_encounters)
+            .map_err(|err| PokedexAdapterError::ParseError(err.to_string()))?;
+        let location_areas: Vec<LocationAreaEncounter> = self.fetch(encounters_url).await?;
+
+        let encounters = Self::get_encounters_data(name, location_areas);
+        debug!("Found encounters: {encounters:?}");
+        Ok(encounters)
+    }
+
+    async fn fetch_sprite_by_name(
+        &self,
+        name: &str,
+        variant: SpriteVariant,
+    ) -> PokedexAdapterResult<Option<SpriteStream>> {
+        let (_, pokemon) = self.fetch_species_and_pokemon(name).await?;
+
+        let Some(url) = Self::sprite_url(pokemon.sprites, variant) else {
+            debug!("No {variant} sprite found for {name}");
+            return Ok(None);
+        };
+        let url =
+            Url::parse(&url).map_err(|err| PokedexAdapterError::ParseError(err.to_string()))?;
+
+        self.fetch_image(url).await
+    }
+
+    async fn fetch_species_texts_by_name(&self, name: &str) -> PokedexAdapterResult<SpeciesTexts> {
+        let species: Species = self.fetch(self.species_endpoint(name)?).await?;
+
+        let species_texts = Self::get_species_texts_data(species);
+        debug!(
+            "Found {} flavor texts for {name}",
+            species_texts.flavor_texts.len()
+        );
+        Ok(species_texts)
+    }
+
+    async fn fetch_pokemon_names(&self) -> PokedexAdapterResult<Vec<String>> {
+        // species names are listed too, since the default forms may have a different name (e.g. `deoxys-normal`)
+        let (species, pokemon) = tokio::try_join!(
+            self.fetch::<ResourceListResponse>(
+                self.resource_list_endpoint("pokemon-species", RESOURCE_LIST_LIMIT)?
+            ),
+            self.fetch::<ResourceListResponse>(
+                self.resource_list_endpoint("pokemon", RESOURCE_LIST_LIMIT)?
+            ),
+        )?;
+
+        let mut names: Vec<String> = species
+            .results
+            .into_iter()
+            .chain(pokemon.results)
+            .map(|resource| resource.name)
+            .collect();
+        names.sort();
+        names.dedup();
+        debug!("Found {} pokemon names", names.len());
+        Ok(names)
+    }
+
+    async fn fetch_generation_by_id(&self, id: u8) -> PokedexAdapterResult<Generation> {
+        let url = self.resource_endpoint("generation", &id.to_string())?;
+        let generation = Self::get_generation_data(self.fetch(url).await?)?;
+        debug!(
+            "Found generation {id} with {} species",
+            generation.species.len()
+        );
+        Ok(generation)
+    }
+
+    async fn fetch_pokedex_by_name(&self, name: &str) -> PokedexAdapterResult<Pokedex> {
+        let url = self.resource_endpoint("pokedex", name)?;
+        let pokedex = Self::get_pokedex_data(self.fetch(url).await?);
+        debug!(
+            "Found pokedex {name} with {} species",
+            pokedex.species.len()
+        );
+        Ok(pokedex)
+    }
+
+    async fn fetch_pokemon_names_by_type(
+        &self,
+        pokemon_type: &str,
+    ) -> PokedexAdapterResult<Vec<String>> {
+        let url = self.resource_endpoint("type", pokemon_type)?;
+        let response: TypePokemonResponse = self.fetch(url).await?;
+        let names: Vec<String> = response
+            .pokemon
+            .into_iter()
+            .map(|pokemon| pokemon.pokemon.name)
+            .collect();
+        debug!("Found {} pokemon of type {pokemon_type}", names.len());
+        Ok(names)
+    }
+
+    async fn fetch_species_names_by_habitat(
+        &self,
+        habitat: &str,
+    ) -> PokedexAdapterResult<Vec<String>> {
+        let url = self.resource_endpoint("pokemon-habitat", habitat)?;
+        let response: HabitatResponse = self.fetch(url).await?;
+        let names: Vec<String> = response
+            .pokemon_species
+            .into_iter()
+            .map(|species| species.name)
+            .collect();
+        debug!("Found {} species living in {habitat}", names.len());
+        Ok(names)
+    }
+
+    async fn health_check(&self) -> PokedexAdapterResult<()> {
+        // listing a single species is the cheapest request which still goes through the API
+        self.fetch::<ResourceListResponse>(self.resource_list_endpoint("pokemon-species", 1)?)
+            .await
+            .map(|_| ())
+    }
+}
+
+/// Limits the stream of the content of an image to `max_size` bytes,
+/// ending it with an error once more bytes are received.
+fn limit_image_size<E>(
+    stream: impl Stream<Item = Result<Bytes, E>> + Send + 'static,
+    max_size: u64,
+) -> BoxStream<'static, io::Result<Bytes>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut received = 0u64;
+    stream
+        .map(move |chunk| {
+            let chunk = chunk.map_err(io::Error::other)?;
+            received += chunk.len() as u64;
+            if received > max_size {
+                return Err(io::Error::other(format!(
+                    "image larger than {max_size} bytes"
+                )));
+            }
+            Ok(chunk)
+        })
+        .take_while({
+            let mut failed = false;
+            move |chunk| {
+                let take = !failed;
+                failed = chunk.is_err();
+                futures::future::ready(take)
+            }
+        })
+        .boxed()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::adapters::pokedex::pokeapi::species::{FlavorTextEntry, Language};
+    use crate::adapters::pokedex::pokeapi::encounter::{EncounterDetail, VersionEncounterDetail};
+    use crate::adapters::pokedex::pokeapi::pokedex::PokemonEntry;
+    use crate::adapters::pokedex::pokeapi::pokemon::{
+        ApiResource, NamedApiResource, OfficialArtwork, OtherSprites, PokemonStat, PokemonTypeSlot,
+    };
+    use crate::adapters::pokedex::pokeapi::species::{
+        FlavorTextEntry, Genus, Language, Variety, VarietyPokemon,
+    };
 
     #[test]
     fn test_should_build_default_adapter() {
@@ -140,6 +681,35 @@ mod tests {
         assert_eq!(adapter.timeout, Duration::from_secs(10));
     }
 
+    #[test]
+    fn test_should_get_pokemon_url() {
+        let adapter = PokeApiAdapter::default();
+        let pokemon_url = adapter.pokemon_endpoint("vulpix-alola").unwrap();
+        assert_eq!(
+            pokemon_url.as_str(),
+            "https://pokeapi.co/api/v2/pokemon/vulpix-alola"
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_check_health() {
+        let adapter = PokeApiAdapter::default();
+        assert!(adapter.health_check().await.is_ok());
+    }
+
+    #[test]
+    fn test_should_get_resource_list_url() {
+        let adapter = PokeApiAdapter::default();
+        let list_url = adapter
+            .resource_list_endpoint("pokemon-species", RESOURCE_LIST_LIMIT)
+            .unwrap();
+        assert_eq!(
+            list_url.as_str(),
+            "https://pokeapi.co/api/v2/pokemon-species?limit=100000"
+        );
+    }
+
     #[test]
     fn test_should_get_pokemon_species_url() {
         let adapter = PokeApiAdapter::default();
@@ -154,6 +724,10 @@ mod tests {
     fn test_should_get_pokemon_data_from_species() {
         let adapter = PokeApiAdapter::default();
         let species = Species {
+            name: "pikachu".to_string(),
+            generation: NamedApiResource {
+                name: "generation-i".to_string(),
+            },
             flavor_text_entries: vec![
                 FlavorTextEntry {
                     flavor_text: "A yellow electric mouse.".to_string(),
@@ -168,18 +742,428 @@ mod tests {
                     },
                 },
             ],
+            genera: vec![],
             is_legendary: false,
             habitat: Some(species::Habitat {
                 name: "forest".to_string(),
             }),
+            varieties: vec![
+                Variety {
+                    is_default: false,
+                    pokemon: VarietyPokemon {
+                        name: "pikachu-rock-star".to_string(),
+                        url: "https://pokeapi.co/api/v2/pokemon/10080/".to_string(),
+                    },
+                },
+                Variety {
+                    is_default: true,
+                    pokemon: VarietyPokemon {
+                        name: "pikachu".to_string(),
+                        url: "https://pokeapi.co/api/v2/pokemon/25/".to_string(),
+                    },
+                },
+            ],
+        };
+        let pokemon = PokemonResponse {
+            height: 4,
+            weight: 60,
+            location_area_encounters: "https://pokeapi.co/api/v2/pokemon/25/encounters".to_string(),
+            species: ApiResource {
+                url: "https://pokeapi.co/api/v2/pokemon-species/25/".to_string(),
+            },
+            sprites: Sprites::default(),
+            stats: [
+                ("hp", 35),
+                ("attack", 55),
+                ("defense", 40),
+                ("special-attack", 50),
+                ("special-defense", 50),
+                ("speed", 90),
+            ]
+            .into_iter()
+            .map(|(name, base_stat)| PokemonStat {
+                base_stat,
+                stat: NamedApiResource {
+                    name: name.to_string(),
+                },
+            })
+            .collect(),
+            types: vec![
+                PokemonTypeSlot {
+                    slot: 2,
+                    pokemon_type: NamedApiResource {
+                        name: "flying".to_string(),
+                    },
+                },
+                PokemonTypeSlot {
+                    slot: 1,
+                    pokemon_type: NamedApiResource {
+                        name: "electric".to_string(),
+                    },
+                },
+            ],
         };
         let pokemon = adapter
-            .get_pokemon_data_from_species("pikachu", species)
+            .get_pokemon_data("pikachu", species, pokemon)
             .unwrap();
         assert_eq!(pokemon.name, "pikachu");
         assert_eq!(pokemon.description, "A yellow electric mouse.");
-        assert_eq!(pokemon.is_legendary, false);
+        assert!(!pokemon.is_legendary);
         assert_eq!(pokemon.habitat.as_deref(), Some("forest"));
+        assert_eq!(pokemon.species, "pikachu");
+        assert_eq!(pokemon.generation, 1);
+        assert_eq!(pokemon.height, 4);
+        assert_eq!(pokemon.weight, 60);
+        assert_eq!(
+            pokemon.forms,
+            vec!["pikachu".to_string(), "pikachu-rock-star".to_string()]
+        );
+        assert_eq!(
+            pokemon.types,
+            vec!["electric".to_string(), "flying".to_string()]
+        );
+        assert_eq!(
+            pokemon.stats,
+            Stats {
+                hp: 35,
+                attack: 55,
+                defense: 40,
+                special_attack: 50,
+                special_defense: 50,
+                speed: 90,
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_get_default_variety_url() {
+        let species = Species {
+            name: "deoxys".to_string(),
+            generation: NamedApiResource {
+                name: "generation-iii".to_string(),
+            },
+            flavor_text_entries: vec![],
+            genera: vec![],
+            is_legendary: false,
+            habitat: None,
+            varieties: vec![
+                Variety {
+                    is_default: false,
+                    pokemon: VarietyPokemon {
+                        name: "deoxys-attack".to_string(),
+                        url: "https://pokeapi.co/api/v2/pokemon/10001/".to_string(),
+                    },
+                },
+                Variety {
+                    is_default: true,
+                    pokemon: VarietyPokemon {
+                        name: "deoxys-normal".to_string(),
+                        url: "https://pokeapi.co/api/v2/pokemon/386/".to_string(),
+                    },
+                },
+            ],
+        };
+        assert_eq!(
+            PokeApiAdapter::default_variety_url(&species)
+                .unwrap()
+                .as_str(),
+            "https://pokeapi.co/api/v2/pokemon/386/"
+        );
+    }
+
+    #[test]
+    fn test_should_group_encounters_by_version() {
+        let named = |name: &str| NamedApiResource {
+            name: name.to_string(),
+        };
+        let detail =
+            |min_level, max_level, chance, method: &str, conditions: &[&str]| EncounterDetail {
+                min_level,
+                max_level,
+                chance,
+                method: named(method),
+                condition_values: conditions.iter().map(|name| named(name)).collect(),
+            };
+        let location_areas = vec![
+            LocationAreaEncounter {
+                location_area: named("viridian-forest-area"),
+                version_details: vec![
+                    VersionEncounterDetail {
+                        version: named("red"),
+                        encounter_details: vec![
+                            detail(3, 3, 5, "walk", &[]),
+                            detail(5, 5, 5, "walk", &[]),
+                        ],
+                    },
+                    VersionEncounterDetail {
+                        version: named("yellow"),
+                        encounter_details: vec![detail(3, 5, 20, "walk", &[])],
+                    },
+                ],
+            },
+            LocationAreaEncounter {
+                location_area: named("power-plant-area"),
+                version_details: vec![VersionEncounterDetail {
+                    version: named("red"),
+                    encounter_details: vec![
+                        detail(21, 21, 25, "walk", &[]),
+                        detail(24, 24, 10, "walk", &["time-night"]),
+                    ],
+                }],
+            },
+        ];
+
+        let encounters = PokeApiAdapter::get_encounters_data("pikachu", location_areas);
+        assert_eq!(encounters.name, "pikachu");
+        assert_eq!(encounters.versions.len(), 2);
+
+        let red = &encounters.versions[0];
+        assert_eq!(red.version, "red");
+        assert_eq!(
+            red.encounters,
+            vec![
+                Encounter {
+                    location: "viridian-forest-area".to_string(),
+                    method: "walk".to_string(),
+                    min_level: 3,
+                    max_level: 5,
+                    chance: 10,
+                    conditions: vec![],
+                },
+                Encounter {
+                    location: "power-plant-area".to_string(),
+                    method: "walk".to_string(),
+                    min_level: 21,
+                    max_level: 21,
+                    chance: 25,
+                    conditions: vec![],
+                },
+                Encounter {
+                    location: "power-plant-area".to_string(),
+                    method: "walk".to_string(),
+                    min_level: 24,
+                    max_level: 24,
+                    chance: 10,
+                    conditions: vec!["time-night".to_string()],
+                },
+            ]
+        );
+
+        let yellow = &encounters.versions[1];
+        assert_eq!(yellow.version, "yellow");
+        assert_eq!(yellow.encounters.len(), 1);
+        assert_eq!(yellow.encounters[0].chance, 20);
+    }
+
+    #[tokio::test]
+    async fn test_should_limit_image_size() {
+        let chunks = || {
+            futures::stream::iter(
+                [&b"pika"[..], &b"chu"[..], &b"!"[..]]
+                    .map(|chunk| Ok::<_, io::Error>(Bytes::from_static(chunk))),
+            )
+        };
+
+        let data = limit_image_size(chunks(), 8)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(data.concat(), b"pikachu!");
+
+        // the stream ends with an error once over the limit
+        let data = limit_image_size(chunks(), 6).collect::<Vec<_>>().await;
+        assert_eq!(data.len(), 2);
+        assert!(data[0].is_ok());
+        assert!(data[1].is_err());
+    }
+
+    #[test]
+    fn test_should_get_sprite_url() {
+        let sprites = || Sprites {
+            front_default: Some("front.png".to_string()),
+            front_shiny: None,
+            other: OtherSprites {
+                official_artwork: OfficialArtwork {
+                    front_default: Some("artwork.png".to_string()),
+                },
+            },
+        };
+        assert_eq!(
+            PokeApiAdapter::sprite_url(sprites(), SpriteVariant::FrontDefault).as_deref(),
+            Some("front.png")
+        );
+        assert_eq!(
+            PokeApiAdapter::sprite_url(sprites(), SpriteVariant::Shiny),
+            None
+        );
+        assert_eq!(
+            PokeApiAdapter::sprite_url(sprites(), SpriteVariant::OfficialArtwork).as_deref(),
+            Some("artwork.png")
+        );
+    }
+
+    #[test]
+    fn test_should_get_generation_data() {
+        let generation = GenerationResponse {
+            id: 1,
+            name: "generation-i".to_string(),
+            main_region: NamedApiResource {
+                name: "kanto".to_string(),
+            },
+            pokemon_species: vec![
+                SpeciesResource {
+                    name: "ivysaur".to_string(),
+                    url: "https://pokeapi.co/api/v2/pokemon-species/2/".to_string(),
+                },
+                SpeciesResource {
+                    name: "bulbasaur".to_string(),
+                    url: "https://pokeapi.co/api/v2/pokemon-species/1/".to_string(),
+                },
+            ],
+        };
+
+        let generation = PokeApiAdapter::get_generation_data(generation).unwrap();
+        assert_eq!(generation.id, 1);
+        assert_eq!(generation.name, "generation-i");
+        assert_eq!(generation.main_region, "kanto");
+        assert_eq!(
+            generation.species,
+            vec![
+                PokedexEntry {
+                    number: 1,
+                    name: "bulbasaur".to_string(),
+                },
+                PokedexEntry {
+                    number: 2,
+                    name: "ivysaur".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_not_get_generation_data_with_invalid_species_url() {
+        let generation = GenerationResponse {
+            id: 1,
+            name: "generation-i".to_string(),
+            main_region: NamedApiResource {
+                name: "kanto".to_string(),
+            },
+            pokemon_species: vec![SpeciesResource {
+                name: "bulbasaur".to_string(),
+                url: "https://pokeapi.co/api/v2/pokemon-species/bulbasaur/".to_string(),
+            }],
+        };
+
+        assert!(matches!(
+            PokeApiAdapter::get_generation_data(generation),
+            Err(PokedexAdapterError::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_should_get_species_texts_data() {
+        let english = || Language {
+            name: LANGUAGE_ENGLISH.to_string(),
+        };
+        let species = Species {
+            name: "pikachu".to_string(),
+            flavor_text_entries: vec![
+                FlavorTextEntry {
+                    flavor_text: "When several of\nthese POKéMON\x0cgather.".to_string(),
+                    language: english(),
+                },
+                FlavorTextEntry {
+                    flavor_text: "Un ratón eléctrico amarillo.".to_string(),
+                    language: Language {
+                        name: "es".to_string(),
+                    },
+                },
+                FlavorTextEntry {
+                    flavor_text: "When several of these POKéMON gather.".to_string(),
+                    language: english(),
+                },
+                FlavorTextEntry {
+                    flavor_text: "It stores electricity.".to_string(),
+                    language: english(),
+                },
+            ],
+            genera: vec![
+                Genus {
+                    genus: "Pokémon Souris".to_string(),
+                    language: Language {
+                        name: "fr".to_string(),
+                    },
+                },
+                Genus {
+                    genus: "Mouse Pokémon".to_string(),
+                    language: english(),
+                },
+            ],
+            generation: NamedApiResource {
+                name: "generation-i".to_string(),
+            },
+            habitat: None,
+            is_legendary: false,
+            varieties: vec![],
+        };
+
+        assert_eq!(
+            PokeApiAdapter::get_species_texts_data(species),
+            SpeciesTexts {
+                name: "pikachu".to_string(),
+                genus: Some("Mouse Pokémon".to_string()),
+                flavor_texts: vec![
+                    "When several of these POKéMON gather.".to_string(),
+                    "It stores electricity.".to_string(),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_get_pokedex_data() {
+        let pokedex = PokedexResponse {
+            name: "galar".to_string(),
+            region: Some(NamedApiResource {
+                name: "galar".to_string(),
+            }),
+            pokemon_entries: vec![
+                PokemonEntry {
+                    entry_number: 2,
+                    pokemon_species: SpeciesResource {
+                        name: "thwackey".to_string(),
+                        url: "https://pokeapi.co/api/v2/pokemon-species/811/".to_string(),
+                    },
+                },
+                PokemonEntry {
+                    entry_number: 1,
+                    pokemon_species: SpeciesResource {
+                        name: "grookey".to_string(),
+                        url: "https://pokeapi.co/api/v2/pokemon-species/810/".to_string(),
+                    },
+                },
+            ],
+        };
+
+        let pokedex = PokeApiAdapter::get_pokedex_data(pokedex);
+        assert_eq!(pokedex.name, "galar");
+        assert_eq!(pokedex.region.as_deref(), Some("galar"));
+        assert_eq!(
+            pokedex.species,
+            vec![
+                PokedexEntry {
+                    number: 1,
+                    name: "grookey".to_string(),
+                },
+                PokedexEntry {
+                    number: 2,
+                    name: "thwackey".to_string(),
+                },
+            ]
+        );
     }
 
     #[tokio::test]
@@ -188,7 +1172,8 @@ mod tests {
         let pokemon = adapter.fetch_pokemon_by_name("lickitung").await.unwrap();
         assert_eq!(pokemon.name, "lickitung");
         assert_eq!(pokemon.habitat.as_deref(), Some("grassland"));
-        assert_eq!(pokemon.is_legendary, false);
+        assert_eq!(pokemon.types, vec!["normal".to_string()]);
+        assert!(!pokemon.is_legendary);
         assert!(!pokemon.description.is_empty());
     }
 
@@ -198,10 +1183,123 @@ mod tests {
         let pokemon = adapter.fetch_pokemon_by_name("bidoof").await.unwrap();
         assert_eq!(pokemon.name, "bidoof");
         assert_eq!(pokemon.habitat, None);
-        assert_eq!(pokemon.is_legendary, false);
+        assert!(!pokemon.is_legendary);
+        assert!(!pokemon.description.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_pokemon_form_by_name() {
+        let adapter = PokeApiAdapter::default();
+        let pokemon = adapter.fetch_pokemon_by_name("vulpix-alola").await.unwrap();
+        assert_eq!(pokemon.name, "vulpix-alola");
+        assert_eq!(pokemon.species, "vulpix");
+        assert_eq!(pokemon.types, vec!["ice".to_string()]);
+        assert_eq!(
+            pokemon.forms,
+            vec!["vulpix".to_string(), "vulpix-alola".to_string()]
+        );
         assert!(!pokemon.description.is_empty());
     }
 
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_encounters_by_name() {
+        let adapter = PokeApiAdapter::default();
+        let encounters = adapter.fetch_encounters_by_name("pikachu").await.unwrap();
+        assert_eq!(encounters.name, "pikachu");
+        assert!(
+            encounters
+                .versions
+                .iter()
+                .any(|version| version.version == "red")
+        );
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_sprite_by_name() {
+        let adapter = PokeApiAdapter::default();
+        let sprite = adapter
+            .fetch_sprite_by_name("pikachu", SpriteVariant::OfficialArtwork)
+            .await
+            .unwrap()
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(sprite.content_type, "image/png");
+        assert!(!sprite.data.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_generation_by_id() {
+        let adapter = PokeApiAdapter::default();
+        let generation = adapter.fetch_generation_by_id(1).await.unwrap();
+        assert_eq!(generation.main_region, "kanto");
+        assert_eq!(generation.species.len(), 151);
+        assert_eq!(generation.species[0].name, "bulbasaur");
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_pokedex_by_name() {
+        let adapter = PokeApiAdapter::default();
+        let pokedex = adapter.fetch_pokedex_by_name("paldea").await.unwrap();
+        assert_eq!(pokedex.region.as_deref(), Some("paldea"));
+        assert_eq!(pokedex.species[0].number, 1);
+        assert_eq!(pokedex.species[0].name, "sprigatito");
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_pokemon_names_by_type() {
+        let adapter = PokeApiAdapter::default();
+        let names = adapter
+            .fetch_pokemon_names_by_type("electric")
+            .await
+            .unwrap();
+        assert!(names.contains(&"pikachu".to_string()));
+        assert!(matches!(
+            adapter.fetch_pokemon_names_by_type("nope").await,
+            Err(PokedexAdapterError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_species_names_by_habitat() {
+        let adapter = PokeApiAdapter::default();
+        let names = adapter
+            .fetch_species_names_by_habitat("forest")
+            .await
+            .unwrap();
+        assert!(names.contains(&"pikachu".to_string()));
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_species_texts_by_name() {
+        let adapter = PokeApiAdapter::default();
+        let species_texts = adapter
+            .fetch_species_texts_by_name("pikachu")
+            .await
+            .unwrap();
+        assert_eq!(species_texts.genus.as_deref(), Some("Mouse Pokémon"));
+        assert!(!species_texts.flavor_texts.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_fetch_pokemon_names() {
+        let adapter = PokeApiAdapter::default();
+        let names = adapter.fetch_pokemon_names().await.unwrap();
+        assert!(names.contains(&"deoxys".to_string()));
+        assert!(names.contains(&"deoxys-attack".to_string()));
+        assert!(names.contains(&"vulpix-alola".to_string()));
+    }
+
     #[tokio::test]
     async fn test_should_return_error_on_unexisting_pokemon() {
         let adapter = PokeApiAdapter::default();
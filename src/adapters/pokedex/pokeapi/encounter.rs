@@ -0,0 +1,27 @@
+use super::pokemon::NamedApiResource;
+
+/// This struct represents a location area where a Pokémon can be encountered, as returned
+/// by the `api/v2/pokemon/:pokemon/encounters` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+pub struct LocationAreaEncounter {
+    pub location_area: NamedApiResource,
+    pub version_details: Vec<VersionEncounterDetail>,
+}
+
+/// This struct represents the encounters in a location area for a game version.
+#[derive(Debug, serde::Deserialize)]
+pub struct VersionEncounterDetail {
+    pub version: NamedApiResource,
+    pub encounter_details: Vec<EncounterDetail>,
+}
+
+/// This struct represents a single encounter slot.
+#[derive(Debug, serde::Deserialize)]
+pub struct EncounterDetail {
+    pub min_level: u8,
+    pub max_level: u8,
+    pub chance: u8,
+    pub method: NamedApiResource,
+    pub condition_values: Vec<NamedApiResource>,
+}
@@ -0,0 +1,60 @@
+use super::pokemon::NamedApiResource;
+
+/// This struct represents a generation returned by the `api/v2/generation/:id` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+pub struct GenerationResponse {
+    pub id: u8,
+    pub name: String,
+    pub main_region: NamedApiResource,
+    pub pokemon_species: Vec<SpeciesResource>,
+}
+
+/// This struct represents a pokedex returned by the `api/v2/pokedex/:name` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+pub struct PokedexResponse {
+    pub name: String,
+    pub region: Option<NamedApiResource>,
+    pub pokemon_entries: Vec<PokemonEntry>,
+}
+
+/// This struct represents an entry of a pokedex.
+#[derive(Debug, serde::Deserialize)]
+pub struct PokemonEntry {
+    pub entry_number: u16,
+    pub pokemon_species: SpeciesResource,
+}
+
+/// This struct represents a reference to a Pokémon species.
+#[derive(Debug, serde::Deserialize)]
+pub struct SpeciesResource {
+    pub name: String,
+    pub url: String,
+}
+
+/// This struct represents a type returned by the `api/v2/type/:name` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+pub struct TypePokemonResponse {
+    pub pokemon: Vec<TypePokemon>,
+}
+
+/// This struct represents a Pokémon of a type.
+#[derive(Debug, serde::Deserialize)]
+pub struct TypePokemon {
+    pub pokemon: NamedApiResource,
+}
+
+/// This struct represents a habitat returned by the `api/v2/pokemon-habitat/:name` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+pub struct HabitatResponse {
+    pub pokemon_species: Vec<NamedApiResource>,
+}
+
+/// This struct represents a page of a resource list returned by the `api/v2/:collection?limit=` endpoints.
+#[derive(Debug, serde::Deserialize)]
+pub struct ResourceListResponse {
+    pub results: Vec<NamedApiResource>,
+}
@@ -0,0 +1,62 @@
+/// This struct represents a Pokémon returned by the `api/v2/pokemon/:pokemon` endpoint.
+/// here only fields relevant to the application should be defined.
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PokemonResponse {
+    pub height: u16,
+    pub weight: u16,
+    pub location_area_encounters: String,
+    pub species: ApiResource,
+    pub sprites: Sprites,
+    pub stats: Vec<PokemonStat>,
+    pub types: Vec<PokemonTypeSlot>,
+}
+
+/// This struct represents the URLs of the sprites of a Pokémon.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct Sprites {
+    pub front_default: Option<String>,
+    pub front_shiny: Option<String>,
+    #[serde(default)]
+    pub other: OtherSprites,
+}
+
+/// This struct represents the URLs of the other images of a Pokémon.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct OtherSprites {
+    #[serde(rename = "official-artwork", default)]
+    pub official_artwork: OfficialArtwork,
+}
+
+/// This struct represents the URLs of the official artwork of a Pokémon.
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct OfficialArtwork {
+    pub front_default: Option<String>,
+}
+
+/// This struct represents a base stat of a Pokémon.
+#[derive(Debug, serde::Deserialize)]
+pub struct PokemonStat {
+    pub base_stat: u16,
+    pub stat: NamedApiResource,
+}
+
+/// This struct represents a type of a Pokémon, with the slot it occupies.
+#[derive(Debug, serde::Deserialize)]
+pub struct PokemonTypeSlot {
+    pub slot: u8,
+    #[serde(rename = "type")]
+    pub pokemon_type: NamedApiResource,
+}
+
+/// This struct represents a reference to another resource returned by the PokeAPI.
+#[derive(Debug, serde::Deserialize)]
+pub struct NamedApiResource {
+    pub name: String,
+}
+
+/// This struct represents a reference to another resource returned by the PokeAPI, by its URL.
+#[derive(Debug, serde::Deserialize)]
+pub struct ApiResource {
+    pub url: String,
+}
@@ -1,3 +1,5 @@
+use super::pokemon::NamedApiResource;
+
 pub const LANGUAGE_ENGLISH: &str = "en";
 
 /// This struct represents a Pokémon species returned by the `api/v2/pokemon-species/:pokemon` endpoint.
@@ -5,9 +7,13 @@ pub const LANGUAGE_ENGLISH: &str = "en";
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub struct Species {
+    pub name: String,
     pub flavor_text_entries: Vec<FlavorTextEntry>,
+    pub genera: Vec<Genus>,
+    pub generation: NamedApiResource,
     pub habitat: Option<Habitat>,
     pub is_legendary: bool,
+    pub varieties: Vec<Variety>,
 }
 
 /// This struct represents a flavor text entry for a Pokémon species.
@@ -19,6 +25,13 @@ pub struct FlavorTextEntry {
     pub language: Language,
 }
 
+/// This struct represents the genus of a Pokémon species in a language (e.g. `Mouse Pokémon`).
+#[derive(Debug, serde::Deserialize)]
+pub struct Genus {
+    pub genus: String,
+    pub language: Language,
+}
+
 /// This struct represents the habitat of a Pokémon species.
 #[derive(Debug, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -31,3 +44,17 @@ pub struct Habitat {
 pub struct Language {
     pub name: String,
 }
+
+/// This struct represents a variety of a Pokémon species, referencing the Pokémon resource.
+#[derive(Debug, serde::Deserialize)]
+pub struct Variety {
+    pub is_default: bool,
+    pub pokemon: VarietyPokemon,
+}
+
+/// This struct represents the Pokémon resource a variety refers to.
+#[derive(Debug, serde::Deserialize)]
+pub struct VarietyPokemon {
+    pub name: String,
+    pub url: String,
+}
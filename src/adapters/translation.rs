@@ -12,10 +12,12 @@ pub use self::mock::MockTranslationAdapter;
 pub type TranslationAdapterResult<T> = Result<T, TranslationAdapterError>;
 
 /// Errors that can occur while interacting with the [`TranslationAdapter`]
-#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+#[derive(Debug, Clone, thiserror::Error, Eq, PartialEq)]
 pub enum TranslationAdapterError {
     #[error("Network error: {0}")]
     NetworkError(String),
+    #[error("Request timed out: {0}")]
+    Timeout(String),
     #[error("Parse error: {0}")]
     ParseError(String),
     #[error("Unknown error: {0}")]
@@ -37,4 +39,7 @@ pub trait TranslationAdapter: Send + Sync {
         &self,
         text: &str,
     ) -> impl Future<Output = TranslationAdapterResult<String>> + Send;
+
+    /// Checks that the translation service is reachable, without translating anything.
+    fn health_check(&self) -> impl Future<Output = TranslationAdapterResult<()>> + Send;
 }
@@ -6,10 +6,14 @@ use crate::adapters::translation::fun_translations::response::TranslateResponse;
 use crate::adapters::translation::{
     TranslationAdapter, TranslationAdapterError, TranslationAdapterResult,
 };
+use crate::log;
+use crate::monitoring::observe_upstream;
 
 mod response;
 
 const DEFAULT_API_TIMEOUT: Duration = Duration::from_secs(30);
+/// The name of the adapter in the metrics.
+const ADAPTER_NAME: &str = "translation";
 
 /// Adapter for Fun Translations API
 pub struct FunTranslationsAdapter {
@@ -42,43 +46,47 @@ impl FunTranslationsAdapter {
 
     /// Translate the given text using the specified translation URL.
     async fn translate(&self, url: Url, text: &str) -> TranslationAdapterResult<String> {
-        let response = self
-            .client
-            .post(url)
-            .form(&[("text", text)])
-            .timeout(self.timeout)
-            .send()
-            .await
-            .map_err(|err| TranslationAdapterError::NetworkError(err.to_string()))?;
-
-        if !response.status().is_success() {
-            error!(
-                "Failed to translate '{text}': HTTP {status}",
-                status = response.status()
-            );
-            return Err(TranslationAdapterError::UnexpectedResponse(format!(
-                "HTTP {}",
-                response.status()
-            )));
-        }
-
-        // parse response
-        debug!("Parsing response for text '{text}'");
-        let translate_response: TranslateResponse = response
-            .json()
-            .await
-            .map_err(|err| TranslationAdapterError::ParseError(err.to_string()))?;
-        trace!("Parsed translate response: {translate_response:?}");
-
-        // check if success is not zero
-        if translate_response.success.total == 0 {
-            error!("Translation API returned unsuccessful response for text '{text}'");
-            return Err(TranslationAdapterError::UnexpectedResponse(
-                "Translation API returned unsuccessful response".to_string(),
-            ));
-        }
-
-        Ok(translate_response.contents.translated)
+        observe_upstream(ADAPTER_NAME, async {
+            let response = self
+                .client
+                .post(url)
+                .form(&[("text", text)])
+                .headers(log::trace_context_headers())
+                .timeout(self.timeout)
+                .send()
+                .await
+                .map_err(TranslationAdapterError::from)?;
+
+            if !response.status().is_success() {
+                error!(
+                    "Failed to translate '{text}': HTTP {status}",
+                    status = response.status()
+                );
+                return Err(TranslationAdapterError::UnexpectedResponse(format!(
+                    "HTTP {}",
+                    response.status()
+                )));
+            }
+
+            // parse response
+            debug!("Parsing response for text '{text}'");
+            let translate_response: TranslateResponse = response
+                .json()
+                .await
+                .map_err(|err| TranslationAdapterError::ParseError(err.to_string()))?;
+            trace!("Parsed translate response: {translate_response:?}");
+
+            // check if success is not zero
+            if translate_response.success.total == 0 {
+                error!("Translation API returned unsuccessful response for text '{text}'");
+                return Err(TranslationAdapterError::UnexpectedResponse(
+                    "Translation API returned unsuccessful response".to_string(),
+                ));
+            }
+
+            Ok(translate_response.contents.translated)
+        })
+        .await
     }
 
     #[inline]
@@ -98,6 +106,16 @@ impl FunTranslationsAdapter {
     }
 }
 
+impl From<reqwest::Error> for TranslationAdapterError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            Self::Timeout(err.to_string())
+        } else {
+            Self::NetworkError(err.to_string())
+        }
+    }
+}
+
 impl TranslationAdapter for FunTranslationsAdapter {
     async fn translate_into_shakespeare(&self, text: &str) -> TranslationAdapterResult<String> {
         self.translate(self.shakespeare_url()?, text).await
@@ -106,6 +124,31 @@ impl TranslationAdapter for FunTranslationsAdapter {
     async fn translate_into_yoda(&self, text: &str) -> TranslationAdapterResult<String> {
         self.translate(self.yoda_url()?, text).await
     }
+
+    async fn health_check(&self) -> TranslationAdapterResult<()> {
+        observe_upstream(ADAPTER_NAME, async {
+            // translations are rate limited, so the endpoint is only checked to respond
+            let response = self
+                .client
+                .head(self.endpoint.clone())
+                .headers(log::trace_context_headers())
+                .timeout(self.timeout)
+                .send()
+                .await
+                .map_err(TranslationAdapterError::from)?;
+
+            let status = response.status();
+            if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                error!("Translation API health check failed: HTTP {status}");
+                return Err(TranslationAdapterError::UnexpectedResponse(format!(
+                    "HTTP {status}"
+                )));
+            }
+
+            Ok(())
+        })
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +195,13 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[ignore] // Ignored to avoid hitting the real API during tests
+    async fn test_should_check_health() {
+        let adapter = FunTranslationsAdapter::default();
+        assert_eq!(adapter.health_check().await, Ok(()));
+    }
+
     #[tokio::test]
     #[ignore] // Ignored to avoid hitting the real API during tests
     async fn test_should_translate_into_yoda() {
@@ -1,4 +1,6 @@
-use crate::adapters::translation::{TranslationAdapter, TranslationAdapterResult};
+use crate::adapters::translation::{
+    TranslationAdapter, TranslationAdapterError, TranslationAdapterResult,
+};
 
 /// A mock translation adapter for testing purposes.
 pub struct MockTranslationAdapter {
@@ -6,6 +8,8 @@ pub struct MockTranslationAdapter {
     shakespeare_response: String,
     /// predefined responses for yoda translation
     yoda_response: String,
+    /// the error returned by the health check, if unhealthy
+    health_error: Option<TranslationAdapterError>,
 }
 
 impl MockTranslationAdapter {
@@ -17,8 +21,15 @@ impl MockTranslationAdapter {
         Self {
             shakespeare_response: shakespeare_response.into(),
             yoda_response: yoda_response.into(),
+            health_error: None,
         }
     }
+
+    /// Makes the health check fail with the given error.
+    pub fn with_health_error(mut self, err: TranslationAdapterError) -> Self {
+        self.health_error = Some(err);
+        self
+    }
 }
 
 impl TranslationAdapter for MockTranslationAdapter {
@@ -29,6 +40,13 @@ impl TranslationAdapter for MockTranslationAdapter {
     async fn translate_into_yoda(&self, _text: &str) -> TranslationAdapterResult<String> {
         Ok(self.yoda_response.clone())
     }
+
+    async fn health_check(&self) -> TranslationAdapterResult<()> {
+        match &self.health_error {
+            Some(err) => Err(err.clone()),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -55,4 +73,14 @@ mod tests {
         let yoda_result = mock_adapter.translate_into_yoda("Hello").await.unwrap();
         assert_eq!(yoda_result, "Do or do not, there is no try.");
     }
+
+    #[tokio::test]
+    async fn test_mock_translation_adapter_health_check() {
+        let mock_adapter = MockTranslationAdapter::new("", "");
+        assert_eq!(mock_adapter.health_check().await, Ok(()));
+
+        let err = TranslationAdapterError::Timeout("timed out".to_string());
+        let mock_adapter = MockTranslationAdapter::new("", "").with_health_error(err.clone());
+        assert_eq!(mock_adapter.health_check().await, Err(err));
+    }
 }
@@ -1,10 +1,18 @@
 //! This module defines the command-line arguments for the pokedex-api application.
 
+mod log_format;
 mod log_level;
+mod log_rotation;
+mod otlp_protocol;
 
 use std::path::PathBuf;
 
+use crate::rate_limit::IpNetwork;
+
+pub use self::log_format::LogFormat;
 pub use self::log_level::LogLevel;
+pub use self::log_rotation::LogRotation;
+pub use self::otlp_protocol::OtlpProtocol;
 
 /// pokedex-api is a web server for accessing Pokémon data.
 #[derive(argh::FromArgs, Debug)]
@@ -12,25 +20,94 @@ pub struct Args {
     /// adapter timeout in seconds [default: 30]
     #[argh(option, default = "30")]
     pub adapter_timeout_secs: u64,
+    /// file holding the token authorizing the requests to the admin endpoints, sent as bearer token [default: admin endpoints disabled]
+    #[argh(option)]
+    pub admin_token_file: Option<PathBuf>,
+    /// file of the issued API keys, one per line: clients sending one in the X-Api-Key header are rate limited by key rather than by address [default: none]
+    #[argh(option)]
+    pub api_keys_file: Option<PathBuf>,
+    /// JSON file of the names suggested by the autocompletion, used instead of the PokeAPI [default: none]
+    #[argh(option)]
+    pub autocomplete_dataset: Option<PathBuf>,
+    /// the interval between the refreshes of the autocomplete index in seconds [default: 3600]
+    #[argh(option, default = "3600")]
+    pub autocomplete_refresh_interval_secs: u64,
     /// bind address for the web server [default: 127.0.0.1]
     #[argh(option, short = 'b', default = "String::from(\"127.0.0.1\")")]
     pub bind_address: String,
     /// fun translation custom endpoint URL [default: https://api.funtranslations.com/]
     #[argh(option)]
     pub funtranslation_endpoint: Option<String>,
+    /// the time the results of the readiness checks are reused for in seconds [default: 10]
+    #[argh(option, default = "10")]
+    pub health_cache_ttl_secs: u64,
+    /// gzip the rotated log files
+    #[argh(switch)]
+    pub log_compress: bool,
     /// the path to the log file [default: log only to stdout]
     #[argh(option, short = 'L')]
     pub log_file: Option<PathBuf>,
-    /// apply log filtering to target matching the given string [default: no filtering]
+    /// the format of the log file, either compact, pretty or json [default: the format of --log-format]
+    #[argh(option)]
+    pub log_file_format: Option<LogFormat>,
+    /// the directives overriding the log level of the matching targets, e.g. pokedex_api::adapters=trace,hyper=warn, or a target prefix keeping only the matching logs, e.g. pokedex_api [default: no filtering]
     #[argh(option)]
     pub log_filter: Option<String>,
+    /// the format of the logs written to stdout, either compact, pretty or json [default: compact]
+    #[argh(option, default = "LogFormat::Compact")]
+    pub log_format: LogFormat,
     /// the log level [default: info]
     #[argh(option, short = 'l', default = "LogLevel::Info")]
     pub log_level: LogLevel,
+    /// the amount of rotated log files kept, the older ones are removed [default: 7]
+    #[argh(option, default = "7")]
+    pub log_max_files: usize,
+    /// the size in MB the log file is rotated at [default: no size limit]
+    #[argh(option)]
+    pub log_max_size_mb: Option<u64>,
+    /// how often the log file is rotated, either never, hourly or daily [default: never]
+    #[argh(option, default = "LogRotation::Never")]
+    pub log_rotation: LogRotation,
+    /// the OTLP collector endpoint the spans are exported to (e.g. http://localhost:4317) [default: $OTEL_EXPORTER_OTLP_ENDPOINT, if set; otherwise no export]
+    #[argh(option)]
+    pub otlp_endpoint: Option<String>,
+    /// the protocol used to export the spans to the OTLP collector, either grpc or http [default: grpc]
+    #[argh(option, default = "OtlpProtocol::Grpc")]
+    pub otlp_protocol: OtlpProtocol,
     /// pokeapi custom endpoint URL [default: https://pokeapi.co/api/v2/]
     #[argh(option)]
     pub pokeapi_endpoint: Option<String>,
     /// the port the web server will listen on [default: 5000]
     #[argh(option, short = 'p', default = "5000")]
     pub port: u16,
+    /// the amount of guesses allowed for each quiz [default: 5]
+    #[argh(option, default = "5")]
+    pub quiz_max_attempts: u8,
+    /// the time to live of each quiz in seconds [default: 600]
+    #[argh(option, default = "600")]
+    pub quiz_ttl_secs: u64,
+    /// the requests each client can make at once [default: the requests per minute]
+    #[argh(option)]
+    pub rate_limit_burst: Option<u32>,
+    /// the requests each client can make per minute, to all the routes but the probes and the metrics [default: unlimited]
+    #[argh(option)]
+    pub rate_limit_per_minute: Option<u32>,
+    /// the interval between the rebuilds of the search index in seconds [default: 86400]
+    #[argh(option, default = "86400")]
+    pub search_rebuild_interval_secs: u64,
+    /// the file where the search index is stored, to be loaded on startup [default: none]
+    #[argh(option)]
+    pub search_snapshot: Option<PathBuf>,
+    /// the directory where the Pokémon images are cached [default: $TMPDIR/pokedex-api/sprites]
+    #[argh(option)]
+    pub sprite_cache_dir: Option<PathBuf>,
+    /// the requests each client can make at once to /pokemon/translated [default: the requests per minute]
+    #[argh(option)]
+    pub translated_rate_limit_burst: Option<u32>,
+    /// the requests each client can make per minute to /pokemon/translated, limited apart from the other routes [default: the limit of --rate-limit-per-minute]
+    #[argh(option)]
+    pub translated_rate_limit_per_minute: Option<u32>,
+    /// the address or network (e.g. 10.0.0.0/8) of a proxy whose X-Forwarded-For header is trusted; can be repeated [default: none]
+    #[argh(option)]
+    pub trusted_proxy: Vec<IpNetwork>,
 }
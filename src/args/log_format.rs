@@ -0,0 +1,57 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The format of the log lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Single line, human readable
+    Compact,
+    /// Multiline, human readable
+    Pretty,
+    /// One JSON object per line, with the target and the fields of the spans
+    Json,
+}
+
+impl Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compact => write!(f, "compact"),
+            Self::Pretty => write!(f, "pretty"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "compact" => Ok(Self::Compact),
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown log format: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_log_format_from_str() {
+        assert_eq!(LogFormat::from_str("compact").unwrap(), LogFormat::Compact);
+        assert_eq!(LogFormat::from_str("pretty").unwrap(), LogFormat::Pretty);
+        assert_eq!(LogFormat::from_str("JSON").unwrap(), LogFormat::Json);
+        assert!(LogFormat::from_str("unknown").is_err());
+    }
+
+    #[test]
+    fn test_log_format_display() {
+        assert_eq!(LogFormat::Compact.to_string(), "compact");
+        assert_eq!(LogFormat::Pretty.to_string(), "pretty");
+        assert_eq!(LogFormat::Json.to_string(), "json");
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// How often the log file is rotated, regardless of its size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogRotation {
+    Never,
+    Hourly,
+    Daily,
+}
+
+impl Display for LogRotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Never => write!(f, "never"),
+            Self::Hourly => write!(f, "hourly"),
+            Self::Daily => write!(f, "daily"),
+        }
+    }
+}
+
+impl FromStr for LogRotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "never" => Ok(Self::Never),
+            "hourly" => Ok(Self::Hourly),
+            "daily" => Ok(Self::Daily),
+            _ => Err(format!("unknown log rotation: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_log_rotation_from_str() {
+        assert_eq!(LogRotation::from_str("never").unwrap(), LogRotation::Never);
+        assert_eq!(
+            LogRotation::from_str("hourly").unwrap(),
+            LogRotation::Hourly
+        );
+        assert_eq!(LogRotation::from_str("Daily").unwrap(), LogRotation::Daily);
+        assert!(LogRotation::from_str("weekly").is_err());
+    }
+
+    #[test]
+    fn test_log_rotation_display() {
+        assert_eq!(LogRotation::Never.to_string(), "never");
+        assert_eq!(LogRotation::Hourly.to_string(), "hourly");
+        assert_eq!(LogRotation::Daily.to_string(), "daily");
+    }
+}
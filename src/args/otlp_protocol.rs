@@ -0,0 +1,66 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use opentelemetry_otlp::Protocol;
+
+/// The transport used to export the spans to the OTLP collector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    Grpc,
+    Http,
+}
+
+impl Display for OtlpProtocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Grpc => write!(f, "grpc"),
+            Self::Http => write!(f, "http"),
+        }
+    }
+}
+
+impl FromStr for OtlpProtocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "grpc" => Ok(Self::Grpc),
+            "http" => Ok(Self::Http),
+            _ => Err(format!("unknown OTLP protocol: {}", s)),
+        }
+    }
+}
+
+impl From<OtlpProtocol> for Protocol {
+    fn from(protocol: OtlpProtocol) -> Self {
+        match protocol {
+            OtlpProtocol::Grpc => Self::Grpc,
+            OtlpProtocol::Http => Self::HttpBinary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_otlp_protocol_from_str() {
+        assert_eq!(OtlpProtocol::from_str("grpc").unwrap(), OtlpProtocol::Grpc);
+        assert_eq!(OtlpProtocol::from_str("HTTP").unwrap(), OtlpProtocol::Http);
+        assert!(OtlpProtocol::from_str("unknown").is_err());
+    }
+
+    #[test]
+    fn test_otlp_protocol_display() {
+        assert_eq!(OtlpProtocol::Grpc.to_string(), "grpc");
+        assert_eq!(OtlpProtocol::Http.to_string(), "http");
+    }
+
+    #[test]
+    fn test_otlp_protocol_to_protocol() {
+        assert_eq!(Protocol::from(OtlpProtocol::Grpc), Protocol::Grpc);
+        assert_eq!(Protocol::from(OtlpProtocol::Http), Protocol::HttpBinary);
+    }
+}
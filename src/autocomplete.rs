@@ -0,0 +1,9 @@
+//! This module implements the autocompletion of the Pokémon names.
+//!
+//! Suggestions are served from an in-memory trie of the names of all the species and forms,
+//! which is periodically refreshed in the background.
+
+mod store;
+mod trie;
+
+pub use self::store::AutocompleteIndex;
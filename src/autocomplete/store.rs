@@ -0,0 +1,202 @@
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use super::trie::Trie;
+use crate::adapters::pokedex::PokedexAdapter;
+use crate::model::Suggestion;
+
+/// Default interval between the refreshes of the index.
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Errors that can occur while refreshing the [`AutocompleteIndex`].
+#[derive(Debug, thiserror::Error)]
+pub enum AutocompleteError {
+    #[error("Failed to fetch the Pokémon names: {0}")]
+    Adapter(#[from] crate::adapters::pokedex::PokedexAdapterError),
+    #[error("Failed to read the dataset: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid dataset: {0}")]
+    Dataset(#[from] serde_json::Error),
+}
+
+/// Holds the current [`Trie`] used by the autocompletion.
+///
+/// The trie is swapped atomically when refreshed, so requests are never blocked by a refresh.
+#[derive(Debug)]
+pub struct AutocompleteIndex {
+    trie: RwLock<Option<Arc<Trie>>>,
+    /// the offline dataset of the suggestions, used instead of the adapter if set
+    dataset: Option<PathBuf>,
+    refresh_interval: Duration,
+}
+
+impl Default for AutocompleteIndex {
+    fn default() -> Self {
+        Self::new(None, DEFAULT_REFRESH_INTERVAL)
+    }
+}
+
+impl AutocompleteIndex {
+    /// Creates a new empty [`AutocompleteIndex`], with an optional offline dataset and the interval between the refreshes.
+    pub fn new(dataset: Option<PathBuf>, refresh_interval: Duration) -> Self {
+        Self {
+            trie: RwLock::new(None),
+            dataset,
+            refresh_interval,
+        }
+    }
+
+    /// Returns at most `limit` suggestions for the given prefix,
+    /// or `None` if the index has not been loaded yet.
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Option<Vec<Suggestion>> {
+        let trie = self
+            .trie
+            .read()
+            .expect("autocomplete index lock poisoned")
+            .clone()?;
+
+        Some(trie.suggest(prefix, limit))
+    }
+
+    /// Returns the amount of names in the index, or `None` if the index has not been loaded yet.
+    pub fn len(&self) -> Option<usize> {
+        self.trie
+            .read()
+            .expect("autocomplete index lock poisoned")
+            .as_ref()
+            .map(|trie| trie.len())
+    }
+
+    /// Refreshes the index at each interval, starting right away.
+    ///
+    /// Failed refreshes keep the current index.
+    pub async fn refresh_periodically<P>(&self, adapter: &P)
+    where
+        P: PokedexAdapter,
+    {
+        let mut interval = tokio::time::interval(self.refresh_interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.refresh(adapter).await {
+                error!("failed to refresh the autocomplete index: {err}");
+            }
+        }
+    }
+
+    /// Refreshes the index from the offline dataset if set, otherwise from the names fetched from the adapter.
+    pub async fn refresh<P>(&self, adapter: &P) -> Result<(), AutocompleteError>
+    where
+        P: PokedexAdapter,
+    {
+        let suggestions = match &self.dataset {
+            Some(path) => serde_json::from_slice(&tokio::fs::read(path).await?)?,
+            None => adapter
+                .fetch_pokemon_names()
+                .await?
+                .into_iter()
+                .map(|name| Suggestion {
+                    label: name.clone(),
+                    name,
+                    language: None,
+                })
+                .collect::<Vec<_>>(),
+        };
+
+        // building the trie of a thousand names takes a few milliseconds, so keep it off the runtime
+        let trie = tokio::task::spawn_blocking(move || Trie::new(suggestions))
+            .await
+            .map_err(io::Error::other)?;
+        info!("autocomplete index refreshed; {} suggestions", trie.len());
+        *self.trie.write().expect("autocomplete index lock poisoned") = Some(Arc::new(trie));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::adapters::pokedex::MockPokedexAdapter;
+    use crate::model::{Pokemon, Stats};
+
+    #[tokio::test]
+    async fn test_should_refresh_from_adapter() {
+        let index = AutocompleteIndex::default();
+        assert!(index.suggest("char", 10).is_none());
+        assert_eq!(index.len(), None);
+
+        index.refresh(&adapter()).await.unwrap();
+
+        let suggestions = index.suggest("char", 10).unwrap();
+        let names: Vec<&str> = suggestions.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["charizard", "charizard-mega-x"]);
+        assert!(suggestions.iter().all(|s| s.language.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_should_refresh_from_dataset() {
+        let dir = tempfile::tempdir().unwrap();
+        let dataset = dir.path().join("names.json");
+        std::fs::write(
+            &dataset,
+            r#"[
+                {"name": "charizard", "label": "charizard"},
+                {"name": "charizard", "label": "Glurak", "language": "de"}
+            ]"#,
+        )
+        .unwrap();
+        let index = AutocompleteIndex::new(Some(dataset), DEFAULT_REFRESH_INTERVAL);
+
+        index
+            .refresh(&MockPokedexAdapter::not_found())
+            .await
+            .unwrap();
+
+        assert_eq!(index.len(), Some(2));
+        assert_eq!(
+            index.suggest("glu", 10).unwrap(),
+            vec![Suggestion {
+                name: "charizard".to_string(),
+                label: "Glurak".to_string(),
+                language: Some("de".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_keep_index_on_failed_refresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let index = AutocompleteIndex::default();
+        index.refresh(&adapter()).await.unwrap();
+
+        let index = AutocompleteIndex {
+            dataset: Some(dir.path().join("missing.json")),
+            ..index
+        };
+        assert!(matches!(
+            index.refresh(&adapter()).await,
+            Err(AutocompleteError::Io(_))
+        ));
+
+        assert_eq!(index.suggest("char", 10).unwrap().len(), 2);
+    }
+
+    fn adapter() -> MockPokedexAdapter {
+        MockPokedexAdapter::new(Pokemon {
+            name: "charizard".to_string(),
+            description: "It spits fire.".to_string(),
+            habitat: Some("mountain".to_string()),
+            is_legendary: false,
+            generation: 1,
+            height: 17,
+            weight: 905,
+            species: "charizard".to_string(),
+            forms: vec!["charizard".to_string(), "charizard-mega-x".to_string()],
+            types: vec!["fire".to_string(), "flying".to_string()],
+            stats: Stats::default(),
+        })
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use crate::model::Suggestion;
+
+/// A node of the [`Trie`].
+#[derive(Debug, Default)]
+struct Node {
+    /// the children, sorted by character so that the suggestions are visited in alphabetical order
+    children: BTreeMap<char, Node>,
+    /// the suggestions whose key ends at this node
+    suggestions: Vec<Suggestion>,
+}
+
+/// Trie of the [`Suggestion`]s, keyed by their normalized label.
+#[derive(Debug, Default)]
+pub struct Trie {
+    root: Node,
+    len: usize,
+}
+
+impl Trie {
+    /// Builds the [`Trie`] of the given suggestions; duplicates are dropped.
+    pub fn new(suggestions: impl IntoIterator<Item = Suggestion>) -> Self {
+        let mut trie = Self::default();
+        for suggestion in suggestions {
+            trie.insert(suggestion);
+        }
+        trie
+    }
+
+    /// Returns the amount of suggestions in the trie.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Inserts the suggestion, unless its label has no letters or digits or it is already present.
+    fn insert(&mut self, suggestion: Suggestion) {
+        let key = normalize(&suggestion.label);
+        if key.is_empty() {
+            return;
+        }
+
+        let node = key.chars().fold(&mut self.root, |node, c| {
+            node.children.entry(c).or_default()
+        });
+        if !node.suggestions.contains(&suggestion) {
+            node.suggestions.push(suggestion);
+            self.len += 1;
+        }
+    }
+
+    /// Returns at most `limit` suggestions whose label starts with the given prefix, sorted alphabetically.
+    ///
+    /// The prefix is compared ignoring case, spaces and punctuation, so `mr. m` matches `mr-mime`.
+    pub fn suggest(&self, prefix: &str, limit: usize) -> Vec<Suggestion> {
+        let mut node = &self.root;
+        for c in normalize(prefix).chars() {
+            match node.children.get(&c) {
+                Some(child) => node = child,
+                None => return vec![],
+            }
+        }
+
+        let mut suggestions = Vec::with_capacity(limit.min(self.len));
+        Self::collect(node, limit, &mut suggestions);
+        suggestions
+    }
+
+    /// Collects the suggestions of the node and of its descendants, in order, up to the limit.
+    fn collect(node: &Node, limit: usize, suggestions: &mut Vec<Suggestion>) {
+        for suggestion in &node.suggestions {
+            if suggestions.len() >= limit {
+                return;
+            }
+            suggestions.push(suggestion.clone());
+        }
+        for child in node.children.values() {
+            if suggestions.len() >= limit {
+                return;
+            }
+            Self::collect(child, limit, suggestions);
+        }
+    }
+}
+
+/// Normalizes a label to its key, keeping only its lowercase letters and digits.
+fn normalize(label: &str) -> String {
+    label
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_suggest_names() {
+        let trie = trie();
+
+        assert_eq!(
+            labels(&trie.suggest("char", 10)),
+            vec!["charizard", "charizard-mega-x", "charmander", "charmeleon"]
+        );
+        assert_eq!(
+            labels(&trie.suggest("CHARM", 10)),
+            vec!["charmander", "charmeleon"]
+        );
+        assert_eq!(labels(&trie.suggest("mr. m", 10)), vec!["mr-mime"]);
+        assert!(trie.suggest("missingno", 10).is_empty());
+    }
+
+    #[test]
+    fn test_should_suggest_localized_names() {
+        let trie = trie();
+
+        let suggestions = trie.suggest("glu", 10);
+
+        assert_eq!(
+            suggestions,
+            vec![Suggestion {
+                name: "charizard".to_string(),
+                label: "Glurak".to_string(),
+                language: Some("de".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_should_limit_suggestions() {
+        let trie = trie();
+
+        assert_eq!(
+            labels(&trie.suggest("char", 2)),
+            vec!["charizard", "charizard-mega-x"]
+        );
+        assert_eq!(trie.suggest("", 3).len(), 3);
+        assert!(trie.suggest("char", 0).is_empty());
+    }
+
+    #[test]
+    fn test_should_drop_duplicates() {
+        let trie = Trie::new([
+            suggestion("pikachu"),
+            suggestion("pikachu"),
+            suggestion("..."),
+        ]);
+
+        assert_eq!(trie.len(), 1);
+    }
+
+    fn trie() -> Trie {
+        Trie::new([
+            suggestion("charmeleon"),
+            suggestion("charizard-mega-x"),
+            suggestion("charizard"),
+            suggestion("charmander"),
+            suggestion("mr-mime"),
+            Suggestion {
+                name: "charizard".to_string(),
+                label: "Glurak".to_string(),
+                language: Some("de".to_string()),
+            },
+        ])
+    }
+
+    fn suggestion(name: &str) -> Suggestion {
+        Suggestion {
+            name: name.to_string(),
+            label: name.to_string(),
+            language: None,
+        }
+    }
+
+    fn labels(suggestions: &[Suggestion]) -> Vec<&str> {
+        suggestions
+            .iter()
+            .map(|suggestion| suggestion.label.as_str())
+            .collect()
+    }
+}
@@ -0,0 +1,275 @@
+//! This module checks the health of the dependencies of the application, for the readiness probe.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use tokio::sync::Mutex;
+
+use crate::adapters::pokedex::PokedexAdapter;
+use crate::adapters::translation::TranslationAdapter;
+use crate::autocomplete::AutocompleteIndex;
+use crate::model::{DependencyHealth, HealthStatus, Readiness};
+use crate::search::SearchIndex;
+use crate::sprites::SpriteCache;
+
+/// Default time the results of the checks are reused for.
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10);
+/// Maximum time each check may take, so probes are answered even if an upstream hangs.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Checks the dependencies of the application, caching the results for a short time.
+///
+/// Concurrent probes wait for the running check instead of starting their own,
+/// so the upstreams are checked at most once per TTL.
+#[derive(Debug)]
+pub struct HealthChecker {
+    ttl: Duration,
+    last_check: Mutex<Option<(Instant, Readiness)>>,
+}
+
+impl Default for HealthChecker {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_TTL)
+    }
+}
+
+impl HealthChecker {
+    /// Creates a new [`HealthChecker`] reusing the results of the checks for the given time.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            last_check: Mutex::new(None),
+        }
+    }
+
+    /// Returns the [`Readiness`] of the application, checking the dependencies if the cached one is expired.
+    ///
+    /// The application is ready when the PokeAPI is up; the other dependencies are reported,
+    /// but the application keeps working without them.
+    pub async fn readiness<P, T>(
+        &self,
+        pokedex_adapter: &P,
+        translation_adapter: &T,
+        sprite_cache: &SpriteCache,
+        search_index: &SearchIndex,
+        autocomplete_index: &AutocompleteIndex,
+    ) -> Readiness
+    where
+        P: PokedexAdapter,
+        T: TranslationAdapter,
+    {
+        let mut last_check = self.last_check.lock().await;
+        if let Some((checked_at, readiness)) = last_check.as_ref()
+            && checked_at.elapsed() < self.ttl
+        {
+            return readiness.clone();
+        }
+
+        debug!("checking the health of the dependencies");
+        let (pokeapi, translations, sprite_cache) = tokio::join!(
+            check("pokeapi", true, pokedex_adapter.health_check()),
+            check("translations", false, translation_adapter.health_check()),
+            check("spriteCache", false, sprite_cache.health_check()),
+        );
+        let dependencies = vec![
+            pokeapi,
+            translations,
+            sprite_cache,
+            warm_up(
+                "searchIndex",
+                search_index.get().map(|index| index.len()),
+                "species indexed",
+            ),
+            warm_up(
+                "autocompleteIndex",
+                autocomplete_index.len(),
+                "names loaded",
+            ),
+        ];
+        let readiness = Readiness {
+            ready: dependencies
+                .iter()
+                .all(|dependency| !dependency.required || dependency.status == HealthStatus::Up),
+            checked_at: Utc::now(),
+            dependencies,
+        };
+        if !readiness.ready {
+            warn!("the application is not ready: {readiness:?}");
+        }
+
+        *last_check = Some((Instant::now(), readiness.clone()));
+        readiness
+    }
+}
+
+/// Runs the check of the given dependency, failing it if it takes too long.
+async fn check<E>(
+    name: &str,
+    required: bool,
+    check: impl Future<Output = Result<(), E>>,
+) -> DependencyHealth
+where
+    E: fmt::Display,
+{
+    let (status, detail) = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(Ok(())) => (HealthStatus::Up, None),
+        Ok(Err(err)) => (HealthStatus::Down, Some(err.to_string())),
+        Err(_) => (
+            HealthStatus::Down,
+            Some(format!("No response within {CHECK_TIMEOUT:?}")),
+        ),
+    };
+
+    DependencyHealth {
+        name: name.to_string(),
+        status,
+        required,
+        detail,
+    }
+}
+
+/// Reports the warm-up of an index, given the amount of its entries if loaded.
+fn warm_up(name: &str, entries: Option<usize>, entries_description: &str) -> DependencyHealth {
+    DependencyHealth {
+        name: name.to_string(),
+        status: match entries {
+            Some(_) => HealthStatus::Up,
+            None => HealthStatus::WarmingUp,
+        },
+        required: false,
+        detail: entries.map(|entries| format!("{entries} {entries_description}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::adapters::pokedex::{MockPokedexAdapter, PokedexAdapterError};
+    use crate::adapters::translation::{MockTranslationAdapter, TranslationAdapterError};
+
+    #[tokio::test]
+    async fn test_should_be_ready() {
+        let dir = tempfile::tempdir().unwrap();
+        let checker = HealthChecker::default();
+
+        let readiness = checker
+            .readiness(
+                &MockPokedexAdapter::not_found(),
+                &MockTranslationAdapter::new("", ""),
+                &SpriteCache::new(dir.path()),
+                &SearchIndex::default(),
+                &AutocompleteIndex::default(),
+            )
+            .await;
+
+        assert!(readiness.ready);
+        let statuses: Vec<(&str, HealthStatus)> = readiness
+            .dependencies
+            .iter()
+            .map(|dependency| (dependency.name.as_str(), dependency.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("pokeapi", HealthStatus::Up),
+                ("translations", HealthStatus::Up),
+                ("spriteCache", HealthStatus::Up),
+                ("searchIndex", HealthStatus::WarmingUp),
+                ("autocompleteIndex", HealthStatus::WarmingUp),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_not_be_ready_if_pokeapi_is_down() {
+        let dir = tempfile::tempdir().unwrap();
+        let checker = HealthChecker::default();
+
+        let readiness = checker
+            .readiness(
+                &MockPokedexAdapter::not_found().with_health_error(
+                    PokedexAdapterError::NetworkError("connection refused".to_string()),
+                ),
+                &MockTranslationAdapter::new("", ""),
+                &SpriteCache::new(dir.path()),
+                &SearchIndex::default(),
+                &AutocompleteIndex::default(),
+            )
+            .await;
+
+        assert!(!readiness.ready);
+        assert_eq!(readiness.dependencies[0].status, HealthStatus::Down);
+        assert_eq!(
+            readiness.dependencies[0].detail.as_deref(),
+            Some("Network error: connection refused")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_be_ready_if_optional_dependency_is_down() {
+        let dir = tempfile::tempdir().unwrap();
+        let checker = HealthChecker::default();
+
+        let readiness = checker
+            .readiness(
+                &MockPokedexAdapter::not_found(),
+                &MockTranslationAdapter::new("", "").with_health_error(
+                    TranslationAdapterError::UnexpectedResponse("HTTP 429".to_string()),
+                ),
+                &SpriteCache::new(dir.path()),
+                &SearchIndex::default(),
+                &AutocompleteIndex::default(),
+            )
+            .await;
+
+        assert!(readiness.ready);
+        assert_eq!(readiness.dependencies[1].status, HealthStatus::Down);
+    }
+
+    #[tokio::test]
+    async fn test_should_cache_readiness() {
+        let dir = tempfile::tempdir().unwrap();
+        let checker = HealthChecker::new(Duration::from_secs(60));
+        let sprite_cache = SpriteCache::new(dir.path());
+        let search_index = SearchIndex::default();
+        let autocomplete_index = AutocompleteIndex::default();
+
+        let first = checker
+            .readiness(
+                &MockPokedexAdapter::not_found(),
+                &MockTranslationAdapter::new("", ""),
+                &sprite_cache,
+                &search_index,
+                &autocomplete_index,
+            )
+            .await;
+        // the failing adapter is not checked, since the previous result is still fresh
+        let second = checker
+            .readiness(
+                &MockPokedexAdapter::not_found()
+                    .with_health_error(PokedexAdapterError::Timeout("timed out".to_string())),
+                &MockTranslationAdapter::new("", ""),
+                &sprite_cache,
+                &search_index,
+                &autocomplete_index,
+            )
+            .await;
+        assert_eq!(first, second);
+
+        // with no TTL, the dependencies are checked each time
+        let checker = HealthChecker::new(Duration::ZERO);
+        let readiness = checker
+            .readiness(
+                &MockPokedexAdapter::not_found()
+                    .with_health_error(PokedexAdapterError::Timeout("timed out".to_string())),
+                &MockTranslationAdapter::new("", ""),
+                &sprite_cache,
+                &search_index,
+                &autocomplete_index,
+            )
+            .await;
+        assert!(!readiness.ready);
+    }
+}
@@ -1,89 +1,233 @@
-use std::fs::{File, OpenOptions};
-use std::path::Path;
-use std::sync::Arc;
+mod control;
+mod otlp;
+mod rotation;
 
-use tracing::level_filters::LevelFilter;
+use std::path::PathBuf;
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing::Subscriber;
+use tracing_appender::non_blocking::{NonBlockingBuilder, WorkerGuard};
+use tracing_subscriber::Layer;
 use tracing_subscriber::fmt::MakeWriter;
-use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::layer::SubscriberExt as _;
-use tracing_subscriber::{Layer as _, filter};
+use tracing_subscriber::registry::LookupSpan;
 
-struct LogFileWriter(File);
+use crate::args::LogFormat;
 
-impl<'a> MakeWriter<'a> for LogFileWriter {
-    type Writer = Box<dyn std::io::Write + 'a>;
+pub use self::control::{LogControlError, LogController, LogSettings};
+pub use self::otlp::{OtlpConfig, set_parent_from_headers, trace_context_headers};
+use self::rotation::RotatingFile;
+pub use self::rotation::{ReopenHandle, RotationPolicy};
 
-    fn make_writer(&'a self) -> Self::Writer {
-        Box::new(&self.0)
-    }
+/// The configuration of the log file.
+#[derive(Debug, Clone)]
+pub struct LogFileConfig {
+    pub path: PathBuf,
+    pub format: LogFormat,
+    pub rotation: RotationPolicy,
+}
+
+/// Guard flushing the logs not written yet and the spans not exported yet when dropped.
+#[must_use = "the logs not written yet are lost when the guard is dropped"]
+pub struct LogGuard {
+    controller: LogController,
+    log_file: Option<(WorkerGuard, ReopenHandle)>,
+    tracer_provider: Option<SdkTracerProvider>,
 }
 
-impl TryFrom<&Path> for LogFileWriter {
-    type Error = anyhow::Error;
+impl LogGuard {
+    /// Returns the handle to change the level and the filter of the logs.
+    pub fn controller(&self) -> LogController {
+        self.controller.clone()
+    }
 
-    fn try_from(path: &Path) -> Result<Self, Self::Error> {
-        let file = OpenOptions::new().create(true).append(true).open(path)?;
-        Ok(Self(file))
+    /// Returns the handle to reopen the log file, if logging to a file.
+    pub fn log_file_reopen_handle(&self) -> Option<ReopenHandle> {
+        self.log_file.as_ref().map(|(_, reopen)| reopen.clone())
     }
 }
 
-/// Initialize the log configuration based on the CLI arguments
-pub fn init_log(log_level: LevelFilter, log_filter: Option<String>, log_file: Option<&Path>) {
-    let log_filter = Arc::new(log_filter);
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        let Some(tracer_provider) = self.tracer_provider.take() else {
+            return;
+        };
+        if let Err(err) = tracer_provider.shutdown() {
+            eprintln!("failed to shut down the OTLP exporter: {err}");
+        }
+    }
+}
 
-    let stdout_logger = tracing_subscriber::fmt::layer()
-        .compact()
-        .with_ansi(true)
-        .with_span_events(FmtSpan::CLOSE)
-        .with_line_number(true)
-        .with_writer(std::io::stdout);
-
-    let log_filter_t = log_filter.clone();
-    let registry =
-        tracing_subscriber::registry().with(stdout_logger.with_filter(log_level).with_filter(
-            filter::filter_fn(move |metadata| {
-                filter_log_matching_target(metadata, log_filter_t.clone())
-            }),
-        ));
-
-    if let Some(log_file) = log_file {
-        let file_logger = tracing_subscriber::fmt::layer()
-            .compact()
-            .with_ansi(false)
-            .with_span_events(FmtSpan::CLOSE)
-            .with_line_number(true)
-            .with_writer(LogFileWriter::try_from(log_file).expect("failed to init log writer"));
-
-        let registry = registry.with(file_logger.with_filter(log_level).with_filter(
-            filter::filter_fn(move |metadata| {
-                filter_log_matching_target(metadata, log_filter.clone())
-            }),
-        ));
-        tracing::subscriber::set_global_default(registry).expect("failed to set global default");
-    } else {
-        tracing::subscriber::set_global_default(registry).expect("failed to set global default");
+/// Initialize the log configuration based on the CLI arguments
+///
+/// Logs are filtered by the `settings`, which can be changed later through the [`LogController`] of the guard.
+/// They are written to stdout in `log_format` and, if `log_file` is set, to that file by a background thread.
+/// When `otlp` is set, the spans are also exported to the OTLP collector.
+pub fn init_log(
+    settings: LogSettings,
+    log_format: LogFormat,
+    log_file: Option<&LogFileConfig>,
+    otlp: Option<&OtlpConfig>,
+) -> LogGuard {
+    // a single filter applies to all the layers, so that it can be replaced at once
+    let (filter, controller) = LogController::new(settings).expect("failed to init log filter");
+    let stdout_logger = fmt_layer(log_format, std::io::stdout, true);
+
+    let (file_logger, log_file) = log_file
+        .map(|log_file| {
+            let file = RotatingFile::open(&log_file.path, log_file.rotation)
+                .expect("failed to init log writer");
+            let reopen = file.reopen_handle();
+            // lines are written by a background thread, which only holds the logging back when far behind
+            let (writer, guard) = NonBlockingBuilder::default().lossy(false).finish(file);
+            let logger = fmt_layer(log_file.format, writer, false);
+
+            (logger, (guard, reopen))
+        })
+        .unzip();
+
+    otlp::init_propagator();
+    let tracer_provider = otlp
+        .map(|config| otlp::init_tracer_provider(config).expect("failed to init OTLP exporter"));
+    let otlp_exporter = tracer_provider.as_ref().map(|tracer_provider| {
+        tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(env!("CARGO_PKG_NAME")))
+    });
+
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(stdout_logger)
+        .with(file_logger)
+        .with(otlp_exporter);
+    tracing::subscriber::set_global_default(registry).expect("failed to set global default");
+
+    LogGuard {
+        controller,
+        log_file,
+        tracer_provider,
     }
 }
 
-/// Log filter to only log messages with target matching the given target
-#[inline]
-fn filter_log_matching_target(metadata: &tracing::Metadata, target: Arc<Option<String>>) -> bool {
-    let Some(matching_target) = target.as_deref() else {
-        return true;
+/// Reopens the log file whenever a `SIGHUP` is received, as expected by `logrotate`.
+#[cfg(unix)]
+pub async fn reopen_on_hangup(reopen: ReopenHandle) {
+    let mut hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(err) => {
+            error!("failed to install SIGHUP handler: {err}");
+            return;
+        }
     };
 
-    let target = metadata.target();
-    target.starts_with(matching_target)
+    while hangup.recv().await.is_some() {
+        reopen.reopen();
+        info!("SIGHUP received; log file reopened");
+    }
+}
+
+/// Builds the layer formatting the logs in the given [`LogFormat`] into the given writer.
+///
+/// ANSI colors are only used by the human readable formats, when `ansi` is set.
+fn fmt_layer<S, W>(format: LogFormat, writer: W, ansi: bool) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let layer = tracing_subscriber::fmt::layer()
+        .with_line_number(true)
+        .with_target(true)
+        .with_writer(writer);
+
+    match format {
+        LogFormat::Compact => layer.compact().with_ansi(ansi).boxed(),
+        LogFormat::Pretty => layer.pretty().with_ansi(ansi).boxed(),
+        LogFormat::Json => layer
+            .json()
+            .with_ansi(false)
+            .with_current_span(true)
+            .with_span_list(true)
+            .boxed(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
+    use std::sync::{Arc, Mutex};
+
+    use tracing::level_filters::LevelFilter;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
     use super::*;
 
+    /// Writer collecting the logs in memory.
+    #[derive(Clone, Default)]
+    struct BufferWriter(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for BufferWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl BufferWriter {
+        /// Logs an event within a span with the given format, returning the written logs.
+        fn log_with(format: LogFormat) -> String {
+            let writer = Self::default();
+            let make_writer = {
+                let writer = writer.clone();
+                move || writer.clone()
+            };
+            let subscriber =
+                tracing_subscriber::registry().with(fmt_layer(format, make_writer, false));
+            tracing::subscriber::with_default(subscriber, || {
+                tracing::info_span!("request", request_id = "abc").in_scope(|| {
+                    tracing::info!(status = 200, "GET /pokemon/pikachu");
+                });
+            });
+
+            let logs = writer.0.lock().unwrap().clone();
+            String::from_utf8(logs).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_should_log_json_with_target_and_spans() {
+        let logs = BufferWriter::log_with(LogFormat::Json);
+        let line: serde_json::Value = serde_json::from_str(logs.trim()).expect("one JSON line");
+
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["target"], "pokedex_api::log::tests");
+        assert_eq!(line["fields"]["message"], "GET /pokemon/pikachu");
+        assert_eq!(line["fields"]["status"], 200);
+        assert_eq!(line["span"]["name"], "request");
+        assert_eq!(line["span"]["request_id"], "abc");
+        assert_eq!(line["spans"][0]["request_id"], "abc");
+    }
+
+    #[test]
+    fn test_should_log_human_readable_formats() {
+        let compact = BufferWriter::log_with(LogFormat::Compact);
+        assert_eq!(compact.lines().count(), 1);
+        assert!(compact.contains("GET /pokemon/pikachu"));
+        assert!(compact.contains("request_id=\"abc\""));
+
+        let pretty = BufferWriter::log_with(LogFormat::Pretty);
+        assert!(pretty.lines().count() > 1);
+        assert!(pretty.contains("GET /pokemon/pikachu"));
+    }
+
     #[test]
     fn test_should_init_log() {
-        init_log(LevelFilter::INFO, None, None);
+        let settings = LogSettings {
+            level: LevelFilter::INFO,
+            filter: Some("pokedex_api=debug,hyper=warn".to_string()),
+        };
+        let _guard = init_log(settings, LogFormat::Compact, None, None);
         tracing::info!("This is an info log");
         tracing::debug!("This is a debug log");
     }
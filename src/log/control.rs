@@ -0,0 +1,197 @@
+//! Runtime control of the level and of the filter of the logs.
+
+use std::sync::{Arc, Mutex};
+
+use thiserror::Error;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+
+/// The layer filtering the logs, which can be replaced at runtime.
+pub type ReloadableFilter = reload::Layer<EnvFilter, Registry>;
+
+/// Error changing the log configuration.
+#[derive(Debug, Error)]
+pub enum LogControlError {
+    #[error("Invalid log filter: {0}")]
+    InvalidFilter(String),
+    #[error("Failed to reload the log filter: {0}")]
+    Reload(String),
+}
+
+/// The level and the filter the logs are currently written with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogSettings {
+    /// The level of the targets not matched by the filter.
+    pub level: LevelFilter,
+    /// The [`EnvFilter`] directives, e.g. `pokedex_api::adapters=trace,hyper=warn`,
+    /// or a bare target prefix, e.g. `pokedex_api`, keeping only the logs of the matching targets.
+    pub filter: Option<String>,
+}
+
+impl LogSettings {
+    /// Builds the [`EnvFilter`] logging at the level, unless overridden by the directives of the filter.
+    ///
+    /// A bare target keeps its former meaning: only the logs of the targets starting with it are written, at the level.
+    pub fn env_filter(&self) -> Result<EnvFilter, LogControlError> {
+        let filter = self.filter.as_deref().unwrap_or_default();
+        let filter = if is_target_prefix(filter) {
+            EnvFilter::builder().parse(format!(
+                "{off},{filter}={level}",
+                off = LevelFilter::OFF,
+                level = self.level
+            ))
+        } else {
+            EnvFilter::builder()
+                .parse(filter)
+                .map(|filter| filter.add_directive(self.level.into()))
+        };
+
+        filter.map_err(|err| LogControlError::InvalidFilter(err.to_string()))
+    }
+}
+
+/// Returns whether the filter is a bare target prefix (e.g. `pokedex_api`) rather than [`EnvFilter`] directives.
+///
+/// A bare level (e.g. `debug`) is a directive, as is anything setting a level or matching spans.
+fn is_target_prefix(filter: &str) -> bool {
+    !filter.is_empty()
+        && !filter.contains(['=', ',', '['])
+        && filter.parse::<LevelFilter>().is_err()
+}
+
+/// Handle changing the level and the filter of the logs at runtime.
+#[derive(Debug, Clone)]
+pub struct LogController {
+    handle: reload::Handle<EnvFilter, Registry>,
+    settings: Arc<Mutex<LogSettings>>,
+}
+
+impl LogController {
+    /// Creates the reloadable filter layer with the given settings, and its controller.
+    pub fn new(settings: LogSettings) -> Result<(ReloadableFilter, Self), LogControlError> {
+        let (layer, handle) = reload::Layer::new(settings.env_filter()?);
+        let controller = Self {
+            handle,
+            settings: Arc::new(Mutex::new(settings)),
+        };
+
+        Ok((layer, controller))
+    }
+
+    /// Creates a controller of a filter which is not installed, for testing.
+    #[cfg(test)]
+    pub fn detached(settings: LogSettings) -> Self {
+        let (layer, controller) = Self::new(settings).expect("valid log filter");
+        // the handle only works as long as the layer is alive
+        std::mem::forget(layer);
+        controller
+    }
+
+    /// Returns the current settings.
+    pub fn settings(&self) -> LogSettings {
+        self.settings
+            .lock()
+            .expect("log settings lock poisoned")
+            .clone()
+    }
+
+    /// Replaces the filter of the logs with the given settings.
+    pub fn update(&self, settings: LogSettings) -> Result<(), LogControlError> {
+        let mut current = self.settings.lock().expect("log settings lock poisoned");
+        self.handle
+            .reload(settings.env_filter()?)
+            .map_err(|err| LogControlError::Reload(err.to_string()))?;
+        // logged as a warning, so that it shows up unless the logs are almost off
+        warn!(
+            "log level set to {level}, filter set to {filter}",
+            level = settings.level,
+            filter = settings.filter.as_deref().unwrap_or("none")
+        );
+        *current = settings;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn settings(level: LevelFilter, filter: Option<&str>) -> LogSettings {
+        LogSettings {
+            level,
+            filter: filter.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_should_build_env_filter_with_directives() {
+        let filter = settings(
+            LevelFilter::INFO,
+            Some("pokedex_api::adapters=trace,hyper=warn"),
+        )
+        .env_filter()
+        .unwrap();
+        let directives = filter.to_string();
+
+        assert!(directives.contains("pokedex_api::adapters=trace"));
+        assert!(directives.contains("hyper=warn"));
+        assert!(directives.contains("info"));
+        assert_eq!(filter.max_level_hint(), Some(LevelFilter::TRACE));
+    }
+
+    #[test]
+    fn test_should_build_env_filter_without_directives() {
+        let filter = settings(LevelFilter::WARN, None).env_filter().unwrap();
+        assert_eq!(filter.max_level_hint(), Some(LevelFilter::WARN));
+    }
+
+    #[test]
+    fn test_should_keep_only_targets_matching_bare_prefix() {
+        let filter = settings(LevelFilter::INFO, Some("pokedex_api"))
+            .env_filter()
+            .unwrap();
+        let directives = filter.to_string();
+
+        assert!(directives.contains("pokedex_api=info"));
+        assert!(directives.contains("off"));
+        // the prefix is not traced
+        assert_eq!(filter.max_level_hint(), Some(LevelFilter::INFO));
+
+        assert!(is_target_prefix("pokedex_api::adapters"));
+        assert!(!is_target_prefix("debug"));
+        assert!(!is_target_prefix("pokedex_api=debug"));
+        assert!(!is_target_prefix("pokedex_api,hyper"));
+        assert!(!is_target_prefix(""));
+    }
+
+    #[test]
+    fn test_should_reject_invalid_filter() {
+        assert!(matches!(
+            settings(LevelFilter::INFO, Some("pokedex_api=loud")).env_filter(),
+            Err(LogControlError::InvalidFilter(_))
+        ));
+    }
+
+    #[test]
+    fn test_should_update_settings() {
+        let controller = LogController::detached(settings(LevelFilter::INFO, None));
+        let updated = settings(LevelFilter::DEBUG, Some("hyper=warn"));
+
+        controller.update(updated.clone()).unwrap();
+        assert_eq!(controller.settings(), updated);
+    }
+
+    #[test]
+    fn test_should_keep_settings_on_invalid_filter() {
+        let controller = LogController::detached(settings(LevelFilter::INFO, None));
+
+        assert!(
+            controller
+                .update(settings(LevelFilter::DEBUG, Some("pokedex_api=loud")))
+                .is_err()
+        );
+        assert_eq!(controller.settings(), settings(LevelFilter::INFO, None));
+    }
+}
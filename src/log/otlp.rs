@@ -0,0 +1,210 @@
+//! Export of the spans to an OTLP collector and propagation of the W3C trace context.
+
+use opentelemetry::global;
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_otlp::{Protocol, SpanExporter, WithExportConfig as _};
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use reqwest::header::HeaderMap;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+/// The environment variables which set the endpoint of the collector.
+const ENDPOINT_ENV_VARS: [&str; 2] = [
+    "OTEL_EXPORTER_OTLP_ENDPOINT",
+    "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT",
+];
+/// The path the spans are exported to, when exported over HTTP.
+const HTTP_TRACES_PATH: &str = "v1/traces";
+
+/// The configuration of the OTLP exporter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtlpConfig {
+    /// The endpoint of the collector; when `None`, it is read from the environment.
+    endpoint: Option<String>,
+    protocol: Protocol,
+}
+
+impl OtlpConfig {
+    /// Returns the configuration of the exporter, if the endpoint is given or set in the environment.
+    pub fn new(endpoint: Option<String>, protocol: impl Into<Protocol>) -> Option<Self> {
+        let endpoint_from_env = ENDPOINT_ENV_VARS
+            .iter()
+            .any(|var| std::env::var_os(var).is_some_and(|value| !value.is_empty()));
+        if endpoint.is_none() && !endpoint_from_env {
+            return None;
+        }
+
+        Some(Self {
+            endpoint,
+            protocol: protocol.into(),
+        })
+    }
+
+    /// Builds the exporter for the configured collector.
+    fn span_exporter(&self) -> anyhow::Result<SpanExporter> {
+        let exporter = match self.protocol {
+            Protocol::Grpc => {
+                let builder = SpanExporter::builder().with_tonic();
+                match &self.endpoint {
+                    Some(endpoint) => builder.with_endpoint(endpoint).build()?,
+                    None => builder.build()?,
+                }
+            }
+            protocol => {
+                // like OTEL_EXPORTER_OTLP_ENDPOINT, the endpoint is the base URL of the collector
+                let builder = SpanExporter::builder().with_http().with_protocol(protocol);
+                match &self.endpoint {
+                    Some(endpoint) => builder
+                        .with_endpoint(format!(
+                            "{}/{HTTP_TRACES_PATH}",
+                            endpoint.trim_end_matches('/')
+                        ))
+                        .build()?,
+                    None => builder.build()?,
+                }
+            }
+        };
+
+        Ok(exporter)
+    }
+}
+
+/// Initializes the tracer provider exporting the spans in batches to the configured collector.
+///
+/// Must be called within a Tokio runtime.
+pub fn init_tracer_provider(config: &OtlpConfig) -> anyhow::Result<SdkTracerProvider> {
+    let resource = Resource::builder()
+        .with_service_name(env!("CARGO_PKG_NAME"))
+        .build();
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(config.span_exporter()?)
+        .with_resource(resource)
+        .build())
+}
+
+/// Installs the W3C trace context propagator, used to read and write the `traceparent` headers.
+pub fn init_propagator() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+}
+
+/// Sets the parent of the given span to the trace context of the incoming request headers, if any.
+pub fn set_parent_from_headers(span: &Span, headers: &HeaderMap) {
+    let context =
+        global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    // fails when the spans are not exported, in which case there is nothing to link
+    let _ = span.set_parent(context);
+}
+
+/// Returns the headers carrying the trace context of the current span, to propagate it to the upstream services.
+pub fn trace_context_headers() -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let context = Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut HeaderInjector(&mut headers))
+    });
+
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+
+    use opentelemetry::trace::TracerProvider as _;
+    use tracing_subscriber::layer::SubscriberExt as _;
+
+    use super::*;
+
+    const TRACE_ID: &str = "4bf92f3577b34e0ea0b6b8d8cb5ec0a1";
+    const TRACEPARENT: &str = "00-4bf92f3577b34e0ea0b6b8d8cb5ec0a1-00f067aa0ba902b7-01";
+
+    /// Runs the given function with a subscriber recording the spans into a tracer with no exporter.
+    fn with_tracer<T>(f: impl FnOnce() -> T) -> T {
+        init_propagator();
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, f)
+    }
+
+    /// Returns the `traceparent` header carrying the trace context of the current span.
+    fn traceparent() -> Option<String> {
+        trace_context_headers()
+            .get("traceparent")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+
+    #[test]
+    fn test_should_propagate_incoming_trace_context() {
+        let traceparent = with_tracer(|| {
+            let mut headers = HeaderMap::new();
+            headers.insert("traceparent", TRACEPARENT.parse().unwrap());
+
+            let span = tracing::info_span!("request");
+            set_parent_from_headers(&span, &headers);
+            span.in_scope(|| {
+                let child = tracing::info_span!("upstream request");
+                child.in_scope(traceparent)
+            })
+        })
+        .expect("traceparent header");
+
+        let fields = traceparent.split('-').collect::<Vec<_>>();
+        assert_eq!(fields[0], "00");
+        assert_eq!(fields[1], TRACE_ID);
+        // the parent is the span of the upstream request, rather than the incoming one
+        assert_ne!(fields[2], "00f067aa0ba902b7");
+        assert_eq!(fields[3], "01");
+    }
+
+    #[test]
+    fn test_should_start_trace_without_incoming_trace_context() {
+        let traceparent = with_tracer(|| {
+            let span = tracing::info_span!("request");
+            set_parent_from_headers(&span, &HeaderMap::new());
+            span.in_scope(traceparent)
+        })
+        .expect("traceparent header");
+
+        assert!(traceparent.starts_with("00-"));
+        assert!(!traceparent.contains(TRACE_ID));
+    }
+
+    #[test]
+    fn test_should_not_propagate_outside_of_spans() {
+        init_propagator();
+        assert!(traceparent().is_none());
+    }
+
+    #[test]
+    fn test_should_configure_exporter_with_endpoint() {
+        let config =
+            OtlpConfig::new(Some("http://localhost:4317".to_string()), Protocol::Grpc).unwrap();
+        assert_eq!(config.endpoint.as_deref(), Some("http://localhost:4317"));
+        assert_eq!(config.protocol, Protocol::Grpc);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore] // Ignored since it requires a collector listening on localhost
+    async fn test_should_export_spans_to_local_collector() {
+        for (endpoint, protocol) in [
+            ("http://localhost:4317", Protocol::Grpc),
+            ("http://localhost:4318", Protocol::HttpBinary),
+        ] {
+            let config = OtlpConfig::new(Some(endpoint.to_string()), protocol).unwrap();
+            let provider = init_tracer_provider(&config).unwrap();
+            let subscriber = tracing_subscriber::registry()
+                .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+            tracing::subscriber::with_default(subscriber, || {
+                tracing::info_span!("test span", protocol = ?protocol).in_scope(|| {});
+            });
+
+            provider.force_flush().expect("spans exported");
+            provider.shutdown().expect("provider shut down");
+        }
+    }
+}
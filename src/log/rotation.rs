@@ -0,0 +1,566 @@
+//! Log file rotating by time and size, keeping the last rotated files.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
+
+use chrono::{DateTime, Duration, DurationRound as _, NaiveDateTime, Utc};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use crate::args::LogRotation;
+
+/// The format of the timestamp appended to the name of the rotated files, which sorts them chronologically.
+const ROTATED_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S-%3f";
+/// The extension of the compressed rotated files.
+const GZIP_EXTENSION: &str = "gz";
+/// The extension of the rotated files being compressed.
+const TMP_EXTENSION: &str = "tmp";
+
+/// When the log file is rotated and how many rotated files are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RotationPolicy {
+    /// Rotates the file at the start of each hour or day.
+    pub interval: LogRotation,
+    /// Rotates the file before it grows over this size in bytes.
+    pub max_size: Option<u64>,
+    /// The amount of rotated files kept; the older ones are removed.
+    pub max_files: usize,
+    /// Whether the rotated files are gzipped.
+    pub compress: bool,
+}
+
+impl Default for RotationPolicy {
+    fn default() -> Self {
+        Self {
+            interval: LogRotation::Never,
+            max_size: None,
+            max_files: 7,
+            compress: false,
+        }
+    }
+}
+
+/// Handle to request the log file to be reopened, e.g. after it has been moved by `logrotate`.
+#[derive(Debug, Clone, Default)]
+pub struct ReopenHandle(Arc<AtomicBool>);
+
+impl ReopenHandle {
+    /// Requests the log file to be reopened before the next line is written.
+    pub fn reopen(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the log file must be reopened, clearing the request.
+    fn take(&self) -> bool {
+        self.0.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// A log file which is rotated according to its [`RotationPolicy`].
+///
+/// Rotated files are renamed after the log file with the time of the rotation appended (e.g. `pokedex.log.20251019-000000-000`),
+/// followed by a counter if several rotations happen within the same millisecond (e.g. `pokedex.log.20251019-000000-000.1`).
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    policy: RotationPolicy,
+    /// The size of the current file.
+    size: u64,
+    /// The time the current file is rotated at, if rotated by time.
+    next_rotation: Option<DateTime<Utc>>,
+    reopen: ReopenHandle,
+    /// The worker compressing the rotated files and removing the old ones, if they are compressed.
+    cleanup_worker: Option<mpsc::Sender<PathBuf>>,
+}
+
+impl RotatingFile {
+    /// Opens the log file at the given path, appending to it if it exists.
+    pub fn open(path: impl Into<PathBuf>, policy: RotationPolicy) -> io::Result<Self> {
+        let path = path.into();
+        let file = Self::open_file(&path)?;
+        let size = file.metadata()?.len();
+        let now = Utc::now();
+
+        Ok(Self {
+            path: path.clone(),
+            file,
+            policy,
+            size,
+            next_rotation: Self::next_rotation(policy.interval, now),
+            reopen: ReopenHandle::default(),
+            cleanup_worker: policy
+                .compress
+                .then(|| spawn_cleanup_worker(path.clone(), policy)),
+        })
+    }
+
+    /// Returns the handle to request the file to be reopened.
+    pub fn reopen_handle(&self) -> ReopenHandle {
+        self.reopen.clone()
+    }
+
+    fn open_file(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    /// Returns the start of the next hour or day after `now`.
+    fn next_rotation(interval: LogRotation, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let period = match interval {
+            LogRotation::Never => return None,
+            LogRotation::Hourly => Duration::hours(1),
+            LogRotation::Daily => Duration::days(1),
+        };
+
+        now.duration_trunc(period)
+            .ok()
+            .map(|period_start| period_start + period)
+    }
+
+    /// Writes the buffer as if at the given time, rotating the file first if needed.
+    fn write_at(&mut self, buf: &[u8], now: DateTime<Utc>) -> io::Result<usize> {
+        if self.reopen.take() {
+            self.reopen_file()?;
+        }
+        if self.should_rotate(buf.len() as u64, now) {
+            self.rotate(now)?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    /// Returns whether the file must be rotated before writing `len` bytes at the given time.
+    fn should_rotate(&self, len: u64, now: DateTime<Utc>) -> bool {
+        let time_elapsed = self
+            .next_rotation
+            .is_some_and(|next_rotation| now >= next_rotation);
+        // a single line larger than the limit is written to an empty file rather than rotating forever
+        let size_exceeded = self
+            .policy
+            .max_size
+            .is_some_and(|max_size| self.size > 0 && self.size + len > max_size);
+
+        time_elapsed || size_exceeded
+    }
+
+    /// Reopens the file at its path, which is created if it has been moved away.
+    fn reopen_file(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        self.file = Self::open_file(&self.path)?;
+        self.size = self.file.metadata()?.len();
+        Ok(())
+    }
+
+    /// Moves the current file aside and starts a new one, then removes the rotated files exceeding the retention.
+    fn rotate(&mut self, now: DateTime<Utc>) -> io::Result<()> {
+        self.file.flush()?;
+        let rotated = self.rotated_path(now);
+        fs::rename(&self.path, &rotated)?;
+        self.file = Self::open_file(&self.path)?;
+        self.size = 0;
+        self.next_rotation = Self::next_rotation(self.policy.interval, now);
+
+        // compression may take a while, so it doesn't hold the writes back
+        match &self.cleanup_worker {
+            Some(worker) => {
+                if let Err(mpsc::SendError(rotated)) = worker.send(rotated) {
+                    cleanup(&self.path, &rotated, self.policy);
+                }
+            }
+            None => cleanup(&self.path, &rotated, self.policy),
+        }
+
+        Ok(())
+    }
+
+    /// Returns the path the file is moved to when rotated at the given time.
+    ///
+    /// A counter is appended if a file was already rotated at that time, so that it's never overwritten.
+    fn rotated_path(&self, now: DateTime<Utc>) -> PathBuf {
+        let mut file_name = self.path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(format!(".{}", now.format(ROTATED_TIMESTAMP_FORMAT)));
+        let mut rotated = self.path.with_file_name(&file_name);
+
+        let mut counter = 0;
+        while rotated.exists() || with_extension(&rotated, GZIP_EXTENSION).exists() {
+            counter += 1;
+            let mut file_name = file_name.clone();
+            file_name.push(format!(".{counter}"));
+            rotated = self.path.with_file_name(file_name);
+        }
+
+        rotated
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_at(buf, Utc::now())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// Spawns the worker compressing the files rotated from the log file at the given path, then removing the old ones.
+///
+/// A single worker handles all the rotations one after the other, so the cleanups never overlap.
+fn spawn_cleanup_worker(path: PathBuf, policy: RotationPolicy) -> mpsc::Sender<PathBuf> {
+    let (sender, receiver) = mpsc::channel::<PathBuf>();
+    std::thread::spawn(move || {
+        for rotated in receiver {
+            cleanup(&path, &rotated, policy);
+        }
+    });
+
+    sender
+}
+
+/// Compresses the file rotated from the log file at the given path, if required, then removes the old rotated files.
+fn cleanup(path: &Path, rotated: &Path, policy: RotationPolicy) {
+    if policy.compress {
+        if let Err(err) = compress(rotated) {
+            eprintln!("failed to compress {}: {err}", rotated.display());
+        }
+    }
+    if let Err(err) = remove_old_files(path, policy.max_files) {
+        eprintln!("failed to remove the old log files: {err}");
+    }
+}
+
+/// Returns the path with the given extension appended.
+fn with_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_os_string();
+    path.push(format!(".{extension}"));
+    PathBuf::from(path)
+}
+
+/// Gzips the given file, replacing it.
+///
+/// The file is compressed into a temporary file first, so a partially compressed file is never taken for a rotated one.
+fn compress(path: &Path) -> io::Result<()> {
+    let compressed_path = with_extension(path, GZIP_EXTENSION);
+    let tmp_path = with_extension(&compressed_path, TMP_EXTENSION);
+
+    if let Err(err) = gzip(path, &tmp_path) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err);
+    }
+    fs::rename(&tmp_path, &compressed_path)?;
+    fs::remove_file(path)
+}
+
+/// Writes the gzipped content of the file at `source` to the file at `target`.
+fn gzip(source: &Path, target: &Path) -> io::Result<()> {
+    let mut encoder = GzEncoder::new(File::create(target)?, Compression::default());
+    io::copy(&mut File::open(source)?, &mut encoder)?;
+    encoder.finish()?.sync_all()
+}
+
+/// Removes the oldest rotated files of the log file at the given path, keeping the last `max_files`.
+///
+/// Only the finished rotated files are counted, not the ones being compressed nor any other file;
+/// the files which are already gone are skipped.
+fn remove_old_files(path: &Path, max_files: usize) -> io::Result<()> {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Ok(());
+    };
+    let prefix = format!("{file_name}.");
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut rotated = fs::read_dir(dir)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            rotation_order(name.strip_prefix(&prefix)?).map(|order| (order, path.clone()))
+        })
+        .collect::<Vec<_>>();
+    rotated.sort();
+
+    let excess = rotated.len().saturating_sub(max_files);
+    for (_, path) in rotated.into_iter().take(excess) {
+        match fs::remove_file(path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the time and the counter of the rotation from the suffix of the name of a rotated file
+/// (e.g. `20251019-000000-000.1.gz`), or `None` if it's not the one of a finished rotated file.
+fn rotation_order(suffix: &str) -> Option<(NaiveDateTime, u32)> {
+    let suffix = suffix
+        .strip_suffix(&format!(".{GZIP_EXTENSION}"))
+        .unwrap_or(suffix);
+    let (timestamp, counter) = match suffix.split_once('.') {
+        Some((timestamp, counter)) => (timestamp, counter.parse().ok()?),
+        None => (suffix, 0),
+    };
+    let time = NaiveDateTime::parse_from_str(timestamp, ROTATED_TIMESTAMP_FORMAT).ok()?;
+
+    Some((time, counter))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use chrono::TimeZone as _;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 10, 19, hour, minute, 0).unwrap()
+    }
+
+    /// Returns the names of the files in the directory, sorted.
+    fn files(dir: &TempDir) -> Vec<String> {
+        let mut files = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    fn open(dir: &TempDir, policy: RotationPolicy, now: DateTime<Utc>) -> RotatingFile {
+        let mut file = RotatingFile::open(dir.path().join("test.log"), policy).unwrap();
+        file.next_rotation = RotatingFile::next_rotation(policy.interval, now);
+        file
+    }
+
+    #[test]
+    fn test_should_compute_next_rotation() {
+        assert_eq!(
+            RotatingFile::next_rotation(LogRotation::Never, at(10, 30)),
+            None
+        );
+        assert_eq!(
+            RotatingFile::next_rotation(LogRotation::Hourly, at(10, 30)),
+            Some(at(11, 0))
+        );
+        assert_eq!(
+            RotatingFile::next_rotation(LogRotation::Daily, at(10, 30)),
+            Some(Utc.with_ymd_and_hms(2025, 10, 20, 0, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_should_rotate_by_time() {
+        let dir = TempDir::new().unwrap();
+        let policy = RotationPolicy {
+            interval: LogRotation::Hourly,
+            ..Default::default()
+        };
+        let mut file = open(&dir, policy, at(10, 30));
+
+        file.write_at(b"first\n", at(10, 45)).unwrap();
+        assert_eq!(files(&dir), vec!["test.log"]);
+        file.write_at(b"second\n", at(11, 5)).unwrap();
+
+        assert_eq!(
+            files(&dir),
+            vec!["test.log", "test.log.20251019-110500-000"]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log.20251019-110500-000")).unwrap(),
+            "first\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log")).unwrap(),
+            "second\n"
+        );
+        assert_eq!(file.next_rotation, Some(at(12, 0)));
+    }
+
+    #[test]
+    fn test_should_rotate_by_size() {
+        let dir = TempDir::new().unwrap();
+        let policy = RotationPolicy {
+            max_size: Some(10),
+            ..Default::default()
+        };
+        let mut file = open(&dir, policy, at(10, 0));
+
+        file.write_at(b"12345\n", at(10, 0)).unwrap();
+        file.write_at(b"678\n", at(10, 1)).unwrap();
+        assert_eq!(files(&dir), vec!["test.log"]);
+        file.write_at(b"9\n", at(10, 2)).unwrap();
+
+        assert_eq!(
+            files(&dir),
+            vec!["test.log", "test.log.20251019-100200-000"]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log")).unwrap(),
+            "9\n"
+        );
+    }
+
+    #[test]
+    fn test_should_write_lines_larger_than_max_size() {
+        let dir = TempDir::new().unwrap();
+        let policy = RotationPolicy {
+            max_size: Some(2),
+            ..Default::default()
+        };
+        let mut file = open(&dir, policy, at(10, 0));
+
+        file.write_at(b"too long\n", at(10, 0)).unwrap();
+        assert_eq!(files(&dir), vec!["test.log"]);
+    }
+
+    #[test]
+    fn test_should_keep_last_rotated_files() {
+        let dir = TempDir::new().unwrap();
+        let policy = RotationPolicy {
+            max_size: Some(1),
+            max_files: 2,
+            ..Default::default()
+        };
+        let mut file = open(&dir, policy, at(10, 0));
+
+        for minute in 0..5 {
+            file.write_at(b"line\n", at(10, minute)).unwrap();
+        }
+
+        assert_eq!(
+            files(&dir),
+            vec![
+                "test.log",
+                "test.log.20251019-100300-000",
+                "test.log.20251019-100400-000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_not_overwrite_files_rotated_at_the_same_time() {
+        let dir = TempDir::new().unwrap();
+        let policy = RotationPolicy {
+            max_size: Some(1),
+            ..Default::default()
+        };
+        let mut file = open(&dir, policy, at(10, 0));
+
+        for line in ["first\n", "second\n", "third\n", "fourth\n"] {
+            file.write_at(line.as_bytes(), at(10, 0)).unwrap();
+        }
+
+        assert_eq!(
+            files(&dir),
+            vec![
+                "test.log",
+                "test.log.20251019-100000-000",
+                "test.log.20251019-100000-000.1",
+                "test.log.20251019-100000-000.2"
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log.20251019-100000-000.2")).unwrap(),
+            "third\n"
+        );
+    }
+
+    #[test]
+    fn test_should_count_only_finished_rotated_files() {
+        let dir = TempDir::new().unwrap();
+        for name in [
+            "test.log.20251019-090000-000.gz",
+            "test.log.20251019-100000-000",
+            "test.log.20251019-100000-000.1.gz",
+            "test.log.20251019-110000-000.gz.tmp",
+            "test.log.backup",
+        ] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+
+        remove_old_files(&dir.path().join("test.log"), 2).unwrap();
+
+        assert_eq!(
+            files(&dir),
+            vec![
+                "test.log.20251019-100000-000",
+                "test.log.20251019-100000-000.1.gz",
+                "test.log.20251019-110000-000.gz.tmp",
+                "test.log.backup"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_order_rotations() {
+        let time = |minute| at(10, minute).naive_utc();
+        assert_eq!(rotation_order("20251019-100000-000"), Some((time(0), 0)));
+        assert_eq!(rotation_order("20251019-100100-000.gz"), Some((time(1), 0)));
+        assert_eq!(
+            rotation_order("20251019-100100-000.2.gz"),
+            Some((time(1), 2))
+        );
+        assert_eq!(rotation_order("20251019-100100-000.gz.tmp"), None);
+        assert_eq!(rotation_order("backup"), None);
+    }
+
+    #[test]
+    fn test_should_compress_rotated_files() {
+        let dir = TempDir::new().unwrap();
+        let policy = RotationPolicy {
+            max_size: Some(1),
+            compress: true,
+            ..Default::default()
+        };
+        let mut file = open(&dir, policy, at(10, 0));
+
+        file.write_at(b"first\n", at(10, 0)).unwrap();
+        file.write_at(b"second\n", at(10, 1)).unwrap();
+
+        // compression runs in the background
+        let compressed = dir.path().join("test.log.20251019-100100-000.gz");
+        for _ in 0..100 {
+            if files(&dir).len() == 2 && compressed.exists() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            files(&dir),
+            vec!["test.log", "test.log.20251019-100100-000.gz"]
+        );
+
+        let mut decoder = flate2::read::GzDecoder::new(File::open(compressed).unwrap());
+        let mut content = String::new();
+        io::Read::read_to_string(&mut decoder, &mut content).unwrap();
+        assert_eq!(content, "first\n");
+    }
+
+    #[test]
+    fn test_should_reopen_moved_file() {
+        let dir = TempDir::new().unwrap();
+        let mut file = open(&dir, RotationPolicy::default(), at(10, 0));
+
+        file.write_at(b"first\n", at(10, 0)).unwrap();
+        fs::rename(dir.path().join("test.log"), dir.path().join("moved.log")).unwrap();
+        file.reopen_handle().reopen();
+        file.write_at(b"second\n", at(10, 1)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("moved.log")).unwrap(),
+            "first\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log")).unwrap(),
+            "second\n"
+        );
+    }
+}
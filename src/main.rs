@@ -7,14 +7,31 @@ use std::time::Duration;
 
 use url::Url;
 
+use crate::adapters::battle::PokeApiBattleAdapter;
+use crate::adapters::item::PokeApiItemAdapter;
 use crate::adapters::pokedex::PokeApiAdapter;
 use crate::adapters::translation::FunTranslationsAdapter;
+use crate::autocomplete::AutocompleteIndex;
+use crate::health::HealthChecker;
+use crate::mechanics::TypeChartCache;
+use crate::quiz::QuizSessions;
+use crate::rate_limit::{ClientIdentifier, RateLimit, RateLimiter, load_api_keys};
+use crate::search::SearchIndex;
+use crate::sprites::SpriteCache;
 use crate::web::AppState;
 
 mod adapters;
 mod args;
+mod autocomplete;
+mod health;
 mod log;
+mod mechanics;
 mod model;
+mod monitoring;
+mod quiz;
+mod rate_limit;
+mod search;
+mod sprites;
 mod web;
 
 #[tokio::main]
@@ -23,11 +40,31 @@ async fn main() -> anyhow::Result<()> {
     let args: args::Args = argh::from_env();
 
     // initialize logging
-    log::init_log(
-        args.log_level.into(),
-        args.log_filter,
-        args.log_file.as_deref(),
+    let log_file = args.log_file.map(|path| log::LogFileConfig {
+        path,
+        format: args.log_file_format.unwrap_or(args.log_format),
+        rotation: log::RotationPolicy {
+            interval: args.log_rotation,
+            max_size: args.log_max_size_mb.map(|size| size * 1024 * 1024),
+            max_files: args.log_max_files,
+            compress: args.log_compress,
+        },
+    });
+    let otlp = log::OtlpConfig::new(args.otlp_endpoint, args.otlp_protocol);
+    let log_settings = log::LogSettings {
+        level: args.log_level.into(),
+        filter: args.log_filter,
+    };
+    let log_guard = log::init_log(
+        log_settings,
+        args.log_format,
+        log_file.as_ref(),
+        otlp.as_ref(),
     );
+    #[cfg(unix)]
+    if let Some(reopen) = log_guard.log_file_reopen_handle() {
+        tokio::spawn(log::reopen_on_hangup(reopen));
+    }
 
     info!(
         "Starting {app_name} v{app_version}",
@@ -48,6 +85,26 @@ async fn main() -> anyhow::Result<()> {
             )
         })
         .unwrap_or_default();
+    let battle_adapter = args
+        .pokeapi_endpoint
+        .as_ref()
+        .map(|endpoint| {
+            PokeApiBattleAdapter::new(
+                Url::parse(endpoint).expect("invalid endpoint url"),
+                adapters_timeout,
+            )
+        })
+        .unwrap_or_default();
+    let item_adapter = args
+        .pokeapi_endpoint
+        .as_ref()
+        .map(|endpoint| {
+            PokeApiItemAdapter::new(
+                Url::parse(endpoint).expect("invalid endpoint url"),
+                adapters_timeout,
+            )
+        })
+        .unwrap_or_default();
     let translation_adapter = args
         .funtranslation_endpoint
         .as_ref()
@@ -66,11 +123,72 @@ async fn main() -> anyhow::Result<()> {
         port = args.port
     ))?;
 
+    // the admin token is read from a file, so it doesn't show up in the process list
+    let admin_token = match &args.admin_token_file {
+        Some(path) => {
+            let token = std::fs::read_to_string(path).map_err(|err| {
+                anyhow::anyhow!(
+                    "failed to read the admin token from {}: {err}",
+                    path.display()
+                )
+            })?;
+            let token = token.trim();
+            if token.is_empty() {
+                anyhow::bail!("the admin token file {} is empty", path.display());
+            }
+            Some(token.to_string())
+        }
+        None => None,
+    };
+
+    // init the rate limiter; the translated routes fall under the default limit, unless they have their own
+    let api_keys = match &args.api_keys_file {
+        Some(path) => load_api_keys(path).map_err(|err| {
+            anyhow::anyhow!("failed to read API keys from {}: {err}", path.display())
+        })?,
+        None => Default::default(),
+    };
+    let rate_limit = |per_minute: Option<u32>, burst: Option<u32>| {
+        per_minute.map(|per_minute| RateLimit::new(per_minute, burst.unwrap_or(per_minute)))
+    };
+    let rate_limiter = RateLimiter::new(
+        rate_limit(args.rate_limit_per_minute, args.rate_limit_burst),
+        rate_limit(
+            args.translated_rate_limit_per_minute,
+            args.translated_rate_limit_burst,
+        ),
+    )
+    .with_clients(ClientIdentifier::new(args.trusted_proxy, api_keys));
+
     // run web server
     debug!("About to start web server...");
     let app_state = AppState {
         pokedex_adapter,
         translation_adapter,
+        battle_adapter,
+        item_adapter,
+        type_chart: TypeChartCache::default(),
+        sprite_cache: args
+            .sprite_cache_dir
+            .map(SpriteCache::new)
+            .unwrap_or_default(),
+        quiz_sessions: QuizSessions::new(
+            Duration::from_secs(args.quiz_ttl_secs),
+            args.quiz_max_attempts,
+        ),
+        search_index: SearchIndex::new(
+            args.search_snapshot,
+            Duration::from_secs(args.search_rebuild_interval_secs),
+        ),
+        autocomplete_index: AutocompleteIndex::new(
+            args.autocomplete_dataset,
+            Duration::from_secs(args.autocomplete_refresh_interval_secs),
+        ),
+        health_checker: HealthChecker::new(Duration::from_secs(args.health_cache_ttl_secs)),
+        metrics: monitoring::install(),
+        log_controller: log_guard.controller(),
+        admin_token,
+        rate_limiter,
     };
     web::WebServer::new(app_state).run(listen_to).await?;
 
@@ -0,0 +1,12 @@
+//! This module implements the game mechanics which are computed by the application
+//! on top of the data provided by the adapters.
+
+mod comparison;
+mod damage;
+mod team;
+mod type_chart;
+
+pub use self::comparison::compare_pokemon;
+pub use self::damage::calculate_damage;
+pub use self::team::{MAX_TEAM_SIZE, analyze_team};
+pub use self::type_chart::{LATEST_GENERATION, TYPES, TypeChartCache};
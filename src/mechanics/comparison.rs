@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use crate::adapters::battle::{BattleAdapter, BattleAdapterResult};
+use crate::mechanics::{LATEST_GENERATION, TypeChartCache};
+use crate::model::{Pokemon, PokemonComparison, StatDeltas, Stats};
+
+/// Compares the two Pokémon side by side, using the type chart of the latest generation
+/// for the type matchups.
+pub async fn compare_pokemon<B>(
+    type_chart: &TypeChartCache,
+    adapter: &B,
+    a: Pokemon,
+    b: Pokemon,
+) -> BattleAdapterResult<PokemonComparison>
+where
+    B: BattleAdapter,
+{
+    let a_against_b = type_matchups(type_chart, adapter, &a, &b).await?;
+    let b_against_a = type_matchups(type_chart, adapter, &b, &a).await?;
+
+    Ok(PokemonComparison {
+        stat_deltas: stat_deltas(&a.stats, &b.stats),
+        height_delta: i32::from(a.height) - i32::from(b.height),
+        weight_delta: i32::from(a.weight) - i32::from(b.weight),
+        legendary_differs: a.is_legendary != b.is_legendary,
+        a_against_b,
+        b_against_a,
+        a,
+        b,
+    })
+}
+
+/// Computes the damage multiplier of each type of the attacker against the defender.
+async fn type_matchups<B>(
+    type_chart: &TypeChartCache,
+    adapter: &B,
+    attacker: &Pokemon,
+    defender: &Pokemon,
+) -> BattleAdapterResult<BTreeMap<String, f32>>
+where
+    B: BattleAdapter,
+{
+    let mut matchups = BTreeMap::new();
+    for attacking_type in &attacker.types {
+        let multiplier = type_chart
+            .effectiveness(adapter, LATEST_GENERATION, attacking_type, &defender.types)
+            .await?;
+        matchups.insert(attacking_type.clone(), multiplier);
+    }
+
+    Ok(matchups)
+}
+
+/// Computes the deltas of the base stats.
+fn stat_deltas(a: &Stats, b: &Stats) -> StatDeltas {
+    let delta = |a: u16, b: u16| i32::from(a) - i32::from(b);
+
+    StatDeltas {
+        hp: delta(a.hp, b.hp),
+        attack: delta(a.attack, b.attack),
+        defense: delta(a.defense, b.defense),
+        special_attack: delta(a.special_attack, b.special_attack),
+        special_defense: delta(a.special_defense, b.special_defense),
+        speed: delta(a.speed, b.speed),
+        total: delta(a.total(), b.total()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::adapters::battle::{BattleAdapterError, MockBattleAdapter};
+    use crate::model::{DamageRelations, PokemonType};
+
+    #[tokio::test]
+    async fn test_should_compare_pokemon() {
+        let type_chart = TypeChartCache::default();
+        let charizard = pokemon(
+            "charizard",
+            &["fire", "flying"],
+            [78, 84, 78, 109, 85, 100],
+            (17, 905),
+        );
+        let blastoise = pokemon(
+            "blastoise",
+            &["water"],
+            [79, 83, 100, 85, 105, 78],
+            (16, 855),
+        );
+
+        let comparison = compare_pokemon(
+            &type_chart,
+            &mock_adapter(),
+            charizard.clone(),
+            blastoise.clone(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(comparison.a, charizard);
+        assert_eq!(comparison.b, blastoise);
+        assert_eq!(
+            comparison.stat_deltas,
+            StatDeltas {
+                hp: -1,
+                attack: 1,
+                defense: -22,
+                special_attack: 24,
+                special_defense: -20,
+                speed: 22,
+                total: 4,
+            }
+        );
+        assert_eq!(comparison.height_delta, 1);
+        assert_eq!(comparison.weight_delta, 50);
+        assert!(!comparison.legendary_differs);
+        assert_eq!(comparison.a_against_b.get("fire"), Some(&0.5));
+        assert_eq!(comparison.a_against_b.get("flying"), Some(&1.0));
+        assert_eq!(comparison.b_against_a.get("water"), Some(&2.0));
+    }
+
+    #[tokio::test]
+    async fn test_should_not_compare_pokemon_with_unknown_type() {
+        let type_chart = TypeChartCache::default();
+        let a = pokemon("missingno", &["bird"], [33, 136, 0, 6, 6, 29], (10, 159));
+        let b = pokemon(
+            "blastoise",
+            &["water"],
+            [79, 83, 100, 85, 105, 78],
+            (16, 855),
+        );
+
+        let result = compare_pokemon(&type_chart, &mock_adapter(), a, b).await;
+
+        assert!(matches!(result, Err(BattleAdapterError::NotFound)));
+    }
+
+    fn pokemon(name: &str, types: &[&str], stats: [u16; 6], size: (u16, u16)) -> Pokemon {
+        let [hp, attack, defense, special_attack, special_defense, speed] = stats;
+        let (height, weight) = size;
+        Pokemon {
+            name: name.to_string(),
+            description: String::new(),
+            habitat: None,
+            is_legendary: false,
+            generation: 1,
+            species: name.to_string(),
+            forms: vec![],
+            types: types.iter().map(|name| name.to_string()).collect(),
+            stats: Stats {
+                hp,
+                attack,
+                defense,
+                special_attack,
+                special_defense,
+                speed,
+            },
+            height,
+            weight,
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn mock_adapter() -> MockBattleAdapter {
+        [
+            (
+                "fire",
+                DamageRelations {
+                    half_damage_to: names(&["fire", "water", "rock", "dragon"]),
+                    double_damage_to: names(&["grass", "ice", "bug", "steel"]),
+                    ..Default::default()
+                },
+            ),
+            (
+                "flying",
+                DamageRelations {
+                    half_damage_to: names(&["electric", "rock", "steel"]),
+                    double_damage_to: names(&["grass", "fighting", "bug"]),
+                    ..Default::default()
+                },
+            ),
+            (
+                "water",
+                DamageRelations {
+                    half_damage_to: names(&["water", "grass", "dragon"]),
+                    double_damage_to: names(&["fire", "ground", "rock"]),
+                    ..Default::default()
+                },
+            ),
+        ]
+        .into_iter()
+        .map(|(name, damage_relations)| PokemonType {
+            name: name.to_string(),
+            damage_relations,
+            past_damage_relations: vec![],
+        })
+        .fold(MockBattleAdapter::new(), MockBattleAdapter::with_type)
+    }
+}
@@ -0,0 +1,625 @@
+use crate::model::{
+    Combatant, DamageCalculation, DamageClass, DamageRequest, KoChance, Move, Pokemon, Stat, Stats,
+    Weather,
+};
+
+/// The maximum amount of hits considered for the KO chances.
+const MAX_HITS: u8 = 4;
+/// The maximum amount of EVs for a single stat.
+const MAX_STAT_EVS: u16 = 252;
+/// The maximum amount of EVs for all the stats.
+const MAX_TOTAL_EVS: u16 = 510;
+/// The maximum amount of IVs for a single stat.
+const MAX_STAT_IVS: u16 = 31;
+/// The maximum absolute value of a stat stage.
+const MAX_STAGE: i8 = 6;
+
+/// Result type for the damage calculator
+pub type DamageCalculatorResult<T> = Result<T, DamageCalculatorError>;
+
+/// Errors that can occur while calculating the damage
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum DamageCalculatorError {
+    #[error("Invalid level: {0}")]
+    InvalidLevel(u8),
+    #[error("Invalid EVs for {0}")]
+    InvalidEvs(String),
+    #[error("Invalid IVs for {0}")]
+    InvalidIvs(String),
+    #[error("Invalid stat stage: {0}")]
+    InvalidStage(i8),
+    #[error("Move {0} doesn't deal direct damage")]
+    NoDamage(String),
+}
+
+/// Calculates the damage dealt by the attacker to the defender with the given move
+/// using the mainline games formula.
+///
+/// The base stats and the types are taken from the given [`Pokemon`], while the
+/// spreads, the natures and the stages are taken from the [`DamageRequest`].
+/// Stats and damage rolls over [`u16::MAX`] are capped to it.
+pub fn calculate_damage(
+    request: &DamageRequest,
+    attacker: &Pokemon,
+    defender: &Pokemon,
+    pokemon_move: &Move,
+    effectiveness: f32,
+) -> DamageCalculatorResult<DamageCalculation> {
+    validate_combatant(&request.attacker)?;
+    validate_combatant(&request.defender)?;
+
+    let power = match (pokemon_move.damage_class, pokemon_move.power) {
+        (DamageClass::Physical | DamageClass::Special, Some(power)) if power > 0 => power,
+        _ => return Err(DamageCalculatorError::NoDamage(pokemon_move.name.clone())),
+    };
+
+    let attacker_stats = compute_stats(&attacker.stats, &request.attacker);
+    let defender_stats = compute_stats(&defender.stats, &request.defender);
+
+    // critical hits ignore the attacker's drops and the defender's boosts
+    let (attack, attack_stage, defense, defense_stage) = match pokemon_move.damage_class {
+        DamageClass::Physical => (
+            attacker_stats.attack,
+            request.attacker.stages.attack,
+            defender_stats.defense,
+            request.defender.stages.defense,
+        ),
+        _ => (
+            attacker_stats.special_attack,
+            request.attacker.stages.special_attack,
+            defender_stats.special_defense,
+            request.defender.stages.special_defense,
+        ),
+    };
+    let (attack_stage, defense_stage) = if request.critical {
+        (attack_stage.max(0), defense_stage.min(0))
+    } else {
+        (attack_stage, defense_stage)
+    };
+    let attack = apply_stage(attack, attack_stage);
+    let mut defense = apply_stage(defense, defense_stage);
+
+    // sandstorm and snow boost the defenses of rock and ice Pokémon
+    let is_defender = |type_name: &str| defender.types.iter().any(|t| t == type_name);
+    match (request.weather, pokemon_move.damage_class) {
+        (Some(Weather::Sandstorm), DamageClass::Special) if is_defender("rock") => {
+            defense = saturating_u16(u64::from(defense) * 3 / 2);
+        }
+        (Some(Weather::Snow), DamageClass::Physical) if is_defender("ice") => {
+            defense = saturating_u16(u64::from(defense) * 3 / 2);
+        }
+        _ => {}
+    }
+
+    let weather_modifier = match (request.weather, pokemon_move.move_type.as_str()) {
+        (Some(Weather::Sun), "fire") | (Some(Weather::Rain), "water") => Some(6144),
+        (Some(Weather::Sun), "water") | (Some(Weather::Rain), "fire") => Some(2048),
+        _ => None,
+    };
+    let stab = attacker
+        .types
+        .iter()
+        .any(|type_name| type_name == &pokemon_move.move_type);
+
+    // the intermediate values overflow a u32 with extreme stats and stages
+    let base =
+        (2 * u64::from(request.attacker.level) / 5 + 2) * u64::from(power) * u64::from(attack)
+            / u64::from(defense.max(1))
+            / 50
+            + 2;
+    let base = weather_modifier.map_or(base, |modifier| apply_modifier(base, modifier));
+    let base = if request.critical {
+        apply_modifier(base, 6144)
+    } else {
+        base
+    };
+
+    let rolls: Vec<u16> = (85..=100)
+        .map(|random| {
+            let damage = base * random / 100;
+            let damage = if stab {
+                apply_modifier(damage, 6144)
+            } else {
+                damage
+            };
+            let damage = (damage as f64 * f64::from(effectiveness)).floor() as u64;
+            if effectiveness > 0.0 {
+                saturating_u16(damage.max(1))
+            } else {
+                0
+            }
+        })
+        .collect();
+
+    let defender_hp = defender_stats.hp;
+    let min_damage = rolls.first().copied().unwrap_or_default();
+    let max_damage = rolls.last().copied().unwrap_or_default();
+
+    Ok(DamageCalculation {
+        attacker: attacker.name.clone(),
+        defender: defender.name.clone(),
+        move_name: pokemon_move.name.clone(),
+        effectiveness,
+        defender_hp,
+        min_damage,
+        max_damage,
+        min_percent: percent(min_damage, defender_hp),
+        max_percent: percent(max_damage, defender_hp),
+        ko_chances: ko_chances(&rolls, defender_hp),
+        rolls,
+    })
+}
+
+/// Validates the level, the spreads and the stages of the given [`Combatant`].
+fn validate_combatant(combatant: &Combatant) -> DamageCalculatorResult<()> {
+    if !(1..=100).contains(&combatant.level) {
+        return Err(DamageCalculatorError::InvalidLevel(combatant.level));
+    }
+    let evs = &combatant.evs;
+    if stats_values(evs).any(|value| value > MAX_STAT_EVS) || evs.total() > MAX_TOTAL_EVS {
+        return Err(DamageCalculatorError::InvalidEvs(combatant.name.clone()));
+    }
+    if stats_values(&combatant.ivs).any(|value| value > MAX_STAT_IVS) {
+        return Err(DamageCalculatorError::InvalidIvs(combatant.name.clone()));
+    }
+    let stages = &combatant.stages;
+    if let Some(stage) = [
+        stages.attack,
+        stages.defense,
+        stages.special_attack,
+        stages.special_defense,
+    ]
+    .into_iter()
+    .find(|stage| stage.abs() > MAX_STAGE)
+    {
+        return Err(DamageCalculatorError::InvalidStage(stage));
+    }
+
+    Ok(())
+}
+
+/// Iterates over the values of the given [`Stats`].
+fn stats_values(stats: &Stats) -> impl Iterator<Item = u16> {
+    [
+        stats.hp,
+        stats.attack,
+        stats.defense,
+        stats.special_attack,
+        stats.special_defense,
+        stats.speed,
+    ]
+    .into_iter()
+}
+
+/// Computes the actual stats of the [`Combatant`] with the given base stats.
+fn compute_stats(base: &Stats, combatant: &Combatant) -> Stats {
+    let level = u64::from(combatant.level);
+    let raw = |base: u16, iv: u16, ev: u16| {
+        (2 * u64::from(base) + u64::from(iv) + u64::from(ev) / 4) * level / 100
+    };
+    let stat = |base: u16, iv: u16, ev: u16, stat: Stat| {
+        let value = raw(base, iv, ev) + 5;
+        let value = match combatant.nature.modified_stats() {
+            Some((increased, _)) if increased == stat => value * 110 / 100,
+            Some((_, decreased)) if decreased == stat => value * 90 / 100,
+            _ => value,
+        };
+        saturating_u16(value)
+    };
+    let (ivs, evs) = (&combatant.ivs, &combatant.evs);
+
+    Stats {
+        hp: saturating_u16(raw(base.hp, ivs.hp, evs.hp) + level + 10),
+        attack: stat(base.attack, ivs.attack, evs.attack, Stat::Attack),
+        defense: stat(base.defense, ivs.defense, evs.defense, Stat::Defense),
+        special_attack: stat(
+            base.special_attack,
+            ivs.special_attack,
+            evs.special_attack,
+            Stat::SpecialAttack,
+        ),
+        special_defense: stat(
+            base.special_defense,
+            ivs.special_defense,
+            evs.special_defense,
+            Stat::SpecialDefense,
+        ),
+        speed: stat(base.speed, ivs.speed, evs.speed, Stat::Speed),
+    }
+}
+
+/// Applies the given stat stage to the stat.
+fn apply_stage(stat: u16, stage: i8) -> u16 {
+    let (numerator, denominator) = if stage >= 0 {
+        (2 + stage as u64, 2)
+    } else {
+        (2, 2 + u64::from(stage.unsigned_abs()))
+    };
+    saturating_u16(u64::from(stat) * numerator / denominator)
+}
+
+/// Applies a modifier, expressed in 4096ths, to the value, rounding half down as the games do.
+fn apply_modifier(value: u64, modifier: u64) -> u64 {
+    let product = value.saturating_mul(modifier);
+    if product % 4096 > 2048 {
+        product / 4096 + 1
+    } else {
+        product / 4096
+    }
+}
+
+/// Narrows the value to a `u16`, saturating at [`u16::MAX`] rather than wrapping.
+fn saturating_u16(value: u64) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
+}
+
+/// Computes the percentage of the HP dealt by the damage, rounded to one decimal.
+fn percent(damage: u16, hp: u16) -> f32 {
+    (f32::from(damage) * 1000.0 / f32::from(hp.max(1))).round() / 10.0
+}
+
+/// Computes the chances to knock out the defender with the given HP within 1 to
+/// [`MAX_HITS`] hits, given that each roll has the same probability.
+fn ko_chances(rolls: &[u16], hp: u16) -> Vec<KoChance> {
+    let hp = usize::from(hp);
+    // probability of each amount of damage dealt so far, capped to the HP
+    let mut dealt = vec![0.0f64; hp + 1];
+    dealt[0] = 1.0;
+
+    (1..=MAX_HITS)
+        .map(|hits| {
+            let mut next = vec![0.0f64; hp + 1];
+            for (damage, probability) in dealt.iter().enumerate() {
+                if *probability == 0.0 {
+                    continue;
+                }
+                for roll in rolls {
+                    let total = (damage + usize::from(*roll)).min(hp);
+                    next[total] += probability / rolls.len() as f64;
+                }
+            }
+            dealt = next;
+
+            KoChance {
+                hits,
+                chance: dealt[hp] as f32,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::model::{Nature, StatStages};
+
+    #[test]
+    fn test_should_compute_stats() {
+        // level 100 garchomp with 252 attack and speed EVs and jolly nature
+        let combatant = Combatant {
+            name: "garchomp".to_string(),
+            level: 100,
+            evs: Stats {
+                attack: 252,
+                speed: 252,
+                hp: 4,
+                ..Default::default()
+            },
+            ivs: Stats::splat(31),
+            nature: Nature::Jolly,
+            stages: StatStages::default(),
+        };
+        let stats = compute_stats(&garchomp().stats, &combatant);
+
+        assert_eq!(
+            stats,
+            Stats {
+                hp: 358,
+                attack: 359,
+                defense: 226,
+                special_attack: 176,
+                special_defense: 206,
+                speed: 333,
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_apply_stage() {
+        assert_eq!(apply_stage(100, 0), 100);
+        assert_eq!(apply_stage(100, 1), 150);
+        assert_eq!(apply_stage(100, 2), 200);
+        assert_eq!(apply_stage(100, 6), 400);
+        assert_eq!(apply_stage(100, -1), 66);
+        assert_eq!(apply_stage(100, -2), 50);
+        assert_eq!(apply_stage(100, -6), 25);
+    }
+
+    #[test]
+    fn test_should_apply_modifier() {
+        assert_eq!(apply_modifier(10, 6144), 15);
+        assert_eq!(apply_modifier(11, 6144), 16);
+        assert_eq!(apply_modifier(11, 2048), 5);
+        assert_eq!(apply_modifier(13, 6144), 19);
+    }
+
+    #[test]
+    fn test_should_calculate_damage() {
+        // level 50 pikachu using thunderbolt against gyarados
+        let request = request(combatant("pikachu"), combatant("gyarados"));
+        let result =
+            calculate_damage(&request, &pikachu(), &gyarados(), &thunderbolt(), 4.0).unwrap();
+
+        assert_eq!(result.attacker, "pikachu");
+        assert_eq!(result.defender, "gyarados");
+        assert_eq!(result.move_name, "thunderbolt");
+        assert_eq!(result.defender_hp, 170);
+        assert_eq!(result.rolls.len(), 16);
+        assert_eq!(result.min_damage, 124);
+        assert_eq!(result.max_damage, 148);
+        assert!(result.rolls.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(result.ko_chances.len(), MAX_HITS as usize);
+        assert_eq!(result.ko_chances[0].chance, 0.0);
+        assert_eq!(result.ko_chances[1].chance, 1.0);
+    }
+
+    #[test]
+    fn test_should_increase_damage_on_critical_hit_and_rain() {
+        let request = request(combatant("pikachu"), combatant("gyarados"));
+        let regular = calculate_damage(&request, &pikachu(), &gyarados(), &surf(), 1.0).unwrap();
+        let critical = calculate_damage(
+            &DamageRequest {
+                critical: true,
+                ..request.clone()
+            },
+            &pikachu(),
+            &gyarados(),
+            &surf(),
+            1.0,
+        )
+        .unwrap();
+        let rain = calculate_damage(
+            &DamageRequest {
+                weather: Some(Weather::Rain),
+                ..request.clone()
+            },
+            &pikachu(),
+            &gyarados(),
+            &surf(),
+            1.0,
+        )
+        .unwrap();
+        let sun = calculate_damage(
+            &DamageRequest {
+                weather: Some(Weather::Sun),
+                ..request
+            },
+            &pikachu(),
+            &gyarados(),
+            &surf(),
+            1.0,
+        )
+        .unwrap();
+
+        assert!(critical.max_damage > regular.max_damage);
+        assert!(rain.max_damage > regular.max_damage);
+        assert!(sun.max_damage < regular.max_damage);
+    }
+
+    #[test]
+    fn test_should_not_deal_damage_to_immune_defender() {
+        let request = request(combatant("pikachu"), combatant("garchomp"));
+        let result =
+            calculate_damage(&request, &pikachu(), &garchomp(), &thunderbolt(), 0.0).unwrap();
+
+        assert_eq!(result.max_damage, 0);
+        assert!(result.ko_chances.iter().all(|ko| ko.chance == 0.0));
+    }
+
+    #[test]
+    fn test_should_not_calculate_damage_of_status_move() {
+        let request = request(combatant("pikachu"), combatant("gyarados"));
+        let thunder_wave = Move {
+            name: "thunder-wave".to_string(),
+            damage_class: DamageClass::Status,
+            power: None,
+            ..thunderbolt()
+        };
+
+        assert_eq!(
+            calculate_damage(&request, &pikachu(), &gyarados(), &thunder_wave, 2.0),
+            Err(DamageCalculatorError::NoDamage("thunder-wave".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_should_validate_combatant() {
+        let mut combatant = combatant("pikachu");
+        assert!(validate_combatant(&combatant).is_ok());
+
+        combatant.level = 101;
+        assert_eq!(
+            validate_combatant(&combatant),
+            Err(DamageCalculatorError::InvalidLevel(101))
+        );
+
+        combatant.level = 50;
+        combatant.evs = Stats::splat(100);
+        assert!(matches!(
+            validate_combatant(&combatant),
+            Err(DamageCalculatorError::InvalidEvs(_))
+        ));
+
+        combatant.evs = Stats::default();
+        combatant.ivs.attack = 32;
+        assert!(matches!(
+            validate_combatant(&combatant),
+            Err(DamageCalculatorError::InvalidIvs(_))
+        ));
+
+        combatant.ivs = Stats::splat(31);
+        combatant.stages.attack = -7;
+        assert_eq!(
+            validate_combatant(&combatant),
+            Err(DamageCalculatorError::InvalidStage(-7))
+        );
+    }
+
+    #[test]
+    fn test_should_saturate_damage_with_extreme_stats_and_stages() {
+        // level 100 +6 adamant attacker with sun, critical hit and STAB against a level 1 -6 defender
+        let attacker = Combatant {
+            level: 100,
+            evs: Stats {
+                attack: 252,
+                ..Default::default()
+            },
+            nature: Nature::Adamant,
+            stages: StatStages {
+                attack: 6,
+                ..Default::default()
+            },
+            ..combatant("groudon")
+        };
+        let defender = Combatant {
+            level: 1,
+            stages: StatStages {
+                defense: -6,
+                ..Default::default()
+            },
+            ..combatant("shuckle")
+        };
+        let request = DamageRequest {
+            weather: Some(Weather::Sun),
+            critical: true,
+            ..request(attacker, defender)
+        };
+        let groudon = pokemon("groudon", &["fire"], [100, 255, 140, 100, 90, 90]);
+        let shuckle = pokemon("shuckle", &["bug", "rock"], [20, 10, 5, 10, 230, 5]);
+        let eruption = Move {
+            name: "eruption".to_string(),
+            move_type: "fire".to_string(),
+            damage_class: DamageClass::Physical,
+            power: Some(250),
+            ..thunderbolt()
+        };
+
+        for effectiveness in [1.0, 4.0] {
+            let result =
+                calculate_damage(&request, &groudon, &shuckle, &eruption, effectiveness).unwrap();
+            assert!(result.rolls.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert_eq!(result.max_damage, u16::MAX);
+            assert!(result.min_damage > 10_000);
+            assert_eq!(result.ko_chances[0].chance, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_should_saturate_stats() {
+        assert_eq!(apply_stage(u16::MAX, 6), u16::MAX);
+        assert_eq!(apply_modifier(u64::MAX, 6144), u64::MAX / 4096 + 1);
+
+        let combatant = Combatant {
+            level: 100,
+            nature: Nature::Adamant,
+            ..combatant("missingno")
+        };
+        let stats = compute_stats(&Stats::splat(u16::MAX), &combatant);
+        assert_eq!(stats.hp, u16::MAX);
+        assert_eq!(stats.attack, u16::MAX);
+    }
+
+    #[test]
+    fn test_should_compute_ko_chances() {
+        let rolls: Vec<u16> = (85..=100).collect();
+        let chances = ko_chances(&rolls, 100);
+
+        assert_eq!(chances[0].chance, 1.0 / 16.0);
+        assert_eq!(chances[1].chance, 1.0);
+        assert_eq!(chances[3].chance, 1.0);
+    }
+
+    fn combatant(name: &str) -> Combatant {
+        Combatant {
+            name: name.to_string(),
+            level: 50,
+            evs: Stats::default(),
+            ivs: Stats::splat(31),
+            nature: Nature::Hardy,
+            stages: StatStages::default(),
+        }
+    }
+
+    fn request(attacker: Combatant, defender: Combatant) -> DamageRequest {
+        DamageRequest {
+            move_name: "thunderbolt".to_string(),
+            attacker,
+            defender,
+            weather: None,
+            critical: false,
+        }
+    }
+
+    fn pokemon(name: &str, types: &[&str], stats: [u16; 6]) -> Pokemon {
+        let [hp, attack, defense, special_attack, special_defense, speed] = stats;
+        Pokemon {
+            name: name.to_string(),
+            description: String::new(),
+            habitat: None,
+            is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: name.to_string(),
+            forms: vec![],
+            types: types.iter().map(|name| name.to_string()).collect(),
+            stats: Stats {
+                hp,
+                attack,
+                defense,
+                special_attack,
+                special_defense,
+                speed,
+            },
+        }
+    }
+
+    fn pikachu() -> Pokemon {
+        pokemon("pikachu", &["electric"], [35, 55, 40, 50, 50, 90])
+    }
+
+    fn gyarados() -> Pokemon {
+        pokemon("gyarados", &["water", "flying"], [95, 125, 79, 60, 100, 81])
+    }
+
+    fn garchomp() -> Pokemon {
+        pokemon(
+            "garchomp",
+            &["dragon", "ground"],
+            [108, 130, 95, 80, 85, 102],
+        )
+    }
+
+    fn thunderbolt() -> Move {
+        Move {
+            name: "thunderbolt".to_string(),
+            move_type: "electric".to_string(),
+            damage_class: DamageClass::Special,
+            power: Some(90),
+            accuracy: Some(100),
+            pp: Some(15),
+            priority: 0,
+            effect: String::new(),
+            short_effect: String::new(),
+        }
+    }
+
+    fn surf() -> Move {
+        Move {
+            name: "surf".to_string(),
+            move_type: "water".to_string(),
+            ..thunderbolt()
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::adapters::battle::{BattleAdapter, BattleAdapterResult};
+use crate::mechanics::{LATEST_GENERATION, TYPES, TypeChartCache};
+use crate::model::{
+    CoverageSuggestion, Pokemon, Stats, TeamAnalysis, TeamMember, TeamStats, TypeCoverage,
+};
+
+/// The maximum amount of members of a team.
+pub const MAX_TEAM_SIZE: usize = 6;
+
+/// Analyzes the type coverage, the weaknesses, the resistances and the stats of the team
+/// made up of the given members, using the type chart of the latest generation.
+///
+/// Members which couldn't be fetched are reported as they are, but are not considered
+/// in the analysis.
+pub async fn analyze_team<B>(
+    type_chart: &TypeChartCache,
+    adapter: &B,
+    members: Vec<TeamMember>,
+) -> BattleAdapterResult<TeamAnalysis>
+where
+    B: BattleAdapter,
+{
+    let team: Vec<&Pokemon> = members
+        .iter()
+        .filter_map(|member| member.pokemon.as_ref())
+        .collect();
+
+    // offensive coverage of the types of the members
+    let attacking_types: BTreeSet<&str> = team
+        .iter()
+        .flat_map(|pokemon| pokemon.types.iter().map(String::as_str))
+        .collect();
+    let mut coverage = TypeCoverage::default();
+    for defender in TYPES {
+        let defenders = [defender.to_string()];
+        let mut covered = false;
+        for attacker in &attacking_types {
+            if type_chart
+                .effectiveness(adapter, LATEST_GENERATION, attacker, &defenders)
+                .await?
+                > 1.0
+            {
+                covered = true;
+                break;
+            }
+        }
+        if covered {
+            coverage.covered.push(defender.to_string());
+        } else {
+            coverage.uncovered.push(defender.to_string());
+        }
+    }
+
+    // defensive matchups of each member
+    let mut weaknesses: BTreeMap<String, u8> = BTreeMap::new();
+    let mut resistances: BTreeMap<String, u8> = BTreeMap::new();
+    for pokemon in &team {
+        let matchups = type_chart
+            .matchups(adapter, LATEST_GENERATION, &pokemon.types)
+            .await?;
+        for attacker in matchups.weaknesses.into_keys() {
+            *weaknesses.entry(attacker).or_default() += 1;
+        }
+        for attacker in matchups.resistances.into_keys().chain(matchups.immunities) {
+            *resistances.entry(attacker).or_default() += 1;
+        }
+    }
+    weaknesses.retain(|_, count| *count >= 2);
+
+    let mut suggestions = Vec::with_capacity(coverage.uncovered.len());
+    for uncovered in &coverage.uncovered {
+        let relations = type_chart
+            .damage_relations(adapter, LATEST_GENERATION, uncovered)
+            .await?;
+        suggestions.push(CoverageSuggestion {
+            uncovered: uncovered.clone(),
+            attacking_types: relations.double_damage_from,
+        });
+    }
+
+    let stats = team_stats(&team);
+
+    Ok(TeamAnalysis {
+        members,
+        coverage,
+        shared_weaknesses: weaknesses,
+        resistances,
+        stats,
+        suggestions,
+    })
+}
+
+/// Computes the total and the average base stats of the team.
+fn team_stats(team: &[&Pokemon]) -> TeamStats {
+    let total = team.iter().fold(Stats::default(), |total, pokemon| Stats {
+        hp: total.hp + pokemon.stats.hp,
+        attack: total.attack + pokemon.stats.attack,
+        defense: total.defense + pokemon.stats.defense,
+        special_attack: total.special_attack + pokemon.stats.special_attack,
+        special_defense: total.special_defense + pokemon.stats.special_defense,
+        speed: total.speed + pokemon.stats.speed,
+    });
+    let size = (team.len() as u16).max(1);
+    let average = Stats {
+        hp: total.hp / size,
+        attack: total.attack / size,
+        defense: total.defense / size,
+        special_attack: total.special_attack / size,
+        special_defense: total.special_defense / size,
+        speed: total.speed / size,
+    };
+
+    TeamStats { total, average }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::adapters::battle::MockBattleAdapter;
+    use crate::model::{DamageRelations, PokemonType, TeamMemberError};
+
+    #[tokio::test]
+    async fn test_should_analyze_team() {
+        let type_chart = TypeChartCache::default();
+        let members = vec![
+            member(pokemon("pikachu", &["electric"], 35)),
+            member(pokemon("raichu", &["electric"], 60)),
+            member(pokemon("squirtle", &["water"], 44)),
+            TeamMember {
+                name: "missingno".to_string(),
+                pokemon: None,
+                error: Some(TeamMemberError {
+                    status: 404,
+                    message: "Pokemon not found".to_string(),
+                }),
+            },
+        ];
+
+        let analysis = analyze_team(&type_chart, &mock_adapter(), members.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(analysis.members, members);
+        assert_eq!(
+            analysis.coverage.covered,
+            names(&["fire", "water", "ground", "flying", "rock"])
+        );
+        assert_eq!(analysis.coverage.uncovered.len(), TYPES.len() - 5);
+        assert_eq!(analysis.shared_weaknesses.get("ground"), Some(&2));
+        assert_eq!(analysis.shared_weaknesses.get("electric"), None);
+        assert_eq!(analysis.resistances.get("electric"), Some(&2));
+        assert_eq!(analysis.resistances.get("water"), Some(&1));
+        assert_eq!(analysis.stats.total.hp, 139);
+        assert_eq!(analysis.stats.average.hp, 46);
+        assert_eq!(
+            analysis.suggestions.len(),
+            analysis.coverage.uncovered.len()
+        );
+        let dragon_suggestion = analysis
+            .suggestions
+            .iter()
+            .find(|suggestion| suggestion.uncovered == "dragon")
+            .unwrap();
+        assert_eq!(
+            dragon_suggestion.attacking_types,
+            names(&["ice", "dragon", "fairy"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_analyze_empty_team() {
+        let type_chart = TypeChartCache::default();
+
+        let analysis = analyze_team(&type_chart, &mock_adapter(), vec![])
+            .await
+            .unwrap();
+
+        assert!(analysis.coverage.covered.is_empty());
+        assert_eq!(analysis.coverage.uncovered.len(), TYPES.len());
+        assert!(analysis.shared_weaknesses.is_empty());
+        assert_eq!(analysis.stats, TeamStats::default());
+    }
+
+    fn member(pokemon: Pokemon) -> TeamMember {
+        TeamMember {
+            name: pokemon.name.clone(),
+            pokemon: Some(pokemon),
+            error: None,
+        }
+    }
+
+    fn pokemon(name: &str, types: &[&str], hp: u16) -> Pokemon {
+        Pokemon {
+            name: name.to_string(),
+            description: String::new(),
+            habitat: None,
+            is_legendary: false,
+            generation: 1,
+            height: 4,
+            weight: 60,
+            species: name.to_string(),
+            forms: vec![],
+            types: types.iter().map(|name| name.to_string()).collect(),
+            stats: Stats {
+                hp,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn mock_adapter() -> MockBattleAdapter {
+        TYPES
+            .into_iter()
+            .map(|name| {
+                let damage_relations = match name {
+                    "electric" => DamageRelations {
+                        half_damage_from: names(&["electric", "flying", "steel"]),
+                        double_damage_from: names(&["ground"]),
+                        no_damage_to: names(&["ground"]),
+                        half_damage_to: names(&["electric", "grass", "dragon"]),
+                        double_damage_to: names(&["water", "flying"]),
+                        ..Default::default()
+                    },
+                    "water" => DamageRelations {
+                        half_damage_from: names(&["water", "fire", "ice", "steel"]),
+                        double_damage_from: names(&["electric", "grass"]),
+                        half_damage_to: names(&["water", "grass", "dragon"]),
+                        double_damage_to: names(&["fire", "ground", "rock"]),
+                        ..Default::default()
+                    },
+                    "dragon" => DamageRelations {
+                        double_damage_from: names(&["ice", "dragon", "fairy"]),
+                        ..Default::default()
+                    },
+                    _ => DamageRelations::default(),
+                };
+                PokemonType {
+                    name: name.to_string(),
+                    damage_relations,
+                    past_damage_relations: vec![],
+                }
+            })
+            .fold(MockBattleAdapter::new(), MockBattleAdapter::with_type)
+    }
+}
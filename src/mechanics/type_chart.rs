@@ -0,0 +1,297 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::RwLock;
+
+use crate::adapters::battle::{BattleAdapter, BattleAdapterResult};
+use crate::model::{DamageRelations, TypeMatchups};
+use crate::monitoring;
+
+/// The name of the cache in the metrics.
+const CACHE_NAME: &str = "type_chart";
+
+/// The latest generation of the main series games.
+pub const LATEST_GENERATION: u8 = 9;
+
+/// The names of the types of the latest generation.
+pub const TYPES: [&str; 18] = [
+    "normal", "fire", "water", "electric", "grass", "ice", "fighting", "poison", "ground",
+    "flying", "psychic", "bug", "rock", "ghost", "dragon", "dark", "steel", "fairy",
+];
+
+/// Cache of the type charts of each generation.
+///
+/// Since the type chart changed across generations, the damage relations of each type
+/// are cached per generation. The charts are lazily populated through the [`BattleAdapter`].
+#[derive(Debug, Default)]
+pub struct TypeChartCache {
+    /// damage relations of each type, for each generation
+    charts: RwLock<HashMap<u8, HashMap<String, DamageRelations>>>,
+}
+
+impl TypeChartCache {
+    /// Gets the [`DamageRelations`] of the given type in the given generation.
+    ///
+    /// If they are not cached yet, the type is fetched through the given [`BattleAdapter`].
+    pub async fn damage_relations<B>(
+        &self,
+        adapter: &B,
+        generation: u8,
+        type_name: &str,
+    ) -> BattleAdapterResult<DamageRelations>
+    where
+        B: BattleAdapter,
+    {
+        if let Some(relations) = self
+            .charts
+            .read()
+            .expect("type chart lock poisoned")
+            .get(&generation)
+            .and_then(|chart| chart.get(type_name))
+        {
+            trace!("type chart cache hit for {type_name} in generation {generation}");
+            monitoring::record_cache_lookup(CACHE_NAME, true);
+            return Ok(relations.clone());
+        }
+
+        debug!("type chart cache miss for {type_name} in generation {generation}");
+        monitoring::record_cache_lookup(CACHE_NAME, false);
+        let pokemon_type = adapter.fetch_type_by_name(type_name).await?;
+        let relations = pokemon_type.damage_relations_in(generation).clone();
+        self.charts
+            .write()
+            .expect("type chart lock poisoned")
+            .entry(generation)
+            .or_default()
+            .insert(type_name.to_string(), relations.clone());
+
+        Ok(relations)
+    }
+
+    /// Computes the damage multiplier of the attacking type against the defending types
+    /// in the given generation.
+    pub async fn effectiveness<B>(
+        &self,
+        adapter: &B,
+        generation: u8,
+        attacker: &str,
+        defenders: &[String],
+    ) -> BattleAdapterResult<f32>
+    where
+        B: BattleAdapter,
+    {
+        let relations = self.damage_relations(adapter, generation, attacker).await?;
+
+        Ok(defenders
+            .iter()
+            .map(|defender| {
+                if relations.no_damage_to.contains(defender) {
+                    0.0
+                } else if relations.half_damage_to.contains(defender) {
+                    0.5
+                } else if relations.double_damage_to.contains(defender) {
+                    2.0
+                } else {
+                    1.0
+                }
+            })
+            .product())
+    }
+
+    /// Computes the [`TypeMatchups`] of the defending types in the given generation.
+    pub async fn matchups<B>(
+        &self,
+        adapter: &B,
+        generation: u8,
+        defenders: &[String],
+    ) -> BattleAdapterResult<TypeMatchups>
+    where
+        B: BattleAdapter,
+    {
+        let mut multipliers: BTreeMap<String, f32> = BTreeMap::new();
+        for defender in defenders {
+            let relations = self.damage_relations(adapter, generation, defender).await?;
+            for (attackers, multiplier) in [
+                (relations.no_damage_from, 0.0),
+                (relations.half_damage_from, 0.5),
+                (relations.double_damage_from, 2.0),
+            ] {
+                for attacker in attackers {
+                    *multipliers.entry(attacker).or_insert(1.0) *= multiplier;
+                }
+            }
+        }
+
+        let mut matchups = TypeMatchups::default();
+        for (attacker, multiplier) in multipliers {
+            if multiplier == 0.0 {
+                matchups.immunities.push(attacker);
+            } else if multiplier < 1.0 {
+                matchups.resistances.insert(attacker, multiplier);
+            } else if multiplier > 1.0 {
+                matchups.weaknesses.insert(attacker, multiplier);
+            }
+        }
+
+        Ok(matchups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::adapters::battle::{BattleAdapterError, MockBattleAdapter};
+    use crate::model::{PastDamageRelations, PokemonType};
+
+    #[tokio::test]
+    async fn test_should_compute_effectiveness() {
+        let cache = TypeChartCache::default();
+        let adapter = mock_adapter();
+
+        for (defenders, expected) in [
+            (vec!["grass"], 2.0),
+            (vec!["grass", "steel"], 4.0),
+            (vec!["water"], 0.5),
+            (vec!["water", "rock"], 0.25),
+            (vec!["water", "grass"], 1.0),
+            (vec!["normal"], 1.0),
+        ] {
+            let defenders: Vec<String> = defenders.into_iter().map(String::from).collect();
+            let multiplier = cache
+                .effectiveness(&adapter, LATEST_GENERATION, "fire", &defenders)
+                .await
+                .unwrap();
+            assert_eq!(multiplier, expected, "fire against {defenders:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_compute_effectiveness_in_past_generation() {
+        let cache = TypeChartCache::default();
+        let adapter = mock_adapter();
+        let defenders = vec!["psychic".to_string()];
+
+        assert_eq!(
+            cache
+                .effectiveness(&adapter, 1, "ghost", &defenders)
+                .await
+                .unwrap(),
+            0.0
+        );
+        assert_eq!(
+            cache
+                .effectiveness(&adapter, LATEST_GENERATION, "ghost", &defenders)
+                .await
+                .unwrap(),
+            2.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_compute_matchups() {
+        let cache = TypeChartCache::default();
+        let adapter = mock_adapter();
+
+        let matchups = cache
+            .matchups(
+                &adapter,
+                LATEST_GENERATION,
+                &["grass".to_string(), "steel".to_string()],
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(matchups.weaknesses.get("fire"), Some(&4.0));
+        assert_eq!(matchups.resistances.get("grass"), Some(&0.25));
+        assert_eq!(matchups.resistances.get("water"), Some(&0.5));
+        assert_eq!(matchups.weaknesses.get("ground"), None);
+        assert_eq!(matchups.resistances.get("ground"), None);
+        assert_eq!(matchups.immunities, vec!["poison".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_should_cache_damage_relations_per_generation() {
+        let cache = TypeChartCache::default();
+        let adapter = mock_adapter();
+
+        cache.damage_relations(&adapter, 1, "ghost").await.unwrap();
+        cache
+            .damage_relations(&adapter, LATEST_GENERATION, "ghost")
+            .await
+            .unwrap();
+
+        let charts = cache.charts.read().unwrap();
+        assert_eq!(
+            charts[&1]["ghost"].no_damage_to,
+            vec!["psychic".to_string()]
+        );
+        assert_eq!(
+            charts[&LATEST_GENERATION]["ghost"].double_damage_to,
+            vec!["psychic".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_not_compute_effectiveness_of_unknown_type() {
+        let cache = TypeChartCache::default();
+        let adapter = mock_adapter();
+
+        let result = cache
+            .effectiveness(&adapter, LATEST_GENERATION, "shadow", &["fire".to_string()])
+            .await;
+        assert!(matches!(result, Err(BattleAdapterError::NotFound)));
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn mock_adapter() -> MockBattleAdapter {
+        MockBattleAdapter::new()
+            .with_type(PokemonType {
+                name: "fire".to_string(),
+                damage_relations: DamageRelations {
+                    half_damage_to: names(&["fire", "water", "rock", "dragon"]),
+                    double_damage_to: names(&["grass", "ice", "bug", "steel"]),
+                    ..Default::default()
+                },
+                past_damage_relations: vec![],
+            })
+            .with_type(PokemonType {
+                name: "grass".to_string(),
+                damage_relations: DamageRelations {
+                    half_damage_from: names(&["ground", "water", "grass", "electric"]),
+                    double_damage_from: names(&["flying", "poison", "bug", "fire", "ice"]),
+                    ..Default::default()
+                },
+                past_damage_relations: vec![],
+            })
+            .with_type(PokemonType {
+                name: "steel".to_string(),
+                damage_relations: DamageRelations {
+                    no_damage_from: names(&["poison"]),
+                    half_damage_from: names(&[
+                        "normal", "flying", "rock", "bug", "steel", "grass", "psychic", "ice",
+                        "dragon", "fairy",
+                    ]),
+                    double_damage_from: names(&["fighting", "ground", "fire"]),
+                    ..Default::default()
+                },
+                past_damage_relations: vec![],
+            })
+            .with_type(PokemonType {
+                name: "ghost".to_string(),
+                damage_relations: DamageRelations {
+                    no_damage_to: names(&["normal"]),
+                    double_damage_to: names(&["psychic"]),
+                    ..Default::default()
+                },
+                past_damage_relations: vec![PastDamageRelations {
+                    generation: 1,
+                    damage_relations: DamageRelations {
+                        no_damage_to: names(&["psychic"]),
+                        ..Default::default()
+                    },
+                }],
+            })
+    }
+}
@@ -1,5 +1,48 @@
 //! This module contains data models for the application.
 
+mod ability;
+mod autocomplete;
+mod batch;
+mod comparison;
+mod damage;
+mod encounter;
+mod health;
+mod item;
+mod log_config;
+mod pokedex;
 mod pokemon;
+mod pokemon_move;
+mod pokemon_type;
+mod problem;
+mod quiz;
+mod search;
+mod sprite;
+mod stats;
+mod team;
+mod type_effectiveness;
 
+pub use self::ability::Ability;
+pub use self::autocomplete::{Suggestion, Suggestions};
+pub use self::batch::{BatchError, BatchRequest, BatchResponse, BatchResult};
+pub use self::comparison::{PokemonComparison, StatDeltas};
+pub use self::damage::{Combatant, DamageCalculation, DamageRequest, KoChance, Stat, Weather};
+#[cfg(test)]
+pub use self::damage::{Nature, StatStages};
+pub use self::encounter::{Encounter, PokemonEncounters, VersionEncounters};
+pub use self::health::{DependencyHealth, HealthStatus, Liveness, Readiness};
+pub use self::item::{Berry, BerryFlavor, Item};
+pub use self::log_config::LogConfig;
+pub use self::pokedex::{Generation, Pokedex, PokedexEntry};
 pub use self::pokemon::Pokemon;
+pub use self::pokemon_move::{DamageClass, Move};
+pub use self::pokemon_type::{DamageRelations, PastDamageRelations, PokemonType};
+pub use self::problem::{PROBLEM_CONTENT_TYPE, Problem};
+pub use self::quiz::{Quiz, QuizGuess, QuizGuessResult, QuizRequest};
+pub use self::search::{SearchResult, SearchResults, SpeciesTexts};
+pub use self::sprite::{Sprite, SpriteStream, SpriteVariant};
+pub use self::stats::Stats;
+pub use self::team::{
+    CoverageSuggestion, TeamAnalysis, TeamMember, TeamMemberError, TeamRequest, TeamStats,
+    TypeCoverage,
+};
+pub use self::type_effectiveness::{PokemonWeaknesses, TypeEffectiveness, TypeMatchups};
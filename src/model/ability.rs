@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A struct representing a Pokémon ability.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Ability {
+    /// The name of the ability.
+    pub name: String,
+    /// The in-depth English description of the ability effect.
+    pub effect: String,
+    /// The short English description of the ability effect.
+    pub short_effect: String,
+}
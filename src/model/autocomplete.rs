@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A suggestion for a Pokémon name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestion {
+    /// The name of the Pokémon species or form, which can be looked up.
+    pub name: String,
+    /// The matching text, which is either the name itself or a localized name (e.g. `Glurak`).
+    pub label: String,
+    /// The language of the localized name, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// The suggestions for a prefix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Suggestions {
+    /// The requested prefix.
+    pub prefix: String,
+    /// The suggestions, sorted alphabetically by label.
+    pub suggestions: Vec<Suggestion>,
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::Pokemon;
+
+/// The request body of the bulk lookup of Pokémon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest {
+    /// The names of the Pokémon to look up.
+    pub names: Vec<String>,
+    /// Whether the descriptions should be translated.
+    #[serde(default)]
+    pub translated: bool,
+}
+
+/// The response of the bulk lookup of Pokémon.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResponse {
+    /// The result of each requested name, in the same order as the request.
+    pub results: Vec<BatchResult>,
+}
+
+/// The result of the lookup of a single name.
+///
+/// Either `pokemon` or `error` is defined, depending on whether the Pokémon could be fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResult {
+    /// The requested name.
+    pub name: String,
+    /// The Pokémon data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pokemon: Option<Pokemon>,
+    /// The error occurred while fetching the Pokémon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<BatchError>,
+}
+
+/// The error occurred while fetching a [`BatchResult`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchError {
+    /// The HTTP status code which would have been returned by `GET /pokemon/{name}`.
+    pub status: u16,
+    /// The error message.
+    pub message: String,
+}
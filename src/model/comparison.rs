@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::Pokemon;
+
+/// The side by side comparison of two Pokémon.
+///
+/// All the deltas are computed as the value of `a` minus the value of `b`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PokemonComparison {
+    /// The first compared Pokémon.
+    pub a: Pokemon,
+    /// The second compared Pokémon.
+    pub b: Pokemon,
+    /// The deltas of the base stats.
+    pub stat_deltas: StatDeltas,
+    /// The delta of the heights, in decimetres.
+    pub height_delta: i32,
+    /// The delta of the weights, in hectograms.
+    pub weight_delta: i32,
+    /// Whether only one of the two Pokémon is legendary.
+    pub legendary_differs: bool,
+    /// The damage multiplier of each type of `a` when attacking `b`.
+    pub a_against_b: BTreeMap<String, f32>,
+    /// The damage multiplier of each type of `b` when attacking `a`.
+    pub b_against_a: BTreeMap<String, f32>,
+}
+
+/// The deltas of the base stats of two Pokémon.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatDeltas {
+    /// Hit points.
+    pub hp: i32,
+    /// Attack.
+    pub attack: i32,
+    /// Defense.
+    pub defense: i32,
+    /// Special attack.
+    pub special_attack: i32,
+    /// Special defense.
+    pub special_defense: i32,
+    /// Speed.
+    pub speed: i32,
+    /// The delta of the base stat totals.
+    pub total: i32,
+}
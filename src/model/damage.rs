@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::Stats;
+
+/// The request body of the damage calculator.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageRequest {
+    /// The attacking Pokémon.
+    pub attacker: Combatant,
+    /// The defending Pokémon.
+    pub defender: Combatant,
+    /// The name of the move used by the attacker.
+    #[serde(rename = "move")]
+    pub move_name: String,
+    /// The active weather.
+    #[serde(default)]
+    pub weather: Option<Weather>,
+    /// Whether the move lands a critical hit.
+    #[serde(default)]
+    pub critical: bool,
+}
+
+/// A Pokémon taking part in the damage calculation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Combatant {
+    /// The name of the Pokémon.
+    pub name: String,
+    /// The level of the Pokémon [default: 50].
+    #[serde(default = "Combatant::default_level")]
+    pub level: u8,
+    /// The effort values of the Pokémon [default: 0 for each stat].
+    #[serde(default)]
+    pub evs: Stats,
+    /// The individual values of the Pokémon [default: 31 for each stat].
+    #[serde(default = "Combatant::default_ivs")]
+    pub ivs: Stats,
+    /// The nature of the Pokémon [default: hardy].
+    #[serde(default)]
+    pub nature: Nature,
+    /// The stat stages of the Pokémon [default: 0 for each stat].
+    #[serde(default)]
+    pub stages: StatStages,
+}
+
+impl Combatant {
+    fn default_level() -> u8 {
+        50
+    }
+
+    fn default_ivs() -> Stats {
+        Stats::splat(31)
+    }
+}
+
+/// The stat stages, from -6 to +6, relevant for the damage calculation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StatStages {
+    /// Attack stage.
+    pub attack: i8,
+    /// Defense stage.
+    pub defense: i8,
+    /// Special attack stage.
+    pub special_attack: i8,
+    /// Special defense stage.
+    pub special_defense: i8,
+}
+
+/// A stat which can be affected by the [`Nature`] of a Pokémon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stat {
+    Attack,
+    Defense,
+    SpecialAttack,
+    SpecialDefense,
+    Speed,
+}
+
+/// The nature of a Pokémon, which increases a stat by 10% and decreases another one by 10%.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Nature {
+    #[default]
+    Hardy,
+    Lonely,
+    Brave,
+    Adamant,
+    Naughty,
+    Bold,
+    Docile,
+    Relaxed,
+    Impish,
+    Lax,
+    Timid,
+    Hasty,
+    Serious,
+    Jolly,
+    Naive,
+    Modest,
+    Mild,
+    Quiet,
+    Bashful,
+    Rash,
+    Calm,
+    Gentle,
+    Sassy,
+    Careful,
+    Quirky,
+}
+
+impl Nature {
+    /// Returns the stats increased and decreased by the nature, if any.
+    pub fn modified_stats(&self) -> Option<(Stat, Stat)> {
+        use Stat::*;
+
+        match self {
+            Self::Hardy | Self::Docile | Self::Serious | Self::Bashful | Self::Quirky => None,
+            Self::Lonely => Some((Attack, Defense)),
+            Self::Brave => Some((Attack, Speed)),
+            Self::Adamant => Some((Attack, SpecialAttack)),
+            Self::Naughty => Some((Attack, SpecialDefense)),
+            Self::Bold => Some((Defense, Attack)),
+            Self::Relaxed => Some((Defense, Speed)),
+            Self::Impish => Some((Defense, SpecialAttack)),
+            Self::Lax => Some((Defense, SpecialDefense)),
+            Self::Timid => Some((Speed, Attack)),
+            Self::Hasty => Some((Speed, Defense)),
+            Self::Jolly => Some((Speed, SpecialAttack)),
+            Self::Naive => Some((Speed, SpecialDefense)),
+            Self::Modest => Some((SpecialAttack, Attack)),
+            Self::Mild => Some((SpecialAttack, Defense)),
+            Self::Quiet => Some((SpecialAttack, Speed)),
+            Self::Rash => Some((SpecialAttack, SpecialDefense)),
+            Self::Calm => Some((SpecialDefense, Attack)),
+            Self::Gentle => Some((SpecialDefense, Defense)),
+            Self::Sassy => Some((SpecialDefense, Speed)),
+            Self::Careful => Some((SpecialDefense, SpecialAttack)),
+        }
+    }
+}
+
+/// The weather affecting the battle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum Weather {
+    /// Boosts fire moves and weakens water moves.
+    Sun,
+    /// Boosts water moves and weakens fire moves.
+    Rain,
+    /// Boosts the special defense of rock Pokémon.
+    Sandstorm,
+    /// Boosts the defense of ice Pokémon.
+    Snow,
+}
+
+/// The result of the damage calculator.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageCalculation {
+    /// The name of the attacking Pokémon.
+    pub attacker: String,
+    /// The name of the defending Pokémon.
+    pub defender: String,
+    /// The name of the move used by the attacker.
+    #[serde(rename = "move")]
+    pub move_name: String,
+    /// The type effectiveness multiplier of the move against the defender.
+    pub effectiveness: f32,
+    /// The HP of the defender.
+    pub defender_hp: u16,
+    /// The minimum damage dealt.
+    pub min_damage: u16,
+    /// The maximum damage dealt.
+    pub max_damage: u16,
+    /// The minimum damage dealt, in percent of the defender HP.
+    pub min_percent: f32,
+    /// The maximum damage dealt, in percent of the defender HP.
+    pub max_percent: f32,
+    /// The damage dealt for each one of the 16 random rolls.
+    pub rolls: Vec<u16>,
+    /// The chance to knock out the defender within a given amount of hits.
+    pub ko_chances: Vec<KoChance>,
+}
+
+/// The chance to knock out the defender within the given amount of hits.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KoChance {
+    /// The amount of hits.
+    pub hits: u8,
+    /// The chance, between 0 and 1.
+    pub chance: f32,
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The locations where a Pokémon can be encountered, grouped by game version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PokemonEncounters {
+    /// The name of the Pokémon.
+    pub name: String,
+    /// The encounters of the Pokémon in each game version.
+    pub versions: Vec<VersionEncounters>,
+}
+
+/// The encounters of a Pokémon in a game version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionEncounters {
+    /// The name of the game version (e.g. `red`).
+    pub version: String,
+    /// The encounters in the game version.
+    pub encounters: Vec<Encounter>,
+}
+
+/// An encounter of a Pokémon in a location area.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Encounter {
+    /// The name of the location area (e.g. `viridian-forest-area`).
+    pub location: String,
+    /// The encounter method (e.g. `walk`, `surf`, `old-rod`).
+    pub method: String,
+    /// The minimum level of the encountered Pokémon.
+    pub min_level: u8,
+    /// The maximum level of the encountered Pokémon.
+    pub max_level: u8,
+    /// The chance in percent of the encounter.
+    pub chance: u8,
+    /// The conditions which must be met for the encounter (e.g. `time-night`).
+    pub conditions: Vec<String>,
+}
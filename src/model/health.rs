@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The status of the application or of one of its dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthStatus {
+    /// Working.
+    Up,
+    /// Not working.
+    Down,
+    /// Still loading its data.
+    WarmingUp,
+}
+
+/// The response of the liveness probe.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Liveness {
+    /// The status of the process.
+    pub status: HealthStatus,
+}
+
+/// The response of the readiness probe.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Readiness {
+    /// Whether all the required dependencies are up.
+    pub ready: bool,
+    /// When the dependencies have been checked.
+    pub checked_at: DateTime<Utc>,
+    /// The status of each dependency.
+    pub dependencies: Vec<DependencyHealth>,
+}
+
+/// The status of a dependency of the application.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyHealth {
+    /// The name of the dependency.
+    pub name: String,
+    /// The status of the dependency.
+    pub status: HealthStatus,
+    /// Whether the application can't serve requests without the dependency.
+    pub required: bool,
+    /// Details about the status, such as the error of a failed check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
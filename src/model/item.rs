@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A struct representing an item.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+    /// The name of the item.
+    pub name: String,
+    /// The category of the item (e.g. `healing`, `held-items`).
+    pub category: String,
+    /// The price of the item in stores.
+    pub cost: u32,
+    /// The power of the move Fling when used with this item, if it can be flung.
+    pub fling_power: Option<u8>,
+    /// The effect of the move Fling when used with this item, if any.
+    pub fling_effect: Option<String>,
+    /// The attributes of the item (e.g. `holdable`, `consumable`).
+    pub attributes: Vec<String>,
+    /// The in-depth English description of the item effect.
+    pub effect: String,
+    /// The short English description of the item effect.
+    pub short_effect: String,
+}
+
+/// A struct representing a berry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Berry {
+    /// The name of the berry (e.g. `cheri`).
+    pub name: String,
+    /// The name of the item of the berry (e.g. `cheri-berry`).
+    pub item: String,
+    /// The firmness of the berry (e.g. `soft`).
+    pub firmness: String,
+    /// The flavors of the berry, with their potency.
+    pub flavors: Vec<BerryFlavor>,
+    /// The type of the move Natural Gift when used with this berry.
+    pub natural_gift_type: String,
+    /// The power of the move Natural Gift when used with this berry.
+    pub natural_gift_power: u8,
+    /// The size of the berry, in millimeters.
+    pub size: u16,
+    /// The smoothness of the berry, used in making Pokéblocks or Poffins.
+    pub smoothness: u8,
+    /// The time in hours it takes for the tree to grow one stage.
+    pub growth_time: u8,
+    /// The maximum amount of berries that can grow on one tree.
+    pub max_harvest: u8,
+    /// The in-depth English description of the berry effect.
+    pub effect: String,
+    /// The short English description of the berry effect.
+    pub short_effect: String,
+}
+
+/// A flavor of a berry.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BerryFlavor {
+    /// The name of the flavor (e.g. `spicy`).
+    pub name: String,
+    /// How strong the flavor is; `0` if the berry doesn't have it.
+    pub potency: u8,
+}
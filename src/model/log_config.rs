@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The level and the filter the logs are written with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LogConfig {
+    /// The level of the targets not matched by the filter: `off`, `error`, `warn`, `info`, `debug` or `trace`.
+    #[schema(example = "info")]
+    pub level: String,
+    /// The directives overriding the level of the matching targets, in the `EnvFilter` syntax,
+    /// or a bare target prefix keeping only the logs of the matching targets.
+    #[schema(example = "pokedex_api::adapters=trace,hyper=warn")]
+    pub filter: Option<String>,
+}
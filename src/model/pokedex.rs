@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A generation of the main series games, with the species it introduced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Generation {
+    /// The number of the generation.
+    pub id: u8,
+    /// The name of the generation (e.g. `generation-i`).
+    pub name: String,
+    /// The name of the main region of the generation (e.g. `kanto`).
+    pub main_region: String,
+    /// The species introduced in the generation, sorted by their national dex number.
+    pub species: Vec<PokedexEntry>,
+}
+
+/// A Pokédex, either national or regional.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Pokedex {
+    /// The name of the Pokédex (e.g. `kanto`, `galar`, `paldea`).
+    pub name: String,
+    /// The name of the region of the Pokédex, if it's a regional one.
+    pub region: Option<String>,
+    /// The species of the Pokédex, sorted by their number in it.
+    pub species: Vec<PokedexEntry>,
+}
+
+/// A species listed in a [`Pokedex`] or introduced in a [`Generation`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PokedexEntry {
+    /// The number of the species in the dex.
+    pub number: u16,
+    /// The name of the species.
+    pub name: String,
+}
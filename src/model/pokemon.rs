@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::Stats;
 
 /// A struct representing a Pokémon.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Pokemon {
     /// The name of the Pokémon.
@@ -14,4 +17,19 @@ pub struct Pokemon {
     pub habitat: Option<String>,
     /// Whether the Pokémon is legendary.
     pub is_legendary: bool,
+    /// The generation which introduced the Pokémon species.
+    pub generation: u8,
+    /// The name of the Pokémon species, shared by all its forms.
+    pub species: String,
+    /// The names of all the forms of the species (e.g. `vulpix` and `vulpix-alola`),
+    /// starting with the default one.
+    pub forms: Vec<String>,
+    /// The names of the Pokémon types, sorted by slot. They may differ between forms.
+    pub types: Vec<String>,
+    /// The base stats of the Pokémon. They may differ between forms.
+    pub stats: Stats,
+    /// The height of the Pokémon in decimetres.
+    pub height: u16,
+    /// The weight of the Pokémon in hectograms.
+    pub weight: u16,
 }
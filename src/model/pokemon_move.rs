@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A struct representing a move a Pokémon can learn.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Move {
+    /// The name of the move.
+    pub name: String,
+    /// The name of the type of the move.
+    #[serde(rename = "type")]
+    pub move_type: String,
+    /// The damage class of the move.
+    pub damage_class: DamageClass,
+    /// The base power of the move. It is not defined for moves which don't deal damage
+    /// or whose power varies.
+    pub power: Option<u16>,
+    /// The percent value of how likely the move is to be successful. It is not defined
+    /// for moves which never miss.
+    pub accuracy: Option<u8>,
+    /// Power points. The number of times the move can be used.
+    pub pp: Option<u8>,
+    /// The priority of the move; moves with higher priority are used first.
+    pub priority: i8,
+    /// The in-depth English description of the move effect.
+    pub effect: String,
+    /// The short English description of the move effect.
+    pub short_effect: String,
+}
+
+/// The damage class of a [`Move`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum DamageClass {
+    /// The move deals damage based on the attack and defense stats.
+    Physical,
+    /// The move deals damage based on the special attack and special defense stats.
+    Special,
+    /// The move doesn't deal damage.
+    Status,
+}
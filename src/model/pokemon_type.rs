@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A struct representing a Pokémon type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PokemonType {
+    /// The name of the type.
+    pub name: String,
+    /// The damage relations of the type in the latest generation.
+    pub damage_relations: DamageRelations,
+    /// The damage relations the type had in the previous generations, sorted by generation.
+    pub past_damage_relations: Vec<PastDamageRelations>,
+}
+
+impl PokemonType {
+    /// Returns the [`DamageRelations`] the type had in the given generation.
+    pub fn damage_relations_in(&self, generation: u8) -> &DamageRelations {
+        self.past_damage_relations
+            .iter()
+            .find(|past| past.generation >= generation)
+            .map(|past| &past.damage_relations)
+            .unwrap_or(&self.damage_relations)
+    }
+}
+
+/// The damage relations of a [`PokemonType`] with the other types.
+///
+/// Each field contains the names of the types matching the relation.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageRelations {
+    /// Types which deal no damage to this type.
+    pub no_damage_from: Vec<String>,
+    /// Types which deal half damage to this type.
+    pub half_damage_from: Vec<String>,
+    /// Types which deal double damage to this type.
+    pub double_damage_from: Vec<String>,
+    /// Types which receive no damage from this type.
+    pub no_damage_to: Vec<String>,
+    /// Types which receive half damage from this type.
+    pub half_damage_to: Vec<String>,
+    /// Types which receive double damage from this type.
+    pub double_damage_to: Vec<String>,
+}
+
+/// The [`DamageRelations`] a [`PokemonType`] had up to a certain generation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PastDamageRelations {
+    /// The last generation in which these damage relations were applied.
+    pub generation: u8,
+    /// The damage relations.
+    pub damage_relations: DamageRelations,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_get_damage_relations_in_generation() {
+        let relations = |double_damage_to: &str| DamageRelations {
+            double_damage_to: vec![double_damage_to.to_string()],
+            ..Default::default()
+        };
+        let pokemon_type = PokemonType {
+            name: "ghost".to_string(),
+            damage_relations: relations("ghost"),
+            past_damage_relations: vec![
+                PastDamageRelations {
+                    generation: 1,
+                    damage_relations: relations("normal"),
+                },
+                PastDamageRelations {
+                    generation: 5,
+                    damage_relations: relations("psychic"),
+                },
+            ],
+        };
+
+        assert_eq!(pokemon_type.damage_relations_in(1), &relations("normal"));
+        assert_eq!(pokemon_type.damage_relations_in(2), &relations("psychic"));
+        assert_eq!(pokemon_type.damage_relations_in(5), &relations("psychic"));
+        assert_eq!(pokemon_type.damage_relations_in(6), &relations("ghost"));
+        assert_eq!(pokemon_type.damage_relations_in(9), &relations("ghost"));
+    }
+}
@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The request body to start a quiz.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizRequest {
+    /// Whether the description should be translated.
+    #[serde(default)]
+    pub translated: bool,
+}
+
+/// A started quiz, asking to guess the Pokémon from its description.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Quiz {
+    /// The id of the quiz session.
+    pub id: String,
+    /// The description of the Pokémon, with its name redacted.
+    pub description: String,
+    /// The amount of guesses left.
+    pub attempts_left: u8,
+    /// The amount of seconds after which the quiz expires.
+    pub expires_in_secs: u64,
+}
+
+/// The request body to guess the Pokémon of a quiz.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizGuess {
+    /// The guessed name.
+    pub name: String,
+}
+
+/// The result of a guess.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuizGuessResult {
+    /// Whether the guess is correct.
+    pub correct: bool,
+    /// Whether the wrong guess is close to the answer.
+    pub close: bool,
+    /// The amount of guesses left.
+    pub attempts_left: u8,
+    /// A hint about the Pokémon, given after a wrong guess.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// The name of the Pokémon, revealed once the quiz is over.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The searchable texts of a Pokémon species.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeciesTexts {
+    /// The name of the species.
+    pub name: String,
+    /// The genus of the species (e.g. `Mouse Pokémon`), if any.
+    pub genus: Option<String>,
+    /// The distinct English flavor texts of the species.
+    pub flavor_texts: Vec<String>,
+}
+
+/// The results of a search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    /// The searched text.
+    pub query: String,
+    /// The matching species, sorted by relevance.
+    pub results: Vec<SearchResult>,
+}
+
+/// A species matching a search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    /// The name of the species.
+    pub name: String,
+    /// The genus of the species, if any.
+    pub genus: Option<String>,
+    /// The relevance of the species; the higher, the more relevant.
+    pub score: f32,
+    /// The text which best matches the search, with the matching words wrapped in `<em>` tags.
+    pub snippet: String,
+}
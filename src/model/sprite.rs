@@ -0,0 +1,84 @@
+use std::{fmt, io};
+
+use bytes::Bytes;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The variants of the images of a Pokémon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum SpriteVariant {
+    /// The default front sprite.
+    #[default]
+    #[serde(rename = "front_default")]
+    FrontDefault,
+    /// The shiny front sprite.
+    #[serde(rename = "shiny")]
+    Shiny,
+    /// The official artwork.
+    #[serde(rename = "official-artwork")]
+    OfficialArtwork,
+}
+
+impl fmt::Display for SpriteVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FrontDefault => write!(f, "front_default"),
+            Self::Shiny => write!(f, "shiny"),
+            Self::OfficialArtwork => write!(f, "official-artwork"),
+        }
+    }
+}
+
+/// An image of a Pokémon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sprite {
+    /// The MIME type of the image (e.g. `image/png`).
+    pub content_type: String,
+    /// The content of the image.
+    pub data: Vec<u8>,
+}
+
+/// An image of a Pokémon whose content is streamed while it is downloaded,
+/// so that it never has to be held in memory as a whole.
+pub struct SpriteStream {
+    /// The MIME type of the image (e.g. `image/png`).
+    pub content_type: String,
+    /// The chunks of the content of the image.
+    pub data: BoxStream<'static, io::Result<Bytes>>,
+}
+
+impl SpriteStream {
+    /// Collects the whole content of the image into a [`Sprite`].
+    pub async fn collect(self) -> io::Result<Sprite> {
+        let data = self
+            .data
+            .try_fold(Vec::new(), |mut data, chunk| async move {
+                data.extend_from_slice(&chunk);
+                Ok(data)
+            })
+            .await?;
+
+        Ok(Sprite {
+            content_type: self.content_type,
+            data,
+        })
+    }
+}
+
+impl fmt::Debug for SpriteStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpriteStream")
+            .field("content_type", &self.content_type)
+            .finish_non_exhaustive()
+    }
+}
+
+impl From<Sprite> for SpriteStream {
+    fn from(sprite: Sprite) -> Self {
+        Self {
+            content_type: sprite.content_type,
+            data: futures::stream::once(async move { Ok(Bytes::from(sprite.data)) }).boxed(),
+        }
+    }
+}
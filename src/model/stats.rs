@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A struct representing a value for each Pokémon stat.
+///
+/// It is used both for the base stats of a Pokémon and for the EVs and IVs spreads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    /// Hit points.
+    pub hp: u16,
+    /// Attack.
+    pub attack: u16,
+    /// Defense.
+    pub defense: u16,
+    /// Special attack.
+    pub special_attack: u16,
+    /// Special defense.
+    pub special_defense: u16,
+    /// Speed.
+    pub speed: u16,
+}
+
+impl Stats {
+    /// Creates a new [`Stats`] with the same value for each stat.
+    pub fn splat(value: u16) -> Self {
+        Self {
+            hp: value,
+            attack: value,
+            defense: value,
+            special_attack: value,
+            special_defense: value,
+            speed: value,
+        }
+    }
+
+    /// Returns the sum of all the stats.
+    pub fn total(&self) -> u16 {
+        self.hp
+            + self.attack
+            + self.defense
+            + self.special_attack
+            + self.special_defense
+            + self.speed
+    }
+}
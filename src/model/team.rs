@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::model::{Pokemon, Stats};
+
+/// The request body of the team analysis.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamRequest {
+    /// The names of the team members; up to six.
+    pub members: Vec<String>,
+}
+
+/// A member of the analyzed team.
+///
+/// Either `pokemon` or `error` is defined, depending on whether the member could be fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamMember {
+    /// The requested name of the member.
+    pub name: String,
+    /// The Pokémon data of the member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pokemon: Option<Pokemon>,
+    /// The error occurred while fetching the member.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<TeamMemberError>,
+}
+
+/// The error occurred while fetching a [`TeamMember`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamMemberError {
+    /// The HTTP status code which would have been returned for the member alone.
+    pub status: u16,
+    /// The error message.
+    pub message: String,
+}
+
+/// The result of the team analysis. Only the members which could be fetched are considered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamAnalysis {
+    /// The members of the team.
+    pub members: Vec<TeamMember>,
+    /// The defending types the team hits super effectively with its own types.
+    pub coverage: TypeCoverage,
+    /// The attacking types at least two members are weak to, with the amount of weak members.
+    pub shared_weaknesses: BTreeMap<String, u8>,
+    /// The attacking types resisted by the team, with the amount of members resisting or immune.
+    pub resistances: BTreeMap<String, u8>,
+    /// The base stats of the team.
+    pub stats: TeamStats,
+    /// Suggestions to cover the uncovered types.
+    pub suggestions: Vec<CoverageSuggestion>,
+}
+
+/// The offensive type coverage of a team.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeCoverage {
+    /// The defending types which are hit super effectively.
+    pub covered: Vec<String>,
+    /// The defending types which are not hit super effectively.
+    pub uncovered: Vec<String>,
+}
+
+/// The base stats of a team.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamStats {
+    /// The sum of the base stats of the members.
+    pub total: Stats,
+    /// The average of the base stats of the members.
+    pub average: Stats,
+}
+
+/// The attacking types which would cover an uncovered type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageSuggestion {
+    /// The defending type which is not covered.
+    pub uncovered: String,
+    /// The attacking types which hit it super effectively.
+    pub attacking_types: Vec<String>,
+}
@@ -0,0 +1,45 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The effectiveness of an attacking type against one or more defending types.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeEffectiveness {
+    /// The name of the attacking type.
+    pub attacker: String,
+    /// The names of the defending types.
+    pub defender: Vec<String>,
+    /// The generation whose type chart has been used.
+    pub generation: u8,
+    /// The damage multiplier; one of 0, 0.25, 0.5, 1, 2 and 4.
+    pub multiplier: f32,
+}
+
+/// The attacking types which are not neutral against one or more defending types.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeMatchups {
+    /// The attacking types which deal more damage, with their multiplier.
+    pub weaknesses: BTreeMap<String, f32>,
+    /// The attacking types which deal less damage, with their multiplier.
+    pub resistances: BTreeMap<String, f32>,
+    /// The attacking types which deal no damage.
+    pub immunities: Vec<String>,
+}
+
+/// The [`TypeMatchups`] of a Pokémon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PokemonWeaknesses {
+    /// The name of the Pokémon.
+    pub name: String,
+    /// The names of the Pokémon types.
+    pub types: Vec<String>,
+    /// The generation whose type chart has been used.
+    pub generation: u8,
+    /// The matchups of the Pokémon types.
+    #[serde(flatten)]
+    pub matchups: TypeMatchups,
+}
@@ -0,0 +1,265 @@
+//! This module records the metrics of the application and exposes them in the Prometheus text format.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use tracing::Instrument as _;
+use tracing::field::Empty;
+
+use crate::adapters::battle::BattleAdapterError;
+use crate::adapters::item::ItemAdapterError;
+use crate::adapters::pokedex::PokedexAdapterError;
+use crate::adapters::translation::TranslationAdapterError;
+
+const HTTP_REQUESTS_TOTAL: &str = "http_requests_total";
+const HTTP_REQUEST_DURATION_SECONDS: &str = "http_request_duration_seconds";
+const HTTP_REQUESTS_IN_FLIGHT: &str = "http_requests_in_flight";
+const UPSTREAM_REQUESTS_TOTAL: &str = "upstream_requests_total";
+const UPSTREAM_REQUEST_DURATION_SECONDS: &str = "upstream_request_duration_seconds";
+const CACHE_LOOKUPS_TOTAL: &str = "cache_lookups_total";
+const TRANSLATION_FALLBACKS_TOTAL: &str = "translation_fallbacks_total";
+const RATE_LIMITED_REQUESTS_TOTAL: &str = "rate_limited_requests_total";
+
+/// Buckets of the latency histograms, in seconds.
+const LATENCY_BUCKETS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+/// Interval between the upkeeps of the recorder, which drain the histogram samples.
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The handle of the global Prometheus recorder.
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Installs the global Prometheus recorder, if not installed yet, and returns the handle rendering the metrics.
+pub fn install() -> PrometheusHandle {
+    HANDLE
+        .get_or_init(|| {
+            let handle = PrometheusBuilder::new()
+                .set_buckets_for_metric(Matcher::Suffix("_seconds".to_string()), &LATENCY_BUCKETS)
+                .expect("latency buckets are not empty")
+                .install_recorder()
+                .expect("no other metrics recorder is installed");
+            describe_metrics();
+            handle
+        })
+        .clone()
+}
+
+/// Runs the upkeep of the recorder at each interval, so the histogram samples don't pile up between scrapes.
+pub async fn run_upkeep_periodically(handle: PrometheusHandle) {
+    let mut interval = tokio::time::interval(UPKEEP_INTERVAL);
+    loop {
+        interval.tick().await;
+        handle.run_upkeep();
+    }
+}
+
+/// Describes the metrics, so they are rendered with their help text.
+fn describe_metrics() {
+    describe_counter!(
+        HTTP_REQUESTS_TOTAL,
+        "Requests served, by method, route and status"
+    );
+    describe_histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        metrics::Unit::Seconds,
+        "Latency of the requests, by method, route and status"
+    );
+    describe_gauge!(HTTP_REQUESTS_IN_FLIGHT, "Requests being served");
+    describe_counter!(
+        UPSTREAM_REQUESTS_TOTAL,
+        "Requests to the upstream services, by adapter and outcome"
+    );
+    describe_histogram!(
+        UPSTREAM_REQUEST_DURATION_SECONDS,
+        metrics::Unit::Seconds,
+        "Latency of the requests to the upstream services, by adapter"
+    );
+    describe_counter!(CACHE_LOOKUPS_TOTAL, "Cache lookups, by cache and result");
+    describe_counter!(
+        TRANSLATION_FALLBACKS_TOTAL,
+        "Descriptions returned untranslated since the translation failed"
+    );
+    describe_counter!(
+        RATE_LIMITED_REQUESTS_TOTAL,
+        "Requests rejected by the rate limiter, by scope"
+    );
+}
+
+/// Records a request served by the web server.
+pub fn record_http_request(method: String, route: String, status: u16, duration: Duration) {
+    let labels = [
+        ("method", method),
+        ("route", route),
+        ("status", status.to_string()),
+    ];
+    counter!(HTTP_REQUESTS_TOTAL, &labels).increment(1);
+    histogram!(HTTP_REQUEST_DURATION_SECONDS, &labels).record(duration);
+}
+
+/// Tracks a request in flight, until dropped.
+#[derive(Debug)]
+pub struct InFlightRequest;
+
+impl InFlightRequest {
+    /// Starts tracking a request in flight.
+    pub fn start() -> Self {
+        gauge!(HTTP_REQUESTS_IN_FLIGHT).increment(1);
+        Self
+    }
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        gauge!(HTTP_REQUESTS_IN_FLIGHT).decrement(1);
+    }
+}
+
+/// An adapter error, whose kind is recorded as the outcome of the upstream request.
+pub trait UpstreamError {
+    /// The kind of the error, used as metric label.
+    fn kind(&self) -> &'static str;
+}
+
+impl UpstreamError for PokedexAdapterError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::NetworkError(_) => "network_error",
+            Self::Timeout(_) => "timeout",
+            Self::ParseError(_) => "parse_error",
+            Self::UnexpectedResponse(_) => "unexpected_response",
+        }
+    }
+}
+
+impl UpstreamError for BattleAdapterError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::NetworkError(_) => "network_error",
+            Self::Timeout(_) => "timeout",
+            Self::ParseError(_) => "parse_error",
+            Self::UnexpectedResponse(_) => "unexpected_response",
+        }
+    }
+}
+
+impl UpstreamError for ItemAdapterError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound => "not_found",
+            Self::NetworkError(_) => "network_error",
+            Self::Timeout(_) => "timeout",
+            Self::ParseError(_) => "parse_error",
+            Self::UnexpectedResponse(_) => "unexpected_response",
+        }
+    }
+}
+
+impl UpstreamError for TranslationAdapterError {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::NetworkError(_) => "network_error",
+            Self::Timeout(_) => "timeout",
+            Self::ParseError(_) => "parse_error",
+            Self::UnknownError(_) => "unknown_error",
+            Self::UnexpectedResponse(_) => "unexpected_response",
+        }
+    }
+}
+
+/// Runs a request to an upstream service through the given adapter, recording its outcome and latency.
+///
+/// The request runs within its own span, which is the parent of the trace context sent upstream.
+pub async fn observe_upstream<T, E>(
+    adapter: &'static str,
+    request: impl Future<Output = Result<T, E>>,
+) -> Result<T, E>
+where
+    E: UpstreamError,
+{
+    let span = info_span!(
+        "upstream request",
+        otel.name = format!("{adapter} request"),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        adapter,
+        outcome = Empty,
+    );
+    let started = Instant::now();
+    let result = request.instrument(span.clone()).await;
+    let outcome = match &result {
+        Ok(_) => "ok",
+        Err(err) => err.kind(),
+    };
+    span.record("outcome", outcome);
+    if result.is_err() {
+        span.record("otel.status_code", "error");
+    }
+    counter!(UPSTREAM_REQUESTS_TOTAL, "adapter" => adapter, "outcome" => outcome).increment(1);
+    histogram!(UPSTREAM_REQUEST_DURATION_SECONDS, "adapter" => adapter).record(started.elapsed());
+
+    result
+}
+
+/// Records a lookup of the given cache.
+pub fn record_cache_lookup(cache: &'static str, hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    counter!(CACHE_LOOKUPS_TOTAL, "cache" => cache, "result" => result).increment(1);
+}
+
+/// Records a description returned untranslated, since the translation failed.
+pub fn record_translation_fallback() {
+    counter!(TRANSLATION_FALLBACKS_TOTAL).increment(1);
+}
+
+/// Records a request rejected by the rate limiter for the given scope.
+pub fn record_rate_limited(scope: String) {
+    counter!(RATE_LIMITED_REQUESTS_TOTAL, "scope" => scope).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_render_metrics() {
+        let handle = install();
+
+        record_http_request(
+            "GET".to_string(),
+            "/test/{name}".to_string(),
+            200,
+            Duration::from_millis(20),
+        );
+        let result: Result<(), PokedexAdapterError> = observe_upstream("test", async {
+            Err(PokedexAdapterError::Timeout("timed out".to_string()))
+        })
+        .await;
+        assert!(result.is_err());
+        record_cache_lookup("test", true);
+        record_rate_limited("test".to_string());
+
+        let rendered = handle.render();
+        assert!(
+            rendered
+                .contains(r#"http_requests_total{method="GET",route="/test/{name}",status="200"}"#)
+        );
+        assert!(rendered.contains(
+            r#"http_request_duration_seconds_bucket{method="GET",route="/test/{name}",status="200",le="0.025"}"#
+        ));
+        assert!(rendered.contains(r#"upstream_requests_total{adapter="test",outcome="timeout"}"#));
+        assert!(rendered.contains(r#"cache_lookups_total{cache="test",result="hit"}"#));
+        assert!(rendered.contains(r#"rate_limited_requests_total{scope="test"}"#));
+    }
+
+    #[test]
+    fn test_should_install_once() {
+        install();
+        install();
+    }
+}
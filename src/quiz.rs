@@ -0,0 +1,8 @@
+//! This module implements the "Who's that Pokémon?" quiz: the in-memory store of the sessions,
+//! the redaction of the descriptions and the fuzzy matching of the guesses.
+
+mod matching;
+mod sessions;
+
+pub use self::matching::redact_name;
+pub use self::sessions::QuizSessions;
@@ -0,0 +1,167 @@
+/// The placeholder replacing the name of the Pokémon in the redacted descriptions.
+const REDACTED: &str = "???";
+/// The minimum length of a part of a composite name (e.g. `mime` in `mr-mime`) to be redacted.
+const MIN_REDACTED_PART_LEN: usize = 4;
+
+/// How close a guess is to the name of the Pokémon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessMatch {
+    /// The guess is correct, allowing small typos.
+    Correct,
+    /// The guess is wrong, but close to the name.
+    Close,
+    /// The guess is wrong.
+    Wrong,
+}
+
+/// Normalizes a name for the comparison, keeping only its lowercase alphanumeric characters.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Computes the Levenshtein distance between the two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
+
+/// Matches the guess against the given names of the Pokémon (e.g. its name and its species).
+///
+/// The amount of allowed typos grows with the length of the name.
+pub fn match_guess(guess: &str, names: &[&str]) -> GuessMatch {
+    let guess = normalize(guess);
+    if guess.is_empty() {
+        return GuessMatch::Wrong;
+    }
+
+    names
+        .iter()
+        .map(|name| {
+            let name = normalize(name);
+            let allowed_typos = match name.len() {
+                0..=4 => 0,
+                5..=8 => 1,
+                _ => 2,
+            };
+            let distance = levenshtein(&guess, &name);
+            if distance <= allowed_typos {
+                GuessMatch::Correct
+            } else if distance <= allowed_typos + 2 {
+                GuessMatch::Close
+            } else {
+                GuessMatch::Wrong
+            }
+        })
+        .min_by_key(|guess_match| *guess_match as u8)
+        .unwrap_or(GuessMatch::Wrong)
+}
+
+/// Redacts every occurrence of the given names of the Pokémon in the description.
+///
+/// Words are compared ignoring case and punctuation, so `PIKACHU's` is redacted as well.
+/// The parts of composite names (e.g. `mime` in `mr-mime`) are redacted too, if long enough.
+pub fn redact_name(description: &str, names: &[&str]) -> String {
+    let mut redacted: Vec<String> = Vec::new();
+    for name in names {
+        redacted.push(normalize(name));
+        redacted.extend(
+            name.split('-')
+                .map(normalize)
+                .filter(|part| part.len() >= MIN_REDACTED_PART_LEN),
+        );
+    }
+
+    description
+        .split(' ')
+        .map(|word| {
+            // keep the punctuation around the word
+            let start = word.find(|c: char| c.is_alphanumeric()).unwrap_or(0);
+            let end = word
+                .rfind(|c: char| c.is_alphanumeric())
+                .map(|end| end + 1)
+                .unwrap_or(start);
+            let core = &word[start..end];
+            let core_name = core
+                .strip_suffix("'s")
+                .or_else(|| core.strip_suffix("’s"))
+                .unwrap_or(core);
+
+            if !core_name.is_empty() && redacted.contains(&normalize(core_name)) {
+                format!(
+                    "{}{REDACTED}{}{}",
+                    &word[..start],
+                    &core[core_name.len()..],
+                    &word[end..]
+                )
+            } else {
+                word.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_compute_levenshtein_distance() {
+        assert_eq!(levenshtein("pikachu", "pikachu"), 0);
+        assert_eq!(levenshtein("pikachu", "pikachuu"), 1);
+        assert_eq!(levenshtein("pikachu", "pickachu"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "mew"), 3);
+    }
+
+    #[test]
+    fn test_should_match_guess() {
+        assert_eq!(match_guess("Pikachu", &["pikachu"]), GuessMatch::Correct);
+        assert_eq!(match_guess("pikachuu", &["pikachu"]), GuessMatch::Correct);
+        assert_eq!(match_guess("Mr. Mime", &["mr-mime"]), GuessMatch::Correct);
+        assert_eq!(
+            match_guess("vulpix", &["vulpix-alola", "vulpix"]),
+            GuessMatch::Correct
+        );
+        assert_eq!(match_guess("pikachoo", &["pikachu"]), GuessMatch::Close);
+        assert_eq!(match_guess("mewtwo", &["mew"]), GuessMatch::Wrong);
+        assert_eq!(match_guess("mow", &["mew"]), GuessMatch::Close);
+        assert_eq!(match_guess("raichu", &["pikachu"]), GuessMatch::Wrong);
+        assert_eq!(match_guess("  ", &["pikachu"]), GuessMatch::Wrong);
+    }
+
+    #[test]
+    fn test_should_redact_name() {
+        assert_eq!(
+            redact_name(
+                "When several of these POKéMON gather, PIKACHU's electricity could build.",
+                &["pikachu"]
+            ),
+            "When several of these POKéMON gather, ???'s electricity could build."
+        );
+        assert_eq!(
+            redact_name("MR. MIME is a pantomime expert.", &["mr-mime"]),
+            "MR. ??? is a pantomime expert."
+        );
+        assert_eq!(
+            redact_name("A yellow electric mouse.", &["pikachu"]),
+            "A yellow electric mouse."
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::matching::{GuessMatch, match_guess};
+use crate::model::{Pokemon, QuizGuessResult};
+
+/// Default time to live of a quiz session.
+const DEFAULT_TTL: Duration = Duration::from_secs(600);
+/// Default amount of guesses allowed for each quiz session.
+const DEFAULT_MAX_ATTEMPTS: u8 = 5;
+
+/// A running quiz session.
+#[derive(Debug)]
+struct QuizSession {
+    /// the Pokémon to guess
+    pokemon: Pokemon,
+    /// the amount of wrong guesses so far
+    wrong_guesses: u8,
+    /// when the session expires
+    expires_at: Instant,
+}
+
+/// In-memory store of the running quiz sessions.
+///
+/// Sessions expire after the configured time to live and are removed as soon as
+/// the Pokémon is guessed or the attempts are over.
+#[derive(Debug)]
+pub struct QuizSessions {
+    sessions: Mutex<HashMap<String, QuizSession>>,
+    ttl: Duration,
+    max_attempts: u8,
+}
+
+impl Default for QuizSessions {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL, DEFAULT_MAX_ATTEMPTS)
+    }
+}
+
+impl QuizSessions {
+    /// Creates a new [`QuizSessions`] store with the given time to live and attempts of each session.
+    pub fn new(ttl: Duration, max_attempts: u8) -> Self {
+        Self {
+            sessions: Mutex::new(HashMap::new()),
+            ttl,
+            max_attempts: max_attempts.max(1),
+        }
+    }
+
+    /// Returns the amount of guesses allowed for each session.
+    pub fn max_attempts(&self) -> u8 {
+        self.max_attempts
+    }
+
+    /// Returns the time to live of each session.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Starts a new session to guess the given [`Pokemon`], returning its id.
+    pub fn start(&self, pokemon: Pokemon) -> String {
+        let id = format!("{:032x}", rand::random::<u128>());
+        let now = Instant::now();
+
+        let mut sessions = self.sessions.lock().expect("quiz sessions lock poisoned");
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(
+            id.clone(),
+            QuizSession {
+                pokemon,
+                wrong_guesses: 0,
+                expires_at: now + self.ttl,
+            },
+        );
+        debug!("started quiz session {id}; {} running", sessions.len());
+
+        id
+    }
+
+    /// Checks the guess for the session with the given id.
+    ///
+    /// Returns `None` if the session doesn't exist or is expired.
+    pub fn guess(&self, id: &str, guess: &str) -> Option<QuizGuessResult> {
+        let mut sessions = self.sessions.lock().expect("quiz sessions lock poisoned");
+        let session = sessions.get_mut(id)?;
+        if session.expires_at <= Instant::now() {
+            debug!("quiz session {id} expired");
+            sessions.remove(id);
+            return None;
+        }
+
+        let guess_match = match_guess(guess, &[&session.pokemon.name, &session.pokemon.species]);
+        if guess_match == GuessMatch::Correct {
+            let session = sessions.remove(id)?;
+            return Some(QuizGuessResult {
+                correct: true,
+                close: false,
+                attempts_left: self.max_attempts - session.wrong_guesses - 1,
+                hint: None,
+                answer: Some(session.pokemon.name),
+            });
+        }
+
+        session.wrong_guesses += 1;
+        let attempts_left = self.max_attempts - session.wrong_guesses;
+        let close = guess_match == GuessMatch::Close;
+        if attempts_left == 0 {
+            let session = sessions.remove(id)?;
+            return Some(QuizGuessResult {
+                correct: false,
+                close,
+                attempts_left,
+                hint: None,
+                answer: Some(session.pokemon.name),
+            });
+        }
+
+        Some(QuizGuessResult {
+            correct: false,
+            close,
+            attempts_left,
+            hint: Some(hint(&session.pokemon, session.wrong_guesses)),
+            answer: None,
+        })
+    }
+}
+
+/// Builds the hint to give after the given amount of wrong guesses.
+///
+/// Each wrong guess reveals something more about the Pokémon.
+fn hint(pokemon: &Pokemon, wrong_guesses: u8) -> String {
+    match wrong_guesses {
+        1 => format!("Its type is {}", pokemon.types.join("/")),
+        2 => format!("It was introduced in generation {}", pokemon.generation),
+        3 => format!(
+            "Its name starts with '{}' and has {} letters",
+            pokemon
+                .name
+                .chars()
+                .next()
+                .unwrap_or_default()
+                .to_ascii_uppercase(),
+            pokemon
+                .name
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .count()
+        ),
+        _ => match (&pokemon.habitat, pokemon.is_legendary) {
+            (_, true) => "It is a legendary Pokémon".to_string(),
+            (Some(habitat), false) => format!("It lives in the {habitat} habitat"),
+            (None, false) => "It is not a legendary Pokémon".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_should_guess_pokemon() {
+        let sessions = QuizSessions::default();
+        let id = sessions.start(pokemon());
+
+        let result = sessions.guess(&id, "Pikachuu").unwrap();
+
+        assert!(result.correct);
+        assert_eq!(result.attempts_left, DEFAULT_MAX_ATTEMPTS - 1);
+        assert_eq!(result.answer.as_deref(), Some("pikachu"));
+        assert!(sessions.guess(&id, "pikachu").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_should_give_hints_on_wrong_guesses() {
+        let sessions = QuizSessions::new(DEFAULT_TTL, 3);
+        let id = sessions.start(pokemon());
+
+        let result = sessions.guess(&id, "raichu").unwrap();
+        assert!(!result.correct);
+        assert!(!result.close);
+        assert_eq!(result.attempts_left, 2);
+        assert_eq!(result.hint.as_deref(), Some("Its type is electric"));
+        assert!(result.answer.is_none());
+
+        let result = sessions.guess(&id, "pickachoo").unwrap();
+        assert!(!result.correct);
+        assert!(result.close);
+        assert_eq!(result.attempts_left, 1);
+        assert_eq!(
+            result.hint.as_deref(),
+            Some("It was introduced in generation 1")
+        );
+
+        let result = sessions.guess(&id, "mew").unwrap();
+        assert!(!result.correct);
+        assert_eq!(result.attempts_left, 0);
+        assert!(result.hint.is_none());
+        assert_eq!(result.answer.as_deref(), Some("pikachu"));
+        assert!(sessions.guess(&id, "pikachu").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_should_expire_sessions() {
+        let sessions = QuizSessions::new(Duration::ZERO, 5);
+        let id = sessions.start(pokemon());
+
+        assert!(sessions.guess(&id, "pikachu").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_should_purge_expired_sessions_on_start() {
+        let sessions = QuizSessions::new(Duration::ZERO, 5);
+        sessions.start(pokemon());
+        sessions.start(pokemon());
+
+        assert_eq!(sessions.sessions.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_should_build_hints() {
+        let pokemon = pokemon();
+
+        assert_eq!(
+            hint(&pokemon, 3),
+            "Its name starts with 'P' and has 7 letters"
+        );
+        assert_eq!(hint(&pokemon, 4), "It lives in the forest habitat");
+        assert_eq!(
+            hint(
+                &Pokemon {
+                    is_legendary: true,
+                    ..pokemon
+                },
+                4
+            ),
+            "It is a legendary Pokémon"
+        );
+    }
+
+    fn pokemon() -> Pokemon {
+        Pokemon {
+            name: "pikachu".to_string(),
+            description: "An electric mouse.".to_string(),
+            habitat: Some("forest".to_string()),
+            is_legendary: false,
+            generation: 1,
+            species: "pikachu".to_string(),
+            forms: vec![],
+            types: vec!["electric".to_string()],
+            stats: Default::default(),
+            height: 4,
+            weight: 60,
+        }
+    }
+}
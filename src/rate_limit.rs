@@ -0,0 +1,12 @@
+//! This module implements the rate limiting of the clients, with a token bucket for each client and scope.
+//!
+//! Clients are identified by their IP address, taken from the `X-Forwarded-For` header when behind trusted proxies,
+//! or by their issued API key; the buckets are kept in memory and periodically cleaned up.
+
+mod client;
+mod ip_network;
+mod limiter;
+
+pub use self::client::{ClientIdentifier, load_api_keys};
+pub use self::ip_network::IpNetwork;
+pub use self::limiter::{RateLimit, RateLimitDecision, RateLimitScope, RateLimiter};
@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+
+use axum::http::HeaderMap;
+
+use super::IpNetwork;
+
+/// The header listing the addresses of the client and of the proxies the request went through.
+const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+/// The header carrying the API key of the client.
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Identifies the client of a request, to rate limit it.
+#[derive(Debug, Clone, Default)]
+pub struct ClientIdentifier {
+    /// the proxies whose `X-Forwarded-For` header is trusted
+    trusted_proxies: Vec<IpNetwork>,
+    /// the issued API keys, identifying their clients
+    api_keys: HashSet<String>,
+}
+
+impl ClientIdentifier {
+    /// Creates a new [`ClientIdentifier`] trusting the `X-Forwarded-For` header of the given proxies,
+    /// and identifying the clients sending one of the given API keys by their key.
+    pub fn new(trusted_proxies: Vec<IpNetwork>, api_keys: HashSet<String>) -> Self {
+        Self {
+            trusted_proxies,
+            api_keys,
+        }
+    }
+
+    /// Returns the key identifying the client of the request received from the given peer.
+    ///
+    /// That's the API key of the request if it is an issued one, otherwise the address of the client:
+    /// unknown keys are ignored, so clients can't get a fresh bucket by making up a new key.
+    pub fn identify(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> String {
+        if let Some(api_key) = self.api_key(headers) {
+            return format!("key:{api_key}");
+        }
+
+        match peer {
+            Some(peer) => format!("ip:{}", self.client_address(headers, peer)),
+            None => "ip:unknown".to_string(),
+        }
+    }
+
+    /// Returns the API key of the request, if it is an issued one.
+    fn api_key<'a>(&self, headers: &'a HeaderMap) -> Option<&'a str> {
+        headers
+            .get(API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| self.api_keys.contains(*value))
+    }
+
+    /// Returns the address of the client of the request received from the given peer.
+    ///
+    /// When the peer is a trusted proxy, the `X-Forwarded-For` header is walked from right to left, skipping the
+    /// trusted proxies: the first address which isn't one is the client, since any address on its left may be forged.
+    fn client_address(&self, headers: &HeaderMap, peer: IpAddr) -> IpAddr {
+        let mut client = peer.to_canonical();
+        if !self.is_trusted(client) {
+            return client;
+        }
+
+        let forwarded = headers
+            .get_all(FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect::<Vec<_>>();
+        for hop in forwarded.into_iter().rev() {
+            let Some(address) = parse_forwarded_address(hop) else {
+                break;
+            };
+            client = address;
+            if !self.is_trusted(client) {
+                break;
+            }
+        }
+
+        client
+    }
+
+    /// Returns whether the address is one of a trusted proxy.
+    fn is_trusted(&self, address: IpAddr) -> bool {
+        self.trusted_proxies
+            .iter()
+            .any(|network| network.contains(address))
+    }
+}
+
+/// Reads the issued API keys from the file, one per line; blank lines and the lines starting with `#` are skipped.
+pub fn load_api_keys(path: &Path) -> io::Result<HashSet<String>> {
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Parses an address of the `X-Forwarded-For` header, which may carry a port too.
+fn parse_forwarded_address(hop: &str) -> Option<IpAddr> {
+    let hop = hop.trim();
+    hop.parse::<IpAddr>()
+        .or_else(|_| hop.parse::<SocketAddr>().map(|address| address.ip()))
+        .ok()
+        .map(|address| address.to_canonical())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::str::FromStr;
+
+    use super::*;
+
+    const PEER: &str = "10.0.0.2";
+
+    fn identifier(trusted_proxies: &[&str], api_keys: &[&str]) -> ClientIdentifier {
+        ClientIdentifier::new(
+            trusted_proxies
+                .iter()
+                .map(|network| IpNetwork::from_str(network).unwrap())
+                .collect(),
+            api_keys.iter().map(|key| key.to_string()).collect(),
+        )
+    }
+
+    fn headers(entries: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in entries {
+            headers.append(*name, value.parse().unwrap());
+        }
+        headers
+    }
+
+    fn peer(address: &str) -> Option<IpAddr> {
+        Some(IpAddr::from_str(address).unwrap())
+    }
+
+    #[test]
+    fn test_should_identify_client_by_peer_address() {
+        let identifier = identifier(&[], &[]);
+        assert_eq!(
+            identifier.identify(&HeaderMap::new(), peer("203.0.113.7")),
+            "ip:203.0.113.7"
+        );
+        assert_eq!(
+            identifier.identify(&HeaderMap::new(), peer("::ffff:203.0.113.7")),
+            "ip:203.0.113.7"
+        );
+        assert_eq!(identifier.identify(&HeaderMap::new(), None), "ip:unknown");
+    }
+
+    #[test]
+    fn test_should_ignore_forwarded_for_from_untrusted_peer() {
+        let identifier = identifier(&["10.0.0.0/8"], &[]);
+        let headers = headers(&[(FORWARDED_FOR_HEADER, "198.51.100.1")]);
+        assert_eq!(
+            identifier.identify(&headers, peer("203.0.113.7")),
+            "ip:203.0.113.7"
+        );
+    }
+
+    #[test]
+    fn test_should_take_client_from_forwarded_for_of_trusted_proxies() {
+        let identifier = identifier(&["10.0.0.0/8"], &[]);
+        // the leftmost address is forged by the client, the rightmost is appended by the last proxy
+        let headers = headers(&[
+            (FORWARDED_FOR_HEADER, "192.0.2.1, 203.0.113.7"),
+            (FORWARDED_FOR_HEADER, "10.0.0.1"),
+        ]);
+        assert_eq!(identifier.identify(&headers, peer(PEER)), "ip:203.0.113.7");
+
+        let headers = self::headers(&[(FORWARDED_FOR_HEADER, "10.0.0.3, 10.0.0.1")]);
+        assert_eq!(identifier.identify(&headers, peer(PEER)), "ip:10.0.0.3");
+
+        let headers = self::headers(&[(FORWARDED_FOR_HEADER, "[2001:db8::1]:4711")]);
+        assert_eq!(identifier.identify(&headers, peer(PEER)), "ip:2001:db8::1");
+    }
+
+    #[test]
+    fn test_should_stop_at_invalid_forwarded_for_address() {
+        let identifier = identifier(&["10.0.0.0/8"], &[]);
+        let headers = headers(&[(FORWARDED_FOR_HEADER, "203.0.113.7, unknown, 10.0.0.1")]);
+        assert_eq!(identifier.identify(&headers, peer(PEER)), "ip:10.0.0.1");
+
+        assert_eq!(
+            identifier.identify(&HeaderMap::new(), peer(PEER)),
+            format!("ip:{PEER}")
+        );
+    }
+
+    #[test]
+    fn test_should_identify_client_by_issued_api_key() {
+        let identifier = identifier(&[], &["ash", "misty"]);
+        let headers = headers(&[(API_KEY_HEADER, "ash")]);
+        assert_eq!(identifier.identify(&headers, peer(PEER)), "key:ash");
+    }
+
+    #[test]
+    fn test_should_identify_client_with_unknown_api_key_by_address() {
+        let identifier = identifier(&[], &["ash"]);
+        for key in ["brock", "ash ", ""] {
+            let headers = headers(&[(API_KEY_HEADER, key)]);
+            assert_eq!(
+                identifier.identify(&headers, peer(PEER)),
+                format!("ip:{PEER}")
+            );
+        }
+    }
+
+    #[test]
+    fn test_should_load_api_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("api-keys");
+        std::fs::write(&path, "# issued keys\nash\n\n  misty  \n").unwrap();
+
+        let api_keys = load_api_keys(&path).unwrap();
+        assert_eq!(
+            api_keys,
+            HashSet::from(["ash".to_string(), "misty".to_string()])
+        );
+        assert!(load_api_keys(&dir.path().join("missing")).is_err());
+    }
+}
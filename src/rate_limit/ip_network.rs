@@ -0,0 +1,122 @@
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+/// A range of IP addresses, written in CIDR notation (e.g. `10.0.0.0/8`), or a single address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpNetwork {
+    /// Returns whether the address belongs to the network.
+    ///
+    /// IPv4-mapped IPv6 addresses (e.g. `::ffff:10.0.0.1`) are matched as IPv4 addresses.
+    pub fn contains(&self, address: IpAddr) -> bool {
+        match (self.address, address.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix_len as u32)
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(address) & mask
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the maximum prefix length of the family of the address.
+    fn max_prefix_len(address: IpAddr) -> u8 {
+        match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+}
+
+impl fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix_len)
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_len) = match s.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (s, None),
+        };
+        let address = IpAddr::from_str(address)
+            .map_err(|_| format!("invalid IP address: {s}"))?
+            .to_canonical();
+        let max_prefix_len = Self::max_prefix_len(address);
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                .ok_or_else(|| format!("invalid network prefix length: {s}"))?,
+            None => max_prefix_len,
+        };
+
+        Ok(Self {
+            address,
+            prefix_len,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn network(s: &str) -> IpNetwork {
+        IpNetwork::from_str(s).unwrap()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        IpAddr::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_should_parse_networks() {
+        assert_eq!(network("10.0.0.0/8").to_string(), "10.0.0.0/8");
+        assert_eq!(network("192.168.1.1").to_string(), "192.168.1.1/32");
+        assert_eq!(network("fd00::/8").to_string(), "fd00::/8");
+        assert_eq!(network("::1").to_string(), "::1/128");
+        assert!(IpNetwork::from_str("10.0.0.0/33").is_err());
+        assert!(IpNetwork::from_str("10.0.0.0/").is_err());
+        assert!(IpNetwork::from_str("localhost").is_err());
+    }
+
+    #[test]
+    fn test_should_match_ipv4_addresses() {
+        let private = network("10.0.0.0/8");
+        assert!(private.contains(ip("10.1.2.3")));
+        assert!(private.contains(ip("::ffff:10.1.2.3")));
+        assert!(!private.contains(ip("11.0.0.1")));
+        assert!(!private.contains(ip("::1")));
+
+        assert!(network("0.0.0.0/0").contains(ip("203.0.113.7")));
+        assert!(network("203.0.113.7").contains(ip("203.0.113.7")));
+        assert!(!network("203.0.113.7").contains(ip("203.0.113.8")));
+    }
+
+    #[test]
+    fn test_should_match_ipv6_addresses() {
+        let unique_local = network("fd00::/8");
+        assert!(unique_local.contains(ip("fd12:3456::1")));
+        assert!(!unique_local.contains(ip("2001:db8::1")));
+        assert!(!unique_local.contains(ip("10.0.0.1")));
+        assert!(network("::/0").contains(ip("2001:db8::1")));
+    }
+}
@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use axum::http::HeaderMap;
+use tokio::time::Instant;
+
+use super::ClientIdentifier;
+
+/// Interval between the cleanups of the idle buckets.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+/// The window the rates of the limits are given in.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// The group of routes a limit applies to; each client has a separate bucket for each scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitScope {
+    /// all the routes but the translated ones
+    Default,
+    /// the translations of the descriptions, by `/pokemon/translated/*` and by the routes with the `translated` option
+    Translated,
+}
+
+impl fmt::Display for RateLimitScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::Translated => write!(f, "translated"),
+        }
+    }
+}
+
+/// A limit of requests per minute, allowing bursts of up to `burst` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    per_minute: u32,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Creates a new [`RateLimit`] of the given requests per minute and burst, which are at least one.
+    pub fn new(per_minute: u32, burst: u32) -> Self {
+        Self {
+            per_minute: per_minute.max(1),
+            burst: burst.max(1),
+        }
+    }
+
+    /// Returns the time it takes to refill the given amount of tokens.
+    fn refill_time(&self, tokens: f64) -> Duration {
+        Duration::from_secs_f64(
+            tokens.max(0.0) * RATE_WINDOW.as_secs_f64() / self.per_minute as f64,
+        )
+    }
+
+    /// Returns the amount of tokens refilled in the given time.
+    fn refilled_tokens(&self, elapsed: Duration) -> f64 {
+        elapsed.as_secs_f64() * self.per_minute as f64 / RATE_WINDOW.as_secs_f64()
+    }
+}
+
+/// The outcome of the rate limiting of a request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+    /// the amount of requests allowed at once
+    pub limit: u32,
+    /// the amount of requests left
+    pub remaining: u32,
+    /// the time until the bucket is full again
+    pub reset: Duration,
+    /// the time to wait before retrying, if the request is rejected
+    pub retry_after: Option<Duration>,
+}
+
+/// The tokens left to a client, refilled over time up to the burst of the limit.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    /// Creates a full bucket.
+    fn full(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst as f64,
+            updated_at: now,
+        }
+    }
+
+    /// Refills the tokens for the time elapsed since the last update.
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        self.tokens = (self.tokens + limit.refilled_tokens(elapsed)).min(limit.burst as f64);
+        self.updated_at = now;
+    }
+
+    /// Takes the given amount of tokens, if there are enough, returning the decision.
+    ///
+    /// Amounts over the burst are never taken, and are rejected with the time to refill them all.
+    fn take(&mut self, limit: &RateLimit, tokens: u32) -> RateLimitDecision {
+        let tokens = f64::from(tokens);
+        let retry_after = if self.tokens >= tokens {
+            self.tokens -= tokens;
+            None
+        } else {
+            Some(limit.refill_time(tokens - self.tokens))
+        };
+
+        RateLimitDecision {
+            limit: limit.burst,
+            remaining: self.tokens.floor() as u32,
+            reset: limit.refill_time(limit.burst as f64 - self.tokens),
+            retry_after,
+        }
+    }
+
+    /// Returns whether the bucket is full at the given time, in which case it is the same as a new one.
+    fn is_full(&self, limit: &RateLimit, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        self.tokens + limit.refilled_tokens(elapsed) >= limit.burst as f64
+    }
+}
+
+/// In-memory rate limiter, with a token bucket for each client and scope.
+///
+/// The translated routes fall under the default limit, unless they have their own.
+/// Buckets refilled to full are removed by the periodic cleanup, so idle clients take no memory.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    default_limit: Option<RateLimit>,
+    translated_limit: Option<RateLimit>,
+    clients: ClientIdentifier,
+    buckets: Mutex<HashMap<(RateLimitScope, String), Bucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a new [`RateLimiter`] with the given limits; routes without a limit are not limited.
+    pub fn new(default_limit: Option<RateLimit>, translated_limit: Option<RateLimit>) -> Self {
+        Self {
+            default_limit,
+            translated_limit,
+            clients: ClientIdentifier::default(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets how the clients are identified.
+    pub fn with_clients(mut self, clients: ClientIdentifier) -> Self {
+        self.clients = clients;
+        self
+    }
+
+    /// Returns the key identifying the client of the request received from the given peer.
+    pub fn client_key(&self, headers: &HeaderMap, peer: Option<IpAddr>) -> String {
+        self.clients.identify(headers, peer)
+    }
+
+    /// Takes a token from the bucket of the client for the given scope.
+    ///
+    /// Returns `None` if the scope is not limited.
+    pub fn check(&self, scope: RateLimitScope, client: &str) -> Option<RateLimitDecision> {
+        self.check_n(scope, client, 1)
+    }
+
+    /// Takes the given amount of tokens from the bucket of the client for the given scope, e.g. one for each
+    /// translation of a batch.
+    ///
+    /// Returns `None` if the scope is not limited.
+    pub fn check_n(
+        &self,
+        scope: RateLimitScope,
+        client: &str,
+        tokens: u32,
+    ) -> Option<RateLimitDecision> {
+        self.check_at(scope, client, tokens, Instant::now())
+    }
+
+    /// Takes the given amount of tokens from the bucket of the client for the given scope, at the given time.
+    fn check_at(
+        &self,
+        scope: RateLimitScope,
+        client: &str,
+        tokens: u32,
+        now: Instant,
+    ) -> Option<RateLimitDecision> {
+        let (scope, limit) = self.limit(scope)?;
+        let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+        let bucket = buckets
+            .entry((scope, client.to_string()))
+            .or_insert_with(|| Bucket::full(&limit, now));
+        bucket.refill(&limit, now);
+
+        Some(bucket.take(&limit, tokens))
+    }
+
+    /// Returns the scope whose bucket the requests of the given scope take from, and its limit.
+    fn limit(&self, scope: RateLimitScope) -> Option<(RateLimitScope, RateLimit)> {
+        match (scope, self.translated_limit) {
+            (RateLimitScope::Translated, Some(limit)) => Some((RateLimitScope::Translated, limit)),
+            _ => self
+                .default_limit
+                .map(|limit| (RateLimitScope::Default, limit)),
+        }
+    }
+
+    /// Removes the full buckets at each interval.
+    pub async fn clean_up_periodically(&self) {
+        let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.clean_up(Instant::now());
+        }
+    }
+
+    /// Removes the buckets which are full at the given time.
+    fn clean_up(&self, now: Instant) {
+        let mut buckets = self.buckets.lock().expect("rate limiter lock poisoned");
+        let before = buckets.len();
+        buckets.retain(|(scope, _), bucket| {
+            self.limit(*scope)
+                .is_some_and(|(_, limit)| !bucket.is_full(&limit, now))
+        });
+        debug!(
+            "removed {} idle rate limiter buckets; {} left",
+            before - buckets.len(),
+            buckets.len()
+        );
+    }
+
+    /// Returns the amount of buckets in the store.
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.buckets
+            .lock()
+            .expect("rate limiter lock poisoned")
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const CLIENT: &str = "ip:203.0.113.7";
+
+    fn limiter(
+        default_limit: Option<RateLimit>,
+        translated_limit: Option<RateLimit>,
+    ) -> RateLimiter {
+        RateLimiter::new(default_limit, translated_limit)
+    }
+
+    #[test]
+    fn test_should_not_limit_without_limits() {
+        let limiter = RateLimiter::default();
+        assert!(limiter.check(RateLimitScope::Default, CLIENT).is_none());
+        assert!(limiter.check(RateLimitScope::Translated, CLIENT).is_none());
+    }
+
+    #[test]
+    fn test_should_allow_burst_then_reject() {
+        let limiter = limiter(Some(RateLimit::new(60, 3)), None);
+        let now = Instant::now();
+
+        for remaining in [2, 1, 0] {
+            let decision = limiter
+                .check_at(RateLimitScope::Default, CLIENT, 1, now)
+                .unwrap();
+            assert!(decision.retry_after.is_none());
+            assert_eq!(decision.limit, 3);
+            assert_eq!(decision.remaining, remaining);
+        }
+
+        let decision = limiter
+            .check_at(RateLimitScope::Default, CLIENT, 1, now)
+            .unwrap();
+        assert!(decision.retry_after.is_some());
+        assert_eq!(decision.remaining, 0);
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(1)));
+        assert_eq!(decision.reset, Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_should_refill_tokens_over_time() {
+        let limiter = limiter(Some(RateLimit::new(60, 2)), None);
+        let now = Instant::now();
+        for _ in 0..2 {
+            limiter.check_at(RateLimitScope::Default, CLIENT, 1, now);
+        }
+        assert!(
+            limiter
+                .check_at(RateLimitScope::Default, CLIENT, 1, now)
+                .unwrap()
+                .retry_after
+                .is_some()
+        );
+
+        let later = now + Duration::from_millis(1500);
+        let decision = limiter
+            .check_at(RateLimitScope::Default, CLIENT, 1, later)
+            .unwrap();
+        assert!(decision.retry_after.is_none());
+        assert_eq!(decision.remaining, 0);
+
+        // never refilled over the burst
+        let much_later = later + Duration::from_secs(3600);
+        let decision = limiter
+            .check_at(RateLimitScope::Default, CLIENT, 1, much_later)
+            .unwrap();
+        assert_eq!(decision.remaining, 1);
+    }
+
+    #[test]
+    fn test_should_limit_clients_separately() {
+        let limiter = limiter(Some(RateLimit::new(60, 1)), None);
+        let now = Instant::now();
+        assert!(
+            limiter
+                .check_at(RateLimitScope::Default, CLIENT, 1, now)
+                .unwrap()
+                .retry_after
+                .is_none()
+        );
+        assert!(
+            limiter
+                .check_at(RateLimitScope::Default, "ip:198.51.100.1", 1, now)
+                .unwrap()
+                .retry_after
+                .is_none()
+        );
+        assert!(
+            limiter
+                .check_at(RateLimitScope::Default, CLIENT, 1, now)
+                .unwrap()
+                .retry_after
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_should_limit_translated_routes_separately() {
+        let limiter = limiter(Some(RateLimit::new(60, 5)), Some(RateLimit::new(1, 1)));
+        let now = Instant::now();
+
+        let decision = limiter
+            .check_at(RateLimitScope::Translated, CLIENT, 1, now)
+            .unwrap();
+        assert!(decision.retry_after.is_none());
+        assert_eq!(decision.limit, 1);
+        let decision = limiter
+            .check_at(RateLimitScope::Translated, CLIENT, 1, now)
+            .unwrap();
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(60)));
+
+        let decision = limiter
+            .check_at(RateLimitScope::Default, CLIENT, 1, now)
+            .unwrap();
+        assert!(decision.retry_after.is_none());
+        assert_eq!(decision.remaining, 4);
+    }
+
+    #[test]
+    fn test_should_apply_default_limit_to_translated_routes() {
+        let limiter = limiter(Some(RateLimit::new(60, 2)), None);
+        let now = Instant::now();
+
+        limiter.check_at(RateLimitScope::Translated, CLIENT, 1, now);
+        let decision = limiter
+            .check_at(RateLimitScope::Default, CLIENT, 1, now)
+            .unwrap();
+        assert_eq!(decision.remaining, 0);
+
+        let limiter = self::limiter(None, Some(RateLimit::new(60, 2)));
+        assert!(limiter.check(RateLimitScope::Default, CLIENT).is_none());
+        assert!(limiter.check(RateLimitScope::Translated, CLIENT).is_some());
+    }
+
+    #[test]
+    fn test_should_take_many_tokens() {
+        let limiter = limiter(None, Some(RateLimit::new(60, 5)));
+        let now = Instant::now();
+
+        let decision = limiter
+            .check_at(RateLimitScope::Translated, CLIENT, 3, now)
+            .unwrap();
+        assert!(decision.retry_after.is_none());
+        assert_eq!(decision.remaining, 2);
+
+        let decision = limiter
+            .check_at(RateLimitScope::Translated, CLIENT, 3, now)
+            .unwrap();
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(1)));
+        assert_eq!(decision.remaining, 2);
+
+        // more tokens than the burst are never taken
+        let decision = limiter
+            .check_at(
+                RateLimitScope::Translated,
+                CLIENT,
+                6,
+                now + Duration::from_secs(60),
+            )
+            .unwrap();
+        assert_eq!(decision.retry_after, Some(Duration::from_secs(1)));
+        assert_eq!(decision.remaining, 5);
+    }
+
+    #[test]
+    fn test_should_clean_up_full_buckets() {
+        let limiter = limiter(Some(RateLimit::new(60, 2)), Some(RateLimit::new(1, 1)));
+        let now = Instant::now();
+        limiter.check_at(RateLimitScope::Default, CLIENT, 1, now);
+        limiter.check_at(RateLimitScope::Translated, CLIENT, 1, now);
+        assert_eq!(limiter.len(), 2);
+
+        limiter.clean_up(now + Duration::from_secs(1));
+        assert_eq!(limiter.len(), 1);
+
+        limiter.clean_up(now + Duration::from_secs(60));
+        assert_eq!(limiter.len(), 0);
+    }
+}
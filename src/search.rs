@@ -0,0 +1,11 @@
+//! This module implements the full-text search over the names, genera and flavor texts of the Pokémon species.
+//!
+//! The search is backed by an inverted index, which is loaded from a local snapshot if any
+//! and periodically rebuilt in the background from the [`PokedexAdapter`](crate::adapters::pokedex::PokedexAdapter).
+
+mod index;
+mod snippet;
+mod store;
+
+pub use self::index::InvertedIndex;
+pub use self::store::SearchIndex;
@@ -0,0 +1,310 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Bound;
+
+use super::snippet::best_snippet;
+use crate::model::{SearchResult, SpeciesTexts};
+
+/// Weight of the terms found in the name of a species.
+const NAME_WEIGHT: f32 = 3.0;
+/// Weight of the terms found in the genus of a species.
+const GENUS_WEIGHT: f32 = 2.0;
+/// Weight of the terms found in the flavor texts of a species.
+const FLAVOR_TEXT_WEIGHT: f32 = 1.0;
+/// Boost of the terms matching a query term by prefix only.
+const PREFIX_MATCH_BOOST: f32 = 0.5;
+/// Minimum length of the last query term to be matched by prefix.
+const MIN_PREFIX_LEN: usize = 2;
+/// Term frequency saturation, as in BM25.
+const K1: f32 = 1.2;
+
+/// The occurrences of a term in a document.
+#[derive(Debug, Clone, Copy)]
+struct Posting {
+    /// the index of the document
+    document: usize,
+    /// the term frequency, weighted by the fields the term was found in
+    weight: f32,
+}
+
+/// Inverted index of the texts of the Pokémon species.
+///
+/// Terms are kept sorted, so that the last term of a query can be matched by prefix
+/// while the user is still typing it.
+#[derive(Debug, Default)]
+pub struct InvertedIndex {
+    documents: Vec<SpeciesTexts>,
+    postings: BTreeMap<String, Vec<Posting>>,
+}
+
+impl InvertedIndex {
+    /// Builds the [`InvertedIndex`] of the given documents.
+    pub fn new(documents: Vec<SpeciesTexts>) -> Self {
+        let mut postings: BTreeMap<String, Vec<Posting>> = BTreeMap::new();
+
+        for (document, texts) in documents.iter().enumerate() {
+            let mut weights: HashMap<String, f32> = HashMap::new();
+            let fields = std::iter::once((texts.name.as_str(), NAME_WEIGHT))
+                .chain(
+                    texts
+                        .genus
+                        .iter()
+                        .map(|genus| (genus.as_str(), GENUS_WEIGHT)),
+                )
+                .chain(
+                    texts
+                        .flavor_texts
+                        .iter()
+                        .map(|text| (text.as_str(), FLAVOR_TEXT_WEIGHT)),
+                );
+            for (text, weight) in fields {
+                for term in tokenize(text) {
+                    *weights.entry(term).or_default() += weight;
+                }
+            }
+
+            for (term, weight) in weights {
+                postings
+                    .entry(term)
+                    .or_default()
+                    .push(Posting { document, weight });
+            }
+        }
+
+        Self {
+            documents,
+            postings,
+        }
+    }
+
+    /// Returns the amount of indexed documents.
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Searches the given text, returning at most `limit` results sorted by relevance.
+    ///
+    /// Documents match if they contain any of the terms of the query; the more terms they contain, the higher they rank.
+    /// The last term is matched by prefix too, so `elec` matches `electric`.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        let terms = tokenize(query);
+        let Some(last_term) = terms.last().cloned() else {
+            return vec![];
+        };
+        let prefix = (last_term.chars().count() >= MIN_PREFIX_LEN).then_some(last_term.as_str());
+        // repeated terms count once, wherever they are in the query
+        let mut seen = HashSet::new();
+        let terms: Vec<String> = terms
+            .into_iter()
+            .filter(|term| seen.insert(term.clone()))
+            .collect();
+
+        let mut scores: HashMap<usize, (f32, usize)> = HashMap::new();
+        for term in &terms {
+            // each query term counts once per document, with its best matching index term
+            let mut term_scores: HashMap<usize, f32> = HashMap::new();
+            for (index_term, postings) in self.matching_terms(term, prefix == Some(term.as_str())) {
+                let boost = if index_term == term {
+                    1.0
+                } else {
+                    PREFIX_MATCH_BOOST
+                };
+                let idf = self.idf(postings.len());
+                for posting in postings {
+                    let score = boost * idf * posting.weight * (K1 + 1.0) / (posting.weight + K1);
+                    let best = term_scores.entry(posting.document).or_default();
+                    *best = best.max(score);
+                }
+            }
+
+            for (document, score) in term_scores {
+                let (total, matched) = scores.entry(document).or_default();
+                *total += score;
+                *matched += 1;
+            }
+        }
+
+        let mut results: Vec<(usize, f32)> = scores
+            .into_iter()
+            .map(|(document, (score, matched))| {
+                (document, score * matched as f32 / terms.len() as f32)
+            })
+            .collect();
+        results.sort_by(|(a, a_score), (b, b_score)| {
+            b_score
+                .total_cmp(a_score)
+                .then_with(|| self.documents[*a].name.cmp(&self.documents[*b].name))
+        });
+        results.truncate(limit);
+
+        results
+            .into_iter()
+            .map(|(document, score)| {
+                let texts = &self.documents[document];
+                SearchResult {
+                    name: texts.name.clone(),
+                    genus: texts.genus.clone(),
+                    score,
+                    snippet: best_snippet(texts, &terms, prefix),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the index terms matching the given query term, either exactly or by prefix.
+    fn matching_terms<'a>(
+        &'a self,
+        term: &'a str,
+        prefix: bool,
+    ) -> Box<dyn Iterator<Item = (&'a String, &'a Vec<Posting>)> + 'a> {
+        if prefix {
+            Box::new(
+                self.postings
+                    .range::<str, _>((Bound::Included(term), Bound::Unbounded))
+                    .take_while(move |(index_term, _)| index_term.starts_with(term)),
+            )
+        } else {
+            Box::new(self.postings.get_key_value(term).into_iter())
+        }
+    }
+
+    /// Computes the inverse document frequency of a term found in the given amount of documents.
+    fn idf(&self, document_frequency: usize) -> f32 {
+        let documents = self.documents.len() as f32;
+        let document_frequency = document_frequency as f32;
+        (1.0 + (documents - document_frequency + 0.5) / (document_frequency + 0.5)).ln()
+    }
+}
+
+/// Splits the text into lowercase terms, dropping punctuation and single characters.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| term.chars().count() > 1)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_tokenize_text() {
+        assert_eq!(
+            tokenize("When several of these POKéMON gather, it's electric!"),
+            vec![
+                "when", "several", "of", "these", "pokémon", "gather", "it", "electric"
+            ]
+        );
+        assert_eq!(tokenize("mr-mime"), vec!["mr", "mime"]);
+        assert!(tokenize("  ,. ").is_empty());
+    }
+
+    #[test]
+    fn test_should_search_documents() {
+        let index = index();
+
+        let results = index.search("electric mouse", 10);
+
+        let names: Vec<&str> = results.iter().map(|result| result.name.as_str()).collect();
+        assert_eq!(names[..2], ["pikachu", "raichu"]);
+        assert_eq!(names.len(), 4);
+        assert_eq!(results[0].genus.as_deref(), Some("Mouse Pokémon"));
+        assert_eq!(
+            results[0].snippet,
+            "It keeps its tail raised to monitor its surroundings. An <em>electric</em> <em>mouse</em>."
+        );
+        assert!(results[0].score > results[1].score);
+        assert!(results[1].score > results[2].score);
+    }
+
+    #[test]
+    fn test_should_rank_names_first() {
+        let index = index();
+
+        let results = index.search("raichu", 10);
+
+        assert_eq!(results[0].name, "raichu");
+        assert_eq!(results.len(), 2);
+    }
+
+    #[test]
+    fn test_should_match_last_term_by_prefix() {
+        let index = index();
+
+        let results = index.search("magn", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "magnemite");
+        assert_eq!(
+            results[0].snippet,
+            "It uses <em>magnetic</em> waves to float."
+        );
+
+        // only the last term is matched by prefix
+        assert!(
+            index
+                .search("magn float", 10)
+                .iter()
+                .all(|result| result.name == "magnemite")
+        );
+        assert!(index.search("m", 10).is_empty());
+    }
+
+    #[test]
+    fn test_should_count_repeated_terms_once() {
+        let index = index();
+        let scores = |query| {
+            index
+                .search(query, 10)
+                .into_iter()
+                .map(|result| (result.name, result.score))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(scores("electric mouse electric"), scores("mouse electric"));
+        // the last term is still matched by prefix
+        assert_eq!(scores("magn float magn"), scores("float magn"));
+        assert_eq!(scores("magn float magn")[0].0, "magnemite");
+    }
+
+    #[test]
+    fn test_should_limit_results() {
+        let index = index();
+
+        assert_eq!(index.search("electric", 1).len(), 1);
+        assert!(index.search("", 10).is_empty());
+        assert!(index.search("missingno", 10).is_empty());
+    }
+
+    fn index() -> InvertedIndex {
+        InvertedIndex::new(vec![
+            SpeciesTexts {
+                name: "pikachu".to_string(),
+                genus: Some("Mouse Pokémon".to_string()),
+                flavor_texts: vec![
+                    "It keeps its tail raised to monitor its surroundings. An electric mouse."
+                        .to_string(),
+                    "It stores electricity in its cheeks.".to_string(),
+                ],
+            },
+            SpeciesTexts {
+                name: "raichu".to_string(),
+                genus: Some("Mouse Pokémon".to_string()),
+                flavor_texts: vec!["Its electric charges can reach 100,000 volts.".to_string()],
+            },
+            SpeciesTexts {
+                name: "magnemite".to_string(),
+                genus: Some("Magnet Pokémon".to_string()),
+                flavor_texts: vec![
+                    "It uses magnetic waves to float.".to_string(),
+                    "It is attracted by electric currents.".to_string(),
+                ],
+            },
+            SpeciesTexts {
+                name: "pichu".to_string(),
+                genus: Some("Tiny Mouse Pokémon".to_string()),
+                flavor_texts: vec!["It is not yet skilled at storing raichu's power.".to_string()],
+            },
+        ])
+    }
+}
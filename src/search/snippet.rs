@@ -0,0 +1,124 @@
+use crate::model::SpeciesTexts;
+
+/// Tag opening a highlighted word.
+const HIGHLIGHT_START: &str = "<em>";
+/// Tag closing a highlighted word.
+const HIGHLIGHT_END: &str = "</em>";
+
+/// Picks the flavor text of the species matching the most terms, with the matching words highlighted.
+///
+/// Falls back to the genus if no flavor text matches, e.g. when only the name does.
+pub fn best_snippet(texts: &SpeciesTexts, terms: &[String], prefix: Option<&str>) -> String {
+    let mut best: Option<(String, usize)> = None;
+    for text in &texts.flavor_texts {
+        let (snippet, matches) = highlight(text, terms, prefix);
+        if best
+            .as_ref()
+            .is_none_or(|(_, best_matches)| matches > *best_matches)
+        {
+            best = Some((snippet, matches));
+        }
+    }
+
+    if let Some((snippet, matches)) = &best
+        && *matches > 0
+    {
+        return snippet.clone();
+    }
+    if let Some(genus) = &texts.genus {
+        let (snippet, matches) = highlight(genus, terms, prefix);
+        if matches > 0 || best.is_none() {
+            return snippet;
+        }
+    }
+
+    best.map(|(snippet, _)| snippet).unwrap_or_default()
+}
+
+/// Highlights the words of the text matching any of the terms, or starting with the prefix.
+///
+/// Returns the highlighted text and the amount of highlighted words.
+fn highlight(text: &str, terms: &[String], prefix: Option<&str>) -> (String, usize) {
+    let mut highlighted = String::with_capacity(text.len());
+    let mut matches = 0;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        // copy everything up to the next word as it is
+        let word_start = rest.find(char::is_alphanumeric).unwrap_or(rest.len());
+        highlighted.push_str(&rest[..word_start]);
+        rest = &rest[word_start..];
+
+        let word_end = rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(rest.len());
+        let word = &rest[..word_end];
+        let term = word.to_lowercase();
+        if !word.is_empty()
+            && (terms.contains(&term) || prefix.is_some_and(|prefix| term.starts_with(prefix)))
+        {
+            highlighted.push_str(HIGHLIGHT_START);
+            highlighted.push_str(word);
+            highlighted.push_str(HIGHLIGHT_END);
+            matches += 1;
+        } else {
+            highlighted.push_str(word);
+        }
+        rest = &rest[word_end..];
+    }
+
+    (highlighted, matches)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_highlight_text() {
+        let terms = vec!["electric".to_string(), "mou".to_string()];
+
+        assert_eq!(
+            highlight("An Electric MOUSE, electrifying.", &terms, Some("mou")),
+            (
+                "An <em>Electric</em> <em>MOUSE</em>, electrifying.".to_string(),
+                2
+            )
+        );
+        assert_eq!(
+            highlight("An electric mouse.", &terms, None),
+            ("An <em>electric</em> mouse.".to_string(), 1)
+        );
+        assert_eq!(
+            highlight("Nothing here", &terms, None),
+            ("Nothing here".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn test_should_pick_best_snippet() {
+        let texts = SpeciesTexts {
+            name: "pikachu".to_string(),
+            genus: Some("Mouse Pokémon".to_string()),
+            flavor_texts: vec![
+                "It stores electricity in its cheeks.".to_string(),
+                "An electric mouse.".to_string(),
+            ],
+        };
+        let terms = vec!["electric".to_string(), "mouse".to_string()];
+
+        assert_eq!(
+            best_snippet(&texts, &terms, Some("mouse")),
+            "An <em>electric</em> <em>mouse</em>."
+        );
+        assert_eq!(
+            best_snippet(&texts, &["pokémon".to_string()], None),
+            "Mouse <em>Pokémon</em>"
+        );
+        assert_eq!(
+            best_snippet(&texts, &["pikachu".to_string()], None),
+            "It stores electricity in its cheeks."
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use super::InvertedIndex;
+use crate::adapters::pokedex::{PokedexAdapter, PokedexAdapterError, PokedexAdapterResult};
+use crate::model::SpeciesTexts;
+
+/// Default interval between the rebuilds of the index.
+const DEFAULT_REBUILD_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+/// The pokedex listing all the species to index.
+const NATIONAL_POKEDEX: &str = "national";
+/// Amount of species fetched concurrently while building the index.
+const FETCH_CONCURRENCY: usize = 8;
+
+/// Holds the current [`InvertedIndex`] used by the searches.
+///
+/// The index is swapped atomically when rebuilt, so searches are never blocked by a rebuild.
+#[derive(Debug)]
+pub struct SearchIndex {
+    index: RwLock<Option<Arc<InvertedIndex>>>,
+    /// the file where the indexed documents are stored, to be loaded on startup
+    snapshot: Option<PathBuf>,
+    rebuild_interval: Duration,
+}
+
+impl Default for SearchIndex {
+    fn default() -> Self {
+        Self::new(None, DEFAULT_REBUILD_INTERVAL)
+    }
+}
+
+impl SearchIndex {
+    /// Creates a new empty [`SearchIndex`], with an optional snapshot file and the interval between the rebuilds.
+    pub fn new(snapshot: Option<PathBuf>, rebuild_interval: Duration) -> Self {
+        Self {
+            index: RwLock::new(None),
+            snapshot,
+            rebuild_interval,
+        }
+    }
+
+    /// Returns the current [`InvertedIndex`], if it has been built yet.
+    pub fn get(&self) -> Option<Arc<InvertedIndex>> {
+        self.index
+            .read()
+            .expect("search index lock poisoned")
+            .clone()
+    }
+
+    /// Replaces the current index with the given one.
+    fn replace(&self, index: InvertedIndex) {
+        info!("search index replaced; {} species indexed", index.len());
+        *self.index.write().expect("search index lock poisoned") = Some(Arc::new(index));
+    }
+
+    /// Loads the snapshot, if any, and then rebuilds the index from the adapter at each interval.
+    ///
+    /// Failed rebuilds keep the current index.
+    pub async fn rebuild_periodically<P>(&self, adapter: &P)
+    where
+        P: PokedexAdapter,
+    {
+        match self.load_snapshot().await {
+            Ok(Some(documents)) => self.replace(InvertedIndex::new(documents)),
+            Ok(None) => {}
+            Err(err) => error!("failed to load the search index snapshot: {err}"),
+        }
+
+        let mut interval = tokio::time::interval(self.rebuild_interval);
+        loop {
+            interval.tick().await;
+            if let Err(err) = self.rebuild(adapter).await {
+                error!("failed to rebuild the search index: {err}");
+            }
+        }
+    }
+
+    /// Rebuilds the index from the species fetched from the adapter, storing them in the snapshot.
+    pub async fn rebuild<P>(&self, adapter: &P) -> PokedexAdapterResult<()>
+    where
+        P: PokedexAdapter,
+    {
+        debug!("rebuilding the search index...");
+        let documents = Self::fetch_documents(adapter).await?;
+
+        if let Err(err) = self.save_snapshot(&documents).await {
+            error!("failed to save the search index snapshot: {err}");
+        }
+        self.replace(InvertedIndex::new(documents));
+
+        Ok(())
+    }
+
+    /// Fetches the texts of all the species of the national pokedex.
+    ///
+    /// Species which can't be fetched are skipped, unless none can be fetched.
+    async fn fetch_documents<P>(adapter: &P) -> PokedexAdapterResult<Vec<SpeciesTexts>>
+    where
+        P: PokedexAdapter,
+    {
+        let species = adapter
+            .fetch_pokedex_by_name(NATIONAL_POKEDEX)
+            .await?
+            .species;
+
+        let mut documents = Vec::with_capacity(species.len());
+        for batch in species.chunks(FETCH_CONCURRENCY) {
+            let results = futures::future::join_all(
+                batch
+                    .iter()
+                    .map(|entry| adapter.fetch_species_texts_by_name(&entry.name)),
+            )
+            .await;
+            for (entry, result) in batch.iter().zip(results) {
+                match result {
+                    Ok(texts) => documents.push(texts),
+                    Err(err) => warn!("failed to fetch texts of {}: {err}", entry.name),
+                }
+            }
+        }
+
+        if documents.is_empty() && !species.is_empty() {
+            return Err(PokedexAdapterError::UnexpectedResponse(
+                "No species texts could be fetched".to_string(),
+            ));
+        }
+
+        Ok(documents)
+    }
+
+    /// Loads the documents from the snapshot, if any.
+    async fn load_snapshot(&self) -> io::Result<Option<Vec<SpeciesTexts>>> {
+        let Some(path) = &self.snapshot else {
+            return Ok(None);
+        };
+
+        let data = match tokio::fs::read(path).await {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        debug!("loaded search index snapshot {}", path.display());
+
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(io::Error::from)
+    }
+
+    /// Stores the documents into the snapshot, if any.
+    ///
+    /// The snapshot is written to a temporary file first, so it is never left partially written.
+    async fn save_snapshot(&self, documents: &[SpeciesTexts]) -> io::Result<()> {
+        let Some(path) = &self.snapshot else {
+            return Ok(());
+        };
+
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec(documents)?).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        debug!("saved search index snapshot {}", path.display());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::adapters::pokedex::MockPokedexAdapter;
+    use crate::model::{Pokedex, PokedexEntry};
+
+    #[tokio::test]
+    async fn test_should_rebuild_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let snapshot = dir.path().join("search").join("snapshot.json");
+        let search_index = SearchIndex::new(Some(snapshot.clone()), DEFAULT_REBUILD_INTERVAL);
+        assert!(search_index.get().is_none());
+
+        search_index.rebuild(&adapter()).await.unwrap();
+
+        let index = search_index.get().unwrap();
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.search("mouse", 10)[0].name, "pikachu");
+
+        // the snapshot is loaded by a new index
+        let search_index = SearchIndex::new(Some(snapshot), DEFAULT_REBUILD_INTERVAL);
+        let documents = search_index.load_snapshot().await.unwrap().unwrap();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].name, "pikachu");
+    }
+
+    #[tokio::test]
+    async fn test_should_skip_species_which_cannot_be_fetched() {
+        let adapter = adapter().with_pokedex(Pokedex {
+            name: NATIONAL_POKEDEX.to_string(),
+            region: None,
+            species: vec![entry(25, "pikachu"), entry(0, "missingno")],
+        });
+        let search_index = SearchIndex::default();
+
+        search_index.rebuild(&adapter).await.unwrap();
+
+        assert_eq!(search_index.get().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_should_keep_index_on_failed_rebuild() {
+        let search_index = SearchIndex::default();
+        search_index.rebuild(&adapter()).await.unwrap();
+
+        assert!(
+            search_index
+                .rebuild(&MockPokedexAdapter::not_found())
+                .await
+                .is_err()
+        );
+
+        assert_eq!(search_index.get().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_should_not_load_missing_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let search_index = SearchIndex::new(
+            Some(dir.path().join("snapshot.json")),
+            DEFAULT_REBUILD_INTERVAL,
+        );
+
+        assert!(search_index.load_snapshot().await.unwrap().is_none());
+        assert!(
+            SearchIndex::default()
+                .load_snapshot()
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    fn adapter() -> MockPokedexAdapter {
+        MockPokedexAdapter::not_found()
+            .with_species_texts(SpeciesTexts {
+                name: "pikachu".to_string(),
+                genus: Some("Mouse Pokémon".to_string()),
+                flavor_texts: vec!["An electric mouse.".to_string()],
+            })
+            .with_species_texts(SpeciesTexts {
+                name: "magnemite".to_string(),
+                genus: Some("Magnet Pokémon".to_string()),
+                flavor_texts: vec!["It uses magnetic waves to float.".to_string()],
+            })
+            .with_pokedex(Pokedex {
+                name: NATIONAL_POKEDEX.to_string(),
+                region: None,
+                species: vec![entry(25, "pikachu"), entry(81, "magnemite")],
+            })
+    }
+
+    fn entry(number: u16, name: &str) -> PokedexEntry {
+        PokedexEntry {
+            number,
+            name: name.to_string(),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! This module provides the disk cache and the processing of the Pokémon images.
+
+mod cache;
+mod resize;
+
+pub use self::cache::SpriteCache;
+pub use self::resize::{MAX_SPRITE_SIZE, resize_sprite, round_sprite_size};
@@ -0,0 +1,469 @@
+use std::io;
+use std::path::PathBuf;
+
+use futures::stream::StreamExt;
+use tokio::io::AsyncWriteExt;
+
+use super::resize::SPRITE_SIZES;
+use crate::model::{Sprite, SpriteStream, SpriteVariant};
+use crate::monitoring;
+
+/// The name of the cache in the metrics.
+const CACHE_NAME: &str = "sprites";
+/// Content type of the cached images whose format can't be detected.
+const FALLBACK_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Cache of the Pokémon images on the disk.
+///
+/// Each image is stored in its own file, named after the Pokémon, the variant and the size.
+#[derive(Debug, Clone)]
+pub struct SpriteCache {
+    /// the directory where the images are stored
+    dir: PathBuf,
+}
+
+impl Default for SpriteCache {
+    fn default() -> Self {
+        Self::new(std::env::temp_dir().join("pokedex-api").join("sprites"))
+    }
+}
+
+impl SpriteCache {
+    /// Creates a new [`SpriteCache`] storing the images in the given directory.
+    ///
+    /// The directory is created on the first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Gets the cached [`Sprite`] of the given Pokémon, variant and size, if any.
+    pub async fn get(
+        &self,
+        name: &str,
+        variant: SpriteVariant,
+        size: Option<u32>,
+    ) -> io::Result<Option<Sprite>> {
+        let Some(path) = self.path(name, variant, size) else {
+            return Ok(None);
+        };
+
+        let data = match tokio::fs::read(&path).await {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                monitoring::record_cache_lookup(CACHE_NAME, false);
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+        trace!("sprite cache hit for {}", path.display());
+        monitoring::record_cache_lookup(CACHE_NAME, true);
+        let content_type = image::guess_format(&data)
+            .map(|format| format.to_mime_type())
+            .unwrap_or(FALLBACK_CONTENT_TYPE)
+            .to_string();
+
+        Ok(Some(Sprite { content_type, data }))
+    }
+
+    /// Stores the [`Sprite`] of the given Pokémon, variant and size.
+    ///
+    /// The image is written to a temporary file first, so readers never see a partial image.
+    pub async fn put(
+        &self,
+        name: &str,
+        variant: SpriteVariant,
+        size: Option<u32>,
+        sprite: &Sprite,
+    ) -> io::Result<()> {
+        let Some(mut writer) = self.writer(name, variant, size).await? else {
+            return Ok(());
+        };
+        writer.write(&sprite.data).await?;
+        writer.finish().await
+    }
+
+    /// Stores the image of the given Pokémon, variant and size while it is streamed,
+    /// returning a [`SpriteStream`] of the same content.
+    ///
+    /// The image is cached only once the whole stream has been consumed without errors;
+    /// cache errors are only logged, so they never fail the stream.
+    pub async fn put_stream(
+        &self,
+        name: &str,
+        variant: SpriteVariant,
+        size: Option<u32>,
+        sprite: SpriteStream,
+    ) -> SpriteStream {
+        let writer = self
+            .writer(name, variant, size)
+            .await
+            .unwrap_or_else(|err| {
+                warn!("Failed to write the {variant} sprite of {name} to cache: {err}");
+                None
+            });
+
+        let data = futures::stream::unfold(
+            (Some(sprite.data), writer),
+            |(data, mut writer)| async move {
+                let mut data = data?;
+                match data.next().await {
+                    Some(Ok(chunk)) => {
+                        if let Some(cache_writer) = writer.as_mut() {
+                            if let Err(err) = cache_writer.write(&chunk).await {
+                                warn!("Failed to write sprite to cache: {err}");
+                                writer = None;
+                            }
+                        }
+                        Some((Ok(chunk), (Some(data), writer)))
+                    }
+                    // the partial image is discarded when the writer is dropped
+                    Some(Err(err)) => Some((Err(err), (None, None))),
+                    None => {
+                        if let Some(writer) = writer {
+                            if let Err(err) = writer.finish().await {
+                                warn!("Failed to write sprite to cache: {err}");
+                            }
+                        }
+                        None
+                    }
+                }
+            },
+        );
+
+        SpriteStream {
+            content_type: sprite.content_type,
+            data: data.boxed(),
+        }
+    }
+
+    /// Opens a [`SpriteCacheWriter`] for the image of the given Pokémon, variant and size.
+    ///
+    /// Returns `None` if the name is not a valid Pokémon name.
+    async fn writer(
+        &self,
+        name: &str,
+        variant: SpriteVariant,
+        size: Option<u32>,
+    ) -> io::Result<Option<SpriteCacheWriter>> {
+        let Some(path) = self.path(name, variant, size) else {
+            debug!("not caching sprite of {name}: invalid name or size");
+            return Ok(None);
+        };
+
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let tmp_path = path.with_extension(format!("{}.tmp", rand::random::<u32>()));
+        let file = tokio::fs::File::create(&tmp_path).await?;
+
+        Ok(Some(SpriteCacheWriter {
+            file,
+            tmp_path,
+            path,
+            finished: false,
+        }))
+    }
+
+    /// Checks that images can be written to the cache directory.
+    pub async fn health_check(&self) -> io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let probe_path = self
+            .dir
+            .join(format!(".health-{}.tmp", rand::random::<u32>()));
+        tokio::fs::write(&probe_path, []).await?;
+        tokio::fs::remove_file(&probe_path).await
+    }
+
+    /// Builds the path of the cached image.
+    ///
+    /// Returns `None` if the name is not a valid Pokémon name,
+    /// so that nothing is ever written outside of the cache directory,
+    /// or if the size is not one of the [`SPRITE_SIZES`], so that the cached thumbnails are bounded.
+    fn path(&self, name: &str, variant: SpriteVariant, size: Option<u32>) -> Option<PathBuf> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return None;
+        }
+        if size.is_some_and(|size| !SPRITE_SIZES.contains(&size)) {
+            return None;
+        }
+
+        let file_name = match size {
+            Some(size) => format!("{name}_{variant}_{size}.img"),
+            None => format!("{name}_{variant}.img"),
+        };
+
+        Some(self.dir.join(file_name))
+    }
+}
+
+/// Writes an image to the [`SpriteCache`] while it is received.
+///
+/// The image is written to a temporary file, which replaces the cached image once [finished](Self::finish);
+/// the temporary file is removed if the writer is dropped before, so readers never see a partial image.
+struct SpriteCacheWriter {
+    file: tokio::fs::File,
+    tmp_path: PathBuf,
+    path: PathBuf,
+    finished: bool,
+}
+
+impl SpriteCacheWriter {
+    /// Appends the chunk to the image.
+    async fn write(&mut self, chunk: &[u8]) -> io::Result<()> {
+        self.file.write_all(chunk).await
+    }
+
+    /// Completes the image, replacing the cached one.
+    async fn finish(mut self) -> io::Result<()> {
+        self.file.flush().await?;
+        tokio::fs::rename(&self.tmp_path, &self.path).await?;
+        self.finished = true;
+        debug!("cached sprite {}", self.path.display());
+
+        Ok(())
+    }
+}
+
+impl Drop for SpriteCacheWriter {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = std::fs::remove_file(&self.tmp_path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::sprites::MAX_SPRITE_SIZE;
+
+    /// Signature of a PNG file.
+    const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+    #[tokio::test]
+    async fn test_should_cache_sprite() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SpriteCache::new(dir.path().join("sprites"));
+        let sprite = Sprite {
+            content_type: "image/png".to_string(),
+            data: PNG_SIGNATURE.to_vec(),
+        };
+
+        assert!(
+            cache
+                .get("pikachu", SpriteVariant::Shiny, None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        cache
+            .put("pikachu", SpriteVariant::Shiny, None, &sprite)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            cache
+                .get("pikachu", SpriteVariant::Shiny, None)
+                .await
+                .unwrap(),
+            Some(sprite)
+        );
+        assert!(
+            cache
+                .get("pikachu", SpriteVariant::FrontDefault, None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            cache
+                .get("pikachu", SpriteVariant::Shiny, Some(32))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_cache_streamed_sprite() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SpriteCache::new(dir.path());
+        let sprite = Sprite {
+            content_type: "image/png".to_string(),
+            data: PNG_SIGNATURE.to_vec(),
+        };
+
+        let streamed = cache
+            .put_stream(
+                "pikachu",
+                SpriteVariant::Shiny,
+                None,
+                SpriteStream::from(sprite.clone()),
+            )
+            .await;
+        // nothing is cached until the stream is consumed
+        assert!(
+            cache
+                .get("pikachu", SpriteVariant::Shiny, None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(streamed.collect().await.unwrap(), sprite);
+
+        assert_eq!(
+            cache
+                .get("pikachu", SpriteVariant::Shiny, None)
+                .await
+                .unwrap(),
+            Some(sprite)
+        );
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_should_not_cache_partially_streamed_sprite() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SpriteCache::new(dir.path());
+        let sprite = SpriteStream {
+            content_type: "image/png".to_string(),
+            data: futures::stream::iter([
+                Ok(bytes::Bytes::from_static(PNG_SIGNATURE)),
+                Err(io::Error::other("connection reset")),
+            ])
+            .boxed(),
+        };
+
+        let streamed = cache
+            .put_stream("pikachu", SpriteVariant::Shiny, None, sprite)
+            .await;
+        assert!(streamed.collect().await.is_err());
+
+        assert!(
+            cache
+                .get("pikachu", SpriteVariant::Shiny, None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // the temporary file is removed
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        // nor an abandoned stream
+        let streamed = cache
+            .put_stream(
+                "pikachu",
+                SpriteVariant::Shiny,
+                None,
+                SpriteStream::from(Sprite {
+                    content_type: "image/png".to_string(),
+                    data: PNG_SIGNATURE.to_vec(),
+                }),
+            )
+            .await;
+        drop(streamed);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_should_not_cache_sprite_with_invalid_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SpriteCache::new(dir.path());
+        let sprite = Sprite {
+            content_type: "image/png".to_string(),
+            data: PNG_SIGNATURE.to_vec(),
+        };
+
+        cache
+            .put("../pikachu", SpriteVariant::FrontDefault, None, &sprite)
+            .await
+            .unwrap();
+
+        assert!(
+            cache
+                .get("../pikachu", SpriteVariant::FrontDefault, None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_should_cache_only_sprite_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SpriteCache::new(dir.path());
+        let sprite = Sprite {
+            content_type: "image/png".to_string(),
+            data: PNG_SIGNATURE.to_vec(),
+        };
+
+        for size in 1..=MAX_SPRITE_SIZE {
+            cache
+                .put("pikachu", SpriteVariant::FrontDefault, Some(size), &sprite)
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(
+            std::fs::read_dir(dir.path()).unwrap().count(),
+            SPRITE_SIZES.len()
+        );
+        assert!(
+            cache
+                .get("pikachu", SpriteVariant::FrontDefault, Some(33))
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            cache
+                .get("pikachu", SpriteVariant::FrontDefault, Some(48))
+                .await
+                .unwrap(),
+            Some(sprite)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_check_health() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SpriteCache::new(dir.path().join("sprites"));
+        assert!(cache.health_check().await.is_ok());
+        // the probe file is removed
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("sprites"))
+                .unwrap()
+                .count(),
+            0
+        );
+
+        // the cache directory can't be created inside a file
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        let cache = SpriteCache::new(file.join("sprites"));
+        assert!(cache.health_check().await.is_err());
+    }
+
+    #[test]
+    fn test_should_build_cache_path() {
+        let cache = SpriteCache::new("/tmp/sprites");
+
+        assert_eq!(
+            cache.path("mr-mime", SpriteVariant::OfficialArtwork, Some(64)),
+            Some(PathBuf::from(
+                "/tmp/sprites/mr-mime_official-artwork_64.img"
+            ))
+        );
+        assert_eq!(
+            cache.path("pikachu", SpriteVariant::FrontDefault, None),
+            Some(PathBuf::from("/tmp/sprites/pikachu_front_default.img"))
+        );
+        assert_eq!(cache.path("Pikachu/..", SpriteVariant::Shiny, None), None);
+        assert_eq!(cache.path("", SpriteVariant::Shiny, None), None);
+        assert_eq!(cache.path("pikachu", SpriteVariant::Shiny, Some(65)), None);
+    }
+}
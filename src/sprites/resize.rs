@@ -0,0 +1,128 @@
+use std::io::Cursor;
+
+use image::ImageFormat;
+
+use crate::model::Sprite;
+
+/// The maximum size in pixels a sprite can be resized to.
+pub const MAX_SPRITE_SIZE: u32 = 512;
+/// The sizes in pixels the sprites are resized to, so that only a few thumbnails of each sprite are cached.
+pub const SPRITE_SIZES: [u32; 10] = [16, 24, 32, 48, 64, 96, 128, 192, 256, MAX_SPRITE_SIZE];
+
+/// Errors that can occur while resizing a sprite.
+#[derive(Debug, thiserror::Error)]
+pub enum SpriteResizeError {
+    #[error("Invalid size {0}: must be between 1 and {MAX_SPRITE_SIZE}")]
+    InvalidSize(u32),
+    #[error("Image error: {0}")]
+    Image(#[from] image::ImageError),
+}
+
+/// Rounds the size up to the nearest of the [`SPRITE_SIZES`].
+///
+/// Returns `None` if the size is larger than [`MAX_SPRITE_SIZE`].
+pub fn round_sprite_size(size: u32) -> Option<u32> {
+    SPRITE_SIZES
+        .into_iter()
+        .find(|sprite_size| *sprite_size >= size)
+}
+
+/// Resizes the [`Sprite`] to fit into a square of `size` pixels, preserving its aspect ratio.
+///
+/// Sprites are never upscaled, so they are returned as they are if already small enough.
+/// The resized sprite is always encoded as PNG.
+pub fn resize_sprite(sprite: Sprite, size: u32) -> Result<Sprite, SpriteResizeError> {
+    if size == 0 || size > MAX_SPRITE_SIZE {
+        return Err(SpriteResizeError::InvalidSize(size));
+    }
+
+    let image = image::load_from_memory(&sprite.data)?;
+    if image.width() <= size && image.height() <= size {
+        return Ok(sprite);
+    }
+
+    let mut data = Vec::new();
+    image
+        .thumbnail(size, size)
+        .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)?;
+
+    Ok(Sprite {
+        content_type: ImageFormat::Png.to_mime_type().to_string(),
+        data,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use image::{GenericImageView, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn test_should_resize_sprite() {
+        let sprite = png(96, 48);
+
+        let resized = resize_sprite(sprite, 32).unwrap();
+
+        assert_eq!(resized.content_type, "image/png");
+        let image = image::load_from_memory(&resized.data).unwrap();
+        assert_eq!(image.dimensions(), (32, 16));
+    }
+
+    #[test]
+    fn test_should_not_upscale_sprite() {
+        let sprite = png(96, 96);
+
+        let resized = resize_sprite(sprite.clone(), 128).unwrap();
+
+        assert_eq!(resized, sprite);
+    }
+
+    #[test]
+    fn test_should_not_resize_sprite_to_invalid_size() {
+        assert!(matches!(
+            resize_sprite(png(96, 96), 0),
+            Err(SpriteResizeError::InvalidSize(0))
+        ));
+        assert!(matches!(
+            resize_sprite(png(96, 96), MAX_SPRITE_SIZE + 1),
+            Err(SpriteResizeError::InvalidSize(_))
+        ));
+    }
+
+    #[test]
+    fn test_should_round_sprite_size() {
+        assert_eq!(round_sprite_size(1), Some(16));
+        assert_eq!(round_sprite_size(32), Some(32));
+        assert_eq!(round_sprite_size(33), Some(48));
+        assert_eq!(round_sprite_size(257), Some(MAX_SPRITE_SIZE));
+        assert_eq!(round_sprite_size(MAX_SPRITE_SIZE), Some(MAX_SPRITE_SIZE));
+        assert_eq!(round_sprite_size(MAX_SPRITE_SIZE + 1), None);
+    }
+
+    #[test]
+    fn test_should_not_resize_invalid_image() {
+        let sprite = Sprite {
+            content_type: "image/png".to_string(),
+            data: b"not an image".to_vec(),
+        };
+
+        assert!(matches!(
+            resize_sprite(sprite, 32),
+            Err(SpriteResizeError::Image(_))
+        ));
+    }
+
+    fn png(width: u32, height: u32) -> Sprite {
+        let mut data = Vec::new();
+        RgbaImage::new(width, height)
+            .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)
+            .unwrap();
+
+        Sprite {
+            content_type: "image/png".to_string(),
+            data,
+        }
+    }
+}
@@ -1,6 +1,10 @@
 //! This module exposes the web server functionality of the application.
 
+pub mod error;
+mod openapi;
+mod rate_limit;
 mod routes;
+mod telemetry;
 #[cfg(test)]
 mod tests;
 
@@ -8,40 +12,85 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use axum::Router;
+use metrics_exporter_prometheus::PrometheusHandle;
 use tokio::net::TcpListener;
+use utoipa::OpenApi;
+use utoipa_redoc::{Redoc, Servable};
 
+use crate::adapters::battle::BattleAdapter;
+use crate::adapters::item::ItemAdapter;
 use crate::adapters::pokedex::PokedexAdapter;
 use crate::adapters::translation::TranslationAdapter;
+use crate::autocomplete::AutocompleteIndex;
+use crate::health::HealthChecker;
+use crate::log::LogController;
+use crate::mechanics::TypeChartCache;
+use crate::monitoring;
+use crate::quiz::QuizSessions;
+use crate::rate_limit::RateLimiter;
+use crate::search::SearchIndex;
+use crate::sprites::SpriteCache;
+use crate::web::openapi::ApiDoc;
 
-/// Application state holding the adapters for Pokedex and Translation services.
-pub struct AppState<P, T>
+/// Application state holding the adapters for Pokedex, Translation, Battle and Item services.
+pub struct AppState<P, T, B, I>
 where
     P: PokedexAdapter + Send + Sync + 'static,
     T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
 {
     /// the [`PokedexAdapter`] to use
     pub pokedex_adapter: P,
     /// the [`TranslationAdapter`] to use
     pub translation_adapter: T,
+    /// the [`BattleAdapter`] to use
+    pub battle_adapter: B,
+    /// the [`ItemAdapter`] to use
+    pub item_adapter: I,
+    /// the cache of the type charts of each generation
+    pub type_chart: TypeChartCache,
+    /// the disk cache of the Pokémon images
+    pub sprite_cache: SpriteCache,
+    /// the running quiz sessions
+    pub quiz_sessions: QuizSessions,
+    /// the full-text search index of the Pokémon species
+    pub search_index: SearchIndex,
+    /// the index of the Pokémon names for the autocompletion
+    pub autocomplete_index: AutocompleteIndex,
+    /// the checker of the dependencies, for the readiness probe
+    pub health_checker: HealthChecker,
+    /// the handle rendering the Prometheus metrics
+    pub metrics: PrometheusHandle,
+    /// the handle changing the level and the filter of the logs
+    pub log_controller: LogController,
+    /// the token authorizing the requests to the admin endpoints, which are disabled if not set
+    pub admin_token: Option<String>,
+    /// the rate limiter of the clients
+    pub rate_limiter: RateLimiter,
 }
 
 /// Represents the web server.
-pub struct WebServer<P, T>
+pub struct WebServer<P, T, B, I>
 where
     P: PokedexAdapter + Send + Sync + 'static,
     T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
 {
     /// Application state
-    app_state: AppState<P, T>,
+    app_state: AppState<P, T, B, I>,
 }
 
-impl<P, T> WebServer<P, T>
+impl<P, T, B, I> WebServer<P, T, B, I>
 where
     P: PokedexAdapter + Send + Sync + 'static,
     T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
 {
     /// Creates a new instance of the web server with the given application state.
-    pub fn new(app_state: AppState<P, T>) -> Self {
+    pub fn new(app_state: AppState<P, T, B, I>) -> Self {
         Self { app_state }
     }
 
@@ -52,18 +101,126 @@ where
         debug!("Web server listening on {listen_to}");
 
         let app_state = Arc::new(self.app_state);
-        let router = Router::new()
+        // the indexes are built in the background, so the server can start right away
+        let search_state = app_state.clone();
+        tokio::spawn(async move {
+            search_state
+                .search_index
+                .rebuild_periodically(&search_state.pokedex_adapter)
+                .await;
+        });
+
+        let autocomplete_state = app_state.clone();
+        tokio::spawn(async move {
+            autocomplete_state
+                .autocomplete_index
+                .refresh_periodically(&autocomplete_state.pokedex_adapter)
+                .await;
+        });
+
+        tokio::spawn(monitoring::run_upkeep_periodically(
+            app_state.metrics.clone(),
+        ));
+
+        let rate_limit_state = app_state.clone();
+        tokio::spawn(async move {
+            rate_limit_state.rate_limiter.clean_up_periodically().await;
+        });
+
+        let router = Self::router(app_state);
+
+        // the address of the peer identifies the client, for the rate limiting
+        axum::serve(
+            listener,
+            router.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(Self::shutdown_signal())
+        .await
+        .map_err(|e| anyhow::anyhow!("Web server error: {}", e))
+    }
+
+    /// Builds the [`Router`] with all the endpoints of the web server.
+    fn router(app_state: Arc<AppState<P, T, B, I>>) -> Router {
+        Router::new()
+            .route("/healthz", axum::routing::get(routes::get_liveness))
+            .route("/readyz", axum::routing::get(routes::get_readiness))
+            .route("/metrics", axum::routing::get(routes::get_metrics))
+            .route(
+                "/admin/log",
+                axum::routing::get(routes::get_log_config).put(routes::put_log_config),
+            )
             .route("/pokemon/{name}", axum::routing::get(routes::get_pokemon))
+            .route(
+                "/pokemon/random",
+                axum::routing::get(routes::get_random_pokemon),
+            )
+            .route(
+                "/pokemon/daily",
+                axum::routing::get(routes::get_daily_pokemon),
+            )
+            .route(
+                "/pokemon/batch",
+                axum::routing::post(routes::post_pokemon_batch),
+            )
             .route(
                 "/pokemon/translated/{name}",
                 axum::routing::get(routes::get_translated_pokemon),
             )
-            .with_state(app_state);
-
-        axum::serve(listener, router)
-            .with_graceful_shutdown(Self::shutdown_signal())
-            .await
-            .map_err(|e| anyhow::anyhow!("Web server error: {}", e))
+            .route("/compare", axum::routing::get(routes::get_comparison))
+            .route(
+                "/calc/damage",
+                axum::routing::post(routes::post_damage_calculation),
+            )
+            .route(
+                "/teams/analyze",
+                axum::routing::post(routes::post_team_analysis),
+            )
+            .route("/abilities/{name}", axum::routing::get(routes::get_ability))
+            .route("/items/{name}", axum::routing::get(routes::get_item))
+            .route("/berries/{name}", axum::routing::get(routes::get_berry))
+            .route("/moves/{name}", axum::routing::get(routes::get_move))
+            .route(
+                "/pokemon/{name}/weaknesses",
+                axum::routing::get(routes::get_pokemon_weaknesses),
+            )
+            .route(
+                "/pokemon/{name}/encounters",
+                axum::routing::get(routes::get_pokemon_encounters),
+            )
+            .route(
+                "/pokemon/{name}/sprite",
+                axum::routing::get(routes::get_pokemon_sprite),
+            )
+            .route(
+                "/generations/{id}",
+                axum::routing::get(routes::get_generation),
+            )
+            .route("/pokedexes/{name}", axum::routing::get(routes::get_pokedex))
+            .route("/quiz", axum::routing::post(routes::post_quiz))
+            .route(
+                "/quiz/{id}/guess",
+                axum::routing::post(routes::post_quiz_guess),
+            )
+            .route("/search", axum::routing::get(routes::get_search))
+            .route(
+                "/autocomplete",
+                axum::routing::get(routes::get_autocomplete),
+            )
+            .route("/types/{name}", axum::routing::get(routes::get_type))
+            .route(
+                "/types/effectiveness",
+                axum::routing::get(routes::get_type_effectiveness),
+            )
+            .route("/openapi.json", axum::routing::get(routes::get_openapi))
+            .merge(Redoc::with_url("/docs", ApiDoc::openapi()))
+            .layer(axum::middleware::from_fn_with_state(
+                app_state.clone(),
+                rate_limit::limit_requests,
+            ))
+            .layer(axum::middleware::from_fn(error::problem_details))
+            .layer(axum::middleware::from_fn(telemetry::track_requests))
+            .layer(axum::middleware::from_fn(telemetry::trace_requests))
+            .with_state(app_state)
     }
 
     /// Handles shutdown signals for graceful termination of the web server.
@@ -75,3 +232,17 @@ where
         info!("Shutting down web server");
     }
 }
+
+/// The offset basis of the FNV-1a 64 hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+/// The prime of the FNV-1a 64 hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Computes the FNV-1a 64 hash of the given data.
+///
+/// Unlike the hasher of the standard library, its algorithm is fixed, so the hashes are stable across builds.
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
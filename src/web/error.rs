@@ -0,0 +1,391 @@
+use std::fmt;
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::adapters::battle::BattleAdapterError;
+use crate::adapters::item::ItemAdapterError;
+use crate::adapters::pokedex::PokedexAdapterError;
+use crate::adapters::translation::TranslationAdapterError;
+use crate::log::LogControlError;
+use crate::model::{PROBLEM_CONTENT_TYPE, Problem};
+use crate::web::telemetry::RequestId;
+
+/// The problem type of the errors which are fully described by their status code.
+const BLANK_PROBLEM_TYPE: &str = "about:blank";
+/// Maximum size of the plain text error bodies converted into problem details.
+const MAX_ERROR_BODY_SIZE: usize = 64 * 1024;
+
+/// The external services the application depends on.
+const POKEAPI_SERVICE: &str = "PokeAPI";
+const FUNTRANSLATIONS_SERVICE: &str = "FunTranslations API";
+
+/// An error returned by the API.
+///
+/// It is rendered as `application/problem+json`; the request path and id are filled in
+/// by the [`problem_details`] middleware.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    status: StatusCode,
+    detail: String,
+    /// the seconds the client should wait before retrying, sent as `Retry-After` header
+    retry_after_secs: Option<u64>,
+}
+
+impl ApiError {
+    /// Creates a new [`ApiError`] with the given status and detail.
+    pub fn new(status: StatusCode, detail: impl Into<String>) -> Self {
+        Self {
+            status,
+            detail: detail.into(),
+            retry_after_secs: None,
+        }
+    }
+
+    /// Creates a `400 Bad Request` error.
+    pub fn bad_request(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, detail)
+    }
+
+    /// Creates a `404 Not Found` error.
+    pub fn not_found(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, detail)
+    }
+
+    /// Creates a `429 Too Many Requests` error, for a client to retry after the given seconds.
+    pub fn too_many_requests(retry_after_secs: u64) -> Self {
+        Self {
+            retry_after_secs: Some(retry_after_secs),
+            ..Self::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                format!("Too many requests; retry in {retry_after_secs} seconds"),
+            )
+        }
+    }
+
+    /// Creates a `500 Internal Server Error` error.
+    pub fn internal(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, detail)
+    }
+
+    /// Creates a `503 Service Unavailable` error.
+    pub fn service_unavailable(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::SERVICE_UNAVAILABLE, detail)
+    }
+
+    /// Returns the status code of the error.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Returns the detail of the error.
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    /// Maps a [`PokedexAdapterError`] into an error, reporting the given resource if not found.
+    pub fn pokedex(resource: &str, err: PokedexAdapterError) -> Self {
+        match err {
+            PokedexAdapterError::NotFound => Self::not_found(format!("{resource} not found")),
+            PokedexAdapterError::Timeout(_) => Self::upstream_timeout(POKEAPI_SERVICE, &err),
+            PokedexAdapterError::NetworkError(_)
+            | PokedexAdapterError::ParseError(_)
+            | PokedexAdapterError::UnexpectedResponse(_) => {
+                Self::bad_gateway(POKEAPI_SERVICE, &err)
+            }
+        }
+    }
+
+    /// Maps a [`BattleAdapterError`] into an error, reporting the given resource if not found.
+    pub fn battle(resource: &str, err: BattleAdapterError) -> Self {
+        match err {
+            BattleAdapterError::NotFound => Self::not_found(format!("{resource} not found")),
+            BattleAdapterError::Timeout(_) => Self::upstream_timeout(POKEAPI_SERVICE, &err),
+            BattleAdapterError::NetworkError(_)
+            | BattleAdapterError::ParseError(_)
+            | BattleAdapterError::UnexpectedResponse(_) => Self::bad_gateway(POKEAPI_SERVICE, &err),
+        }
+    }
+
+    /// Maps an [`ItemAdapterError`] into an error, reporting the given resource if not found.
+    pub fn item(resource: &str, err: ItemAdapterError) -> Self {
+        match err {
+            ItemAdapterError::NotFound => Self::not_found(format!("{resource} not found")),
+            ItemAdapterError::Timeout(_) => Self::upstream_timeout(POKEAPI_SERVICE, &err),
+            ItemAdapterError::NetworkError(_)
+            | ItemAdapterError::ParseError(_)
+            | ItemAdapterError::UnexpectedResponse(_) => Self::bad_gateway(POKEAPI_SERVICE, &err),
+        }
+    }
+
+    /// Creates a `502 Bad Gateway` error for a failure of the given upstream service.
+    ///
+    /// The upstream error is logged, but not exposed to the client.
+    fn bad_gateway(service: &str, err: &dyn fmt::Display) -> Self {
+        error!("{service} failure: {err}");
+        Self::new(
+            StatusCode::BAD_GATEWAY,
+            format!("The {service} could not be reached or returned an invalid response"),
+        )
+    }
+
+    /// Creates a `504 Gateway Timeout` error for a timeout of the given upstream service.
+    fn upstream_timeout(service: &str, err: &dyn fmt::Display) -> Self {
+        error!("{service} timeout: {err}");
+        Self::new(
+            StatusCode::GATEWAY_TIMEOUT,
+            format!("The {service} did not respond in time"),
+        )
+    }
+
+    /// Builds the [`Problem`] of the error.
+    fn problem(&self, instance: Option<String>, request_id: Option<String>) -> Problem {
+        Problem {
+            problem_type: BLANK_PROBLEM_TYPE.to_string(),
+            title: self
+                .status
+                .canonical_reason()
+                .unwrap_or("Unknown Error")
+                .to_string(),
+            status: self.status.as_u16(),
+            detail: self.detail.clone(),
+            instance,
+            request_id,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.status, self.detail)
+    }
+}
+
+impl From<PokedexAdapterError> for ApiError {
+    fn from(err: PokedexAdapterError) -> Self {
+        Self::pokedex("Pokemon", err)
+    }
+}
+
+impl From<TranslationAdapterError> for ApiError {
+    fn from(err: TranslationAdapterError) -> Self {
+        match err {
+            TranslationAdapterError::Timeout(_) => {
+                Self::upstream_timeout(FUNTRANSLATIONS_SERVICE, &err)
+            }
+            TranslationAdapterError::NetworkError(_)
+            | TranslationAdapterError::ParseError(_)
+            | TranslationAdapterError::UnknownError(_)
+            | TranslationAdapterError::UnexpectedResponse(_) => {
+                Self::bad_gateway(FUNTRANSLATIONS_SERVICE, &err)
+            }
+        }
+    }
+}
+
+impl From<BattleAdapterError> for ApiError {
+    fn from(err: BattleAdapterError) -> Self {
+        Self::battle("Resource", err)
+    }
+}
+
+impl From<ItemAdapterError> for ApiError {
+    fn from(err: ItemAdapterError) -> Self {
+        Self::item("Resource", err)
+    }
+}
+
+impl From<LogControlError> for ApiError {
+    fn from(err: LogControlError) -> Self {
+        match err {
+            LogControlError::InvalidFilter(_) => Self::bad_request(err.to_string()),
+            LogControlError::Reload(_) => Self::internal(err.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut response = problem_response(self.status, &self.problem(None, None));
+        if let Some(retry_after_secs) = self.retry_after_secs {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        }
+        // keep the error around, so that the middleware can fill in the request details
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+/// Middleware rendering all the error responses as problem details, filled in with the request path and id.
+///
+/// Errors which are not [`ApiError`]s, such as the rejections of the extractors, are converted too,
+/// using their plain text body as detail.
+/// The request id is the one assigned by the [`trace_requests`](super::telemetry::trace_requests) middleware.
+pub async fn problem_details(request: Request, next: Next) -> Response {
+    let instance = request.uri().path().to_string();
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(RequestId::to_string);
+
+    let mut response = next.run(request).await;
+    let error = match response.extensions().get::<ApiError>() {
+        Some(error) => error.clone(),
+        None if is_plain_error(&response) => {
+            let (parts, body) = response.into_parts();
+            let detail = axum::body::to_bytes(body, MAX_ERROR_BODY_SIZE)
+                .await
+                .map(|body| String::from_utf8_lossy(&body).trim().to_string())
+                .unwrap_or_default();
+            response = Response::from_parts(parts, Body::empty());
+            let detail = if detail.is_empty() {
+                response
+                    .status()
+                    .canonical_reason()
+                    .unwrap_or_default()
+                    .to_string()
+            } else {
+                detail
+            };
+            ApiError::new(response.status(), detail)
+        }
+        None => return response,
+    };
+
+    // keep the headers of the original response (e.g. `Allow`), replacing the body
+    let (mut parts, _) = response.into_parts();
+    let problem = problem_response(error.status, &error.problem(Some(instance), request_id));
+    let (problem_parts, body) = problem.into_parts();
+    parts.headers.extend(problem_parts.headers);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    // keep the error around, so that it is logged with the response
+    parts.extensions.insert(error);
+    Response::from_parts(parts, body)
+}
+
+/// Returns whether the response is an error with a plain body, such as the rejections of the extractors.
+///
+/// Errors with a JSON body (e.g. the readiness probe) are left as they are.
+fn is_plain_error(response: &Response) -> bool {
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.starts_with("application/json"));
+
+    (response.status().is_client_error() || response.status().is_server_error()) && !is_json
+}
+
+/// Renders the [`Problem`] with the given status.
+fn problem_response(status: StatusCode, problem: &Problem) -> Response {
+    let body = serde_json::to_vec(problem).expect("problem is always serializable");
+    (status, [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)], body).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_map_pokedex_errors() {
+        assert_eq!(
+            ApiError::pokedex("Pokedex", PokedexAdapterError::NotFound),
+            ApiError::not_found("Pokedex not found")
+        );
+        assert_eq!(
+            ApiError::from(PokedexAdapterError::NotFound),
+            ApiError::not_found("Pokemon not found")
+        );
+        assert_eq!(
+            ApiError::from(PokedexAdapterError::Timeout("timed out".to_string())).status(),
+            StatusCode::GATEWAY_TIMEOUT
+        );
+        let err = ApiError::from(PokedexAdapterError::NetworkError(
+            "error sending request for url (https://pokeapi.co/api/v2/pokemon/pikachu)".to_string(),
+        ));
+        assert_eq!(err.status(), StatusCode::BAD_GATEWAY);
+        assert!(!err.detail().contains("pokeapi.co"));
+        assert_eq!(
+            ApiError::from(PokedexAdapterError::UnexpectedResponse(
+                "HTTP 500".to_string()
+            ))
+            .status(),
+            StatusCode::BAD_GATEWAY
+        );
+    }
+
+    #[test]
+    fn test_should_map_translation_errors() {
+        assert_eq!(
+            ApiError::from(TranslationAdapterError::Timeout("timed out".to_string())).status(),
+            StatusCode::GATEWAY_TIMEOUT
+        );
+        assert_eq!(
+            ApiError::from(TranslationAdapterError::NetworkError("refused".to_string())).status(),
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(
+            ApiError::from(TranslationAdapterError::UnexpectedResponse(
+                "HTTP 429".to_string()
+            ))
+            .status(),
+            StatusCode::BAD_GATEWAY
+        );
+    }
+
+    #[test]
+    fn test_should_map_battle_and_item_errors() {
+        assert_eq!(
+            ApiError::battle("Move", BattleAdapterError::NotFound),
+            ApiError::not_found("Move not found")
+        );
+        assert_eq!(
+            ApiError::from(BattleAdapterError::Timeout("timed out".to_string())).status(),
+            StatusCode::GATEWAY_TIMEOUT
+        );
+        assert_eq!(
+            ApiError::item("Berry", ItemAdapterError::NotFound),
+            ApiError::not_found("Berry not found")
+        );
+        assert_eq!(
+            ApiError::from(ItemAdapterError::ParseError("invalid json".to_string())).status(),
+            StatusCode::BAD_GATEWAY
+        );
+    }
+
+    #[test]
+    fn test_should_build_problem() {
+        let problem = ApiError::not_found("Pokemon not found").problem(
+            Some("/pokemon/missingno".to_string()),
+            Some("abc".to_string()),
+        );
+
+        assert_eq!(
+            problem,
+            Problem {
+                problem_type: "about:blank".to_string(),
+                title: "Not Found".to_string(),
+                status: 404,
+                detail: "Pokemon not found".to_string(),
+                instance: Some("/pokemon/missingno".to_string()),
+                request_id: Some("abc".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_should_send_retry_after_header() {
+        let response = ApiError::too_many_requests(12).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "12");
+
+        let response = ApiError::not_found("Pokemon not found").into_response();
+        assert!(response.headers().get(header::RETRY_AFTER).is_none());
+    }
+}
@@ -0,0 +1,170 @@
+//! OpenAPI specification of the web server, generated from the route handlers and the models.
+
+use utoipa::openapi::content::ContentBuilder;
+use utoipa::openapi::header::HeaderBuilder;
+use utoipa::openapi::response::ResponseBuilder;
+use utoipa::openapi::schema::{ObjectBuilder, Type};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{Ref, RefOr};
+use utoipa::{Modify, OpenApi};
+
+use crate::model::PROBLEM_CONTENT_TYPE;
+use crate::web::rate_limit::UNLIMITED_ROUTES;
+use crate::web::routes;
+
+/// The OpenAPI specification of the API.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "pokedex-api", description = "A RESTful API web service for accessing Pokémon data."),
+    paths(
+        routes::get_liveness,
+        routes::get_readiness,
+        routes::get_metrics,
+        routes::get_log_config,
+        routes::put_log_config,
+        routes::get_pokemon,
+        routes::get_random_pokemon,
+        routes::get_daily_pokemon,
+        routes::post_pokemon_batch,
+        routes::get_translated_pokemon,
+        routes::get_comparison,
+        routes::post_damage_calculation,
+        routes::post_team_analysis,
+        routes::get_ability,
+        routes::get_item,
+        routes::get_berry,
+        routes::get_move,
+        routes::get_pokemon_weaknesses,
+        routes::get_pokemon_encounters,
+        routes::get_pokemon_sprite,
+        routes::get_generation,
+        routes::get_pokedex,
+        routes::post_quiz,
+        routes::post_quiz_guess,
+        routes::get_search,
+        routes::get_autocomplete,
+        routes::get_type,
+        routes::get_type_effectiveness,
+    ),
+    modifiers(&AdminTokenSecurity, &RateLimitResponses),
+    tags(
+        (name = "health", description = "Probes and metrics"),
+        (name = "admin", description = "Runtime configuration, authorized by the admin token"),
+        (name = "pokemon", description = "Pokémon data"),
+        (name = "battle", description = "Abilities, moves, types and battle mechanics"),
+        (name = "items", description = "Items and berries"),
+        (name = "pokedex", description = "Generations and pokedexes"),
+        (name = "quiz", description = "Who's that Pokémon?"),
+        (name = "search", description = "Full-text search and autocompletion"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Declares the bearer token authorizing the admin endpoints.
+struct AdminTokenSecurity;
+
+impl Modify for AdminTokenSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "admin_token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+    }
+}
+
+/// Declares the `429 Too Many Requests` response of the rate limited routes.
+struct RateLimitResponses;
+
+impl Modify for RateLimitResponses {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let response = ResponseBuilder::new()
+            .description("Too many requests from the client")
+            .content(
+                PROBLEM_CONTENT_TYPE,
+                ContentBuilder::new()
+                    .schema(Some(Ref::from_schema_name("Problem")))
+                    .build(),
+            )
+            .header(
+                "Retry-After",
+                HeaderBuilder::new()
+                    .schema(ObjectBuilder::new().schema_type(Type::Integer))
+                    .description(Some("The seconds to wait before retrying"))
+                    .build(),
+            )
+            .build();
+
+        for (path, item) in openapi.paths.paths.iter_mut() {
+            if UNLIMITED_ROUTES.contains(&path.as_str()) {
+                continue;
+            }
+            for operation in [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                operation
+                    .responses
+                    .responses
+                    .insert("429".to_string(), RefOr::T(response.clone()));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::path::Path;
+
+    use super::*;
+
+    /// The committed specification, which clients generate their models from.
+    const SPEC_PATH: &str = "openapi.json";
+
+    #[test]
+    fn test_should_match_committed_spec() {
+        let generated = ApiDoc::openapi()
+            .to_pretty_json()
+            .expect("Failed to serialize the spec");
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SPEC_PATH);
+        // set UPDATE_OPENAPI to regenerate the committed spec
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(&path, format!("{generated}\n")).expect("Failed to write the spec");
+        }
+
+        let committed = std::fs::read_to_string(&path).expect("Failed to read the spec");
+        assert_eq!(
+            committed.trim_end(),
+            generated,
+            "{SPEC_PATH} is out of date; run `UPDATE_OPENAPI=1 cargo test` to regenerate it"
+        );
+    }
+
+    #[test]
+    fn test_should_generate_openapi_3_1() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).expect("Failed to serialize the spec");
+
+        assert_eq!(spec["openapi"], "3.1.0");
+        assert!(spec["paths"]["/pokemon/{name}"]["get"].is_object());
+        assert!(spec["components"]["schemas"]["Pokemon"].is_object());
+        assert!(spec["components"]["schemas"]["Problem"].is_object());
+    }
+
+    #[test]
+    fn test_should_declare_rate_limit_responses() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).expect("Failed to serialize the spec");
+
+        assert!(spec["paths"]["/pokemon/translated/{name}"]["get"]["responses"]["429"].is_object());
+        assert!(spec["paths"]["/quiz"]["post"]["responses"]["429"].is_object());
+        assert!(spec["paths"]["/healthz"]["get"]["responses"]["429"].is_null());
+    }
+}
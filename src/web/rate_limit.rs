@@ -0,0 +1,197 @@
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::adapters::battle::BattleAdapter;
+use crate::adapters::item::ItemAdapter;
+use crate::adapters::pokedex::PokedexAdapter;
+use crate::adapters::translation::TranslationAdapter;
+use crate::monitoring;
+use crate::rate_limit::{RateLimitDecision, RateLimitScope, RateLimiter};
+use crate::web::error::ApiError;
+use crate::web::{AppState, fnv1a};
+
+/// The header carrying the amount of requests allowed at once.
+const RATE_LIMIT_LIMIT_HEADER: HeaderName = HeaderName::from_static("ratelimit-limit");
+/// The header carrying the amount of requests left.
+const RATE_LIMIT_REMAINING_HEADER: HeaderName = HeaderName::from_static("ratelimit-remaining");
+/// The header carrying the seconds until all the requests are available again.
+const RATE_LIMIT_RESET_HEADER: HeaderName = HeaderName::from_static("ratelimit-reset");
+/// The prefix of the routes translating the description of a Pokémon, charged as a translation.
+const TRANSLATED_ROUTES_PREFIX: &str = "/pokemon/translated/";
+/// The routes which are never limited, so that the probes and the scrapes don't fail under load.
+pub const UNLIMITED_ROUTES: [&str; 3] = ["/healthz", "/readyz", "/metrics"];
+/// The prefix of the keys of the clients identified by their API key.
+const API_KEY_CLIENT_PREFIX: &str = "key:";
+
+/// The key identifying the client of a request to the rate limiter.
+///
+/// It is available to the handlers as a request extension, to [charge the translations](charge_translations).
+/// When displayed, as in the logs, the API keys are replaced by their hash so they are not disclosed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitClient(String);
+
+impl RateLimitClient {
+    /// Returns the key as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RateLimitClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.strip_prefix(API_KEY_CLIENT_PREFIX) {
+            Some(api_key) => write!(
+                f,
+                "{API_KEY_CLIENT_PREFIX}#{:016x}",
+                fnv1a(api_key.as_bytes())
+            ),
+            None => f.write_str(&self.0),
+        }
+    }
+}
+
+/// Middleware rate limiting the requests of each client, rejecting them with `429 Too Many Requests` once over the limit.
+///
+/// The limited responses carry the `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` headers,
+/// and the rejections a `Retry-After` header too.
+pub async fn limit_requests<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    mut request: Request,
+    next: Next,
+) -> Response
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let Some(scope) = scope(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip());
+    let client = RateLimitClient(state.rate_limiter.client_key(request.headers(), peer));
+    let Some(decision) = state.rate_limiter.check(scope, client.as_str()) else {
+        request.extensions_mut().insert(client);
+        return next.run(request).await;
+    };
+
+    let mut response = match decision.retry_after {
+        None => {
+            request.extensions_mut().insert(client);
+            next.run(request).await
+        }
+        Some(retry_after) => reject(scope, &client, retry_after).into_response(),
+    };
+    insert_rate_limit_headers(response.headers_mut(), &decision);
+
+    response
+}
+
+/// Charges the client for the given amount of translations, rejecting the request once over the translated limit.
+///
+/// This is for the routes translating on demand, e.g. a batch with the `translated` option takes a token for each name;
+/// the `/pokemon/translated/*` routes are charged by the [`limit_requests`] middleware instead.
+pub fn charge_translations(
+    rate_limiter: &RateLimiter,
+    client: &RateLimitClient,
+    translations: usize,
+) -> Result<(), ApiError> {
+    let translations = u32::try_from(translations).unwrap_or(u32::MAX);
+    let Some(decision) =
+        rate_limiter.check_n(RateLimitScope::Translated, client.as_str(), translations)
+    else {
+        return Ok(());
+    };
+
+    match decision.retry_after {
+        None => Ok(()),
+        // the bucket never holds that many tokens, so retrying won't help
+        Some(_) if translations > decision.limit => Err(ApiError::bad_request(format!(
+            "At most {} descriptions can be translated at once",
+            decision.limit
+        ))),
+        Some(retry_after) => Err(reject(RateLimitScope::Translated, client, retry_after)),
+    }
+}
+
+/// Rejects the request of the client over the limit of the given scope.
+fn reject(scope: RateLimitScope, client: &RateLimitClient, retry_after: Duration) -> ApiError {
+    debug!("rate limited client {client} for the {scope} routes");
+    monitoring::record_rate_limited(scope.to_string());
+    ApiError::too_many_requests(seconds(retry_after).max(1))
+}
+
+/// Returns the scope of the requests to the given path, or `None` if they are not limited.
+fn scope(path: &str) -> Option<RateLimitScope> {
+    if UNLIMITED_ROUTES.contains(&path) {
+        None
+    } else if path.starts_with(TRANSLATED_ROUTES_PREFIX) {
+        Some(RateLimitScope::Translated)
+    } else {
+        Some(RateLimitScope::Default)
+    }
+}
+
+/// Inserts the `RateLimit-*` headers describing the decision.
+fn insert_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(decision.limit));
+    headers.insert(
+        RATE_LIMIT_REMAINING_HEADER,
+        HeaderValue::from(decision.remaining),
+    );
+    headers.insert(
+        RATE_LIMIT_RESET_HEADER,
+        HeaderValue::from(seconds(decision.reset)),
+    );
+}
+
+/// Returns the duration in whole seconds, rounded up.
+fn seconds(duration: Duration) -> u64 {
+    duration.as_secs_f64().ceil() as u64
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_scope_routes() {
+        assert_eq!(
+            scope("/pokemon/translated/mewtwo"),
+            Some(RateLimitScope::Translated)
+        );
+        assert_eq!(scope("/pokemon/mewtwo"), Some(RateLimitScope::Default));
+        assert_eq!(scope("/unknown"), Some(RateLimitScope::Default));
+        assert_eq!(scope("/healthz"), None);
+        assert_eq!(scope("/readyz"), None);
+        assert_eq!(scope("/metrics"), None);
+    }
+
+    #[test]
+    fn test_should_not_display_api_keys() {
+        let client = RateLimitClient("key:ash".to_string());
+        assert_eq!(client.to_string(), format!("key:#{:016x}", fnv1a(b"ash")));
+        assert!(!client.to_string().contains("ash"));
+
+        let client = RateLimitClient("ip:127.0.0.1".to_string());
+        assert_eq!(client.to_string(), "ip:127.0.0.1");
+    }
+
+    #[test]
+    fn test_should_round_seconds_up() {
+        assert_eq!(seconds(Duration::ZERO), 0);
+        assert_eq!(seconds(Duration::from_millis(1)), 1);
+        assert_eq!(seconds(Duration::from_secs(60)), 60);
+    }
+}
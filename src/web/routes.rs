@@ -1,58 +1,385 @@
+use std::collections::HashSet;
+use std::str::FromStr;
 use std::sync::Arc;
 
-use axum::Json;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use chrono::{Datelike, NaiveDate, Utc};
+use futures::StreamExt;
+use rand::seq::{IndexedRandom, SliceRandom};
+use serde::Deserialize;
+use subtle::ConstantTimeEq;
+use tracing::level_filters::LevelFilter;
+use utoipa::{IntoParams, OpenApi};
 
+use crate::adapters::battle::BattleAdapter;
+use crate::adapters::item::ItemAdapter;
 use crate::adapters::pokedex::{PokedexAdapter, PokedexAdapterError};
 use crate::adapters::translation::TranslationAdapter;
-use crate::model::Pokemon;
-use crate::web::AppState;
+use crate::log::LogSettings;
+use crate::mechanics::{
+    LATEST_GENERATION, MAX_TEAM_SIZE, TYPES, analyze_team, calculate_damage, compare_pokemon,
+};
+use crate::model::{
+    Ability, BatchError, BatchRequest, BatchResponse, BatchResult, Berry, DamageCalculation,
+    DamageRequest, Generation, HealthStatus, Item, Liveness, LogConfig, Move, Pokedex,
+    PokedexEntry, Pokemon, PokemonComparison, PokemonEncounters, PokemonType, PokemonWeaknesses,
+    Problem, Quiz, QuizGuess, QuizGuessResult, QuizRequest, Readiness, SearchResults, Sprite,
+    SpriteStream, SpriteVariant, Suggestions, TeamAnalysis, TeamMember, TeamMemberError,
+    TeamRequest, TypeEffectiveness,
+};
+use crate::monitoring;
+use crate::quiz::redact_name;
+use crate::sprites::{MAX_SPRITE_SIZE, resize_sprite, round_sprite_size};
+use crate::web::error::ApiError;
+use crate::web::openapi::ApiDoc;
+use crate::web::rate_limit::{RateLimitClient, charge_translations};
+use crate::web::{AppState, fnv1a};
+
+/// GET /healthz endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "health",
+    summary = "Check that the process is alive",
+    responses(
+        (status = 200, description = "The process is alive", body = Liveness),
+    )
+)]
+pub async fn get_liveness() -> Json<Liveness> {
+    Json(Liveness {
+        status: HealthStatus::Up,
+    })
+}
+
+/// GET /readyz endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    summary = "Check that the application can serve requests",
+    responses(
+        (status = 200, description = "The application is ready", body = Readiness),
+        (status = 503, description = "A required dependency is down", body = Readiness),
+    )
+)]
+pub async fn get_readiness<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> (StatusCode, Json<Readiness>)
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let readiness = state
+        .health_checker
+        .readiness(
+            &state.pokedex_adapter,
+            &state.translation_adapter,
+            &state.sprite_cache,
+            &state.search_index,
+            &state.autocomplete_index,
+        )
+        .await;
+    // probes are frequent, so they are only logged at debug level, as their access log
+    debug!("GET /readyz ready: {}", readiness.ready);
+
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness))
+}
+
+/// The content type of the Prometheus text format.
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// GET /metrics endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    summary = "Get the metrics in the Prometheus text format",
+    responses(
+        (status = 200, description = "The metrics", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn get_metrics<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> impl IntoResponse
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    (
+        [(header::CONTENT_TYPE, METRICS_CONTENT_TYPE)],
+        state.metrics.render(),
+    )
+}
+
+/// GET /admin/log endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/admin/log",
+    tag = "admin",
+    summary = "Get the level and the filter of the logs",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "The level and the filter of the logs", body = LogConfig),
+        (status = 401, description = "Missing or invalid admin token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The admin endpoints are disabled", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_log_config<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    headers: HeaderMap,
+) -> Result<Json<LogConfig>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    authorize_admin(&state, &headers)?;
+
+    Ok(Json(log_config(state.log_controller.settings())))
+}
+
+/// PUT /admin/log endpoint handler.
+#[utoipa::path(
+    put,
+    path = "/admin/log",
+    tag = "admin",
+    summary = "Change the level and the filter of the logs",
+    security(("admin_token" = [])),
+    request_body = LogConfig,
+    responses(
+        (status = 200, description = "The new level and filter of the logs", body = LogConfig),
+        (status = 400, description = "Invalid level or filter", body = Problem, content_type = "application/problem+json"),
+        (status = 401, description = "Missing or invalid admin token", body = Problem, content_type = "application/problem+json"),
+        (status = 403, description = "The admin endpoints are disabled", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn put_log_config<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    headers: HeaderMap,
+    Json(config): Json<LogConfig>,
+) -> Result<Json<LogConfig>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    authorize_admin(&state, &headers)?;
+
+    let level = LevelFilter::from_str(&config.level)
+        .map_err(|_| ApiError::bad_request(format!("Invalid log level: {}", config.level)))?;
+    let filter = config.filter.filter(|filter| !filter.trim().is_empty());
+    state.log_controller.update(LogSettings { level, filter })?;
+
+    Ok(Json(log_config(state.log_controller.settings())))
+}
+
+/// Converts the [`LogSettings`] into their [`LogConfig`] representation.
+fn log_config(settings: LogSettings) -> LogConfig {
+    LogConfig {
+        level: settings.level.to_string().to_lowercase(),
+        filter: settings.filter,
+    }
+}
+
+/// Checks that the request carries the admin token as bearer token.
+fn authorize_admin<P, T, B, I>(
+    state: &AppState<P, T, B, I>,
+    headers: &HeaderMap,
+) -> Result<(), ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let Some(admin_token) = state.admin_token.as_deref() else {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "The admin endpoints are disabled",
+        ));
+    };
+
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    match token {
+        // compared in constant time, so the token can't be guessed from the response times
+        Some(token) if bool::from(token.as_bytes().ct_eq(admin_token.as_bytes())) => Ok(()),
+        _ => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid admin token",
+        )),
+    }
+}
 
 /// GET /pokemon/{name} endpoint handler.
-pub async fn get_pokemon<P, T>(
+#[utoipa::path(
+    get,
+    path = "/pokemon/{name}",
+    tag = "pokemon",
+    summary = "Get a Pokémon",
+    params(
+        ("name" = String, Path, description = "The name of the Pokémon"),
+    ),
+    responses(
+        (status = 200, description = "The Pokémon", body = Pokemon),
+        (status = 404, description = "Pokémon not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_pokemon<P, T, B, I>(
     Path(name): Path<String>,
-    State(state): State<Arc<AppState<P, T>>>,
-) -> Result<Json<Pokemon>, (StatusCode, String)>
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Pokemon>, ApiError>
 where
     P: PokedexAdapter + Send + Sync + 'static,
     T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
 {
-    info!("GET /pokemon/{name}");
+    state
+        .pokedex_adapter
+        .fetch_pokemon_by_name(&name)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Maximum amount of names of a bulk lookup.
+const MAX_BATCH_SIZE: usize = 100;
+/// Maximum amount of Pokémon fetched concurrently by a bulk lookup.
+const BATCH_CONCURRENCY: usize = 8;
+
+/// POST /pokemon/batch endpoint handler.
+#[utoipa::path(
+    post,
+    path = "/pokemon/batch",
+    tag = "pokemon",
+    summary = "Get many Pokémon at once",
+    request_body = BatchRequest,
+    responses(
+        (status = 200, description = "The Pokémon, or the error of each name", body = BatchResponse),
+        (status = 400, description = "Invalid request or amount of names, or more translations than allowed at once", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn post_pokemon_batch<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    Extension(client): Extension<RateLimitClient>,
+    Json(request): Json<BatchRequest>,
+) -> Result<Json<BatchResponse>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    if request.names.is_empty() || request.names.len() > MAX_BATCH_SIZE {
+        return Err(ApiError::bad_request(format!(
+            "A batch must have between 1 and {MAX_BATCH_SIZE} names"
+        )));
+    }
+    if request.translated {
+        charge_translations(&state.rate_limiter, &client, request.names.len())?;
+    }
 
-    let response = match state.pokedex_adapter.fetch_pokemon_by_name(&name).await {
-        Ok(pokemon) => Ok(Json(pokemon)),
-        Err(PokedexAdapterError::NotFound) => {
-            Err((StatusCode::NOT_FOUND, "Pokemon not found".to_string()))
+    // fetch the pokemon concurrently, a few at a time; failures are reported per name
+    let permits = tokio::sync::Semaphore::new(BATCH_CONCURRENCY);
+    let results = futures::future::join_all(request.names.into_iter().map(|name| async {
+        let _permit = permits.acquire().await.expect("semaphore is never closed");
+        match state.pokedex_adapter.fetch_pokemon_by_name(&name).await {
+            Ok(pokemon) if request.translated => BatchResult {
+                name,
+                pokemon: Some(translate_pokemon(&state, pokemon).await),
+                error: None,
+            },
+            Ok(pokemon) => BatchResult {
+                name,
+                pokemon: Some(pokemon),
+                error: None,
+            },
+            Err(err) => {
+                // upstream failures are already logged when mapped into an error
+                let err = ApiError::from(err);
+                if err.status().is_client_error() {
+                    debug!("failed to fetch {name} in batch: {err}");
+                }
+                BatchResult {
+                    name,
+                    pokemon: None,
+                    error: Some(BatchError {
+                        status: err.status().as_u16(),
+                        message: err.detail().to_string(),
+                    }),
+                }
+            }
         }
-        Err(err) => Err((StatusCode::INTERNAL_SERVER_ERROR, err.to_string())),
-    };
-    log_response("GET", format!("/pokemon/{name}"), response)
+    }))
+    .await;
+
+    Ok(Json(BatchResponse { results }))
 }
 
 /// GET /pokemon/translated/{name} endpoint handler.
-pub async fn get_translated_pokemon<P, T>(
-    State(state): State<Arc<AppState<P, T>>>,
+#[utoipa::path(
+    get,
+    path = "/pokemon/translated/{name}",
+    tag = "pokemon",
+    summary = "Get a Pokémon with its description translated",
+    params(
+        ("name" = String, Path, description = "The name of the Pokémon"),
+    ),
+    responses(
+        (status = 200, description = "The Pokémon with the translated description", body = Pokemon),
+        (status = 404, description = "Pokémon not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_translated_pokemon<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
     Path(name): Path<String>,
-) -> Result<Json<Pokemon>, (StatusCode, String)>
+) -> Result<Json<Pokemon>, ApiError>
 where
     P: PokedexAdapter + Send + Sync + 'static,
     T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
 {
-    info!("GET /pokemon/translated/{name}");
+    match state.pokedex_adapter.fetch_pokemon_by_name(&name).await {
+        Ok(pokemon) => Ok(Json(translate_pokemon(&state, pokemon).await)),
+        Err(err) => Err(ApiError::from(err)),
+    }
+}
 
-    let pokemon = state
-        .pokedex_adapter
-        .fetch_pokemon_by_name(&name)
-        .await
-        .map_err(|err| {
-            let status_code = match err {
-                PokedexAdapterError::NotFound => StatusCode::NOT_FOUND,
-                _ => StatusCode::INTERNAL_SERVER_ERROR,
-            };
-            error!("GET /pokemon/translated/{name} {status_code}: {err}");
-            (status_code, err.to_string())
-        })?;
+/// Translates the description of the [`Pokemon`] into Yoda style if it is legendary or lives in a cave,
+/// otherwise into Shakespearean style.
+///
+/// If the translation fails, the [`Pokemon`] is returned with its original description.
+async fn translate_pokemon<P, T, B, I>(state: &AppState<P, T, B, I>, pokemon: Pokemon) -> Pokemon
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let name = &pokemon.name;
 
     // Translate the description based on whether the pokemon is legendary or from a cave habitat
     let translation_response = if pokemon.is_legendary
@@ -74,33 +401,1332 @@ where
             .await
     };
 
-    let response = match translation_response {
+    match translation_response {
         Ok(translated_description) => {
-            let translated_pokemon = Pokemon {
+            debug!("Translated description of {name} successfully");
+            Pokemon {
                 description: translated_description,
                 ..pokemon
-            };
-            debug!("Translated description of {name} successfully");
-            Ok(Json(translated_pokemon))
+            }
         }
         Err(err) => {
             error!("failed to get translation for {name}: {err}");
+            monitoring::record_translation_fallback();
             // we return the original pokemon data if translation fails
-            Ok(Json(pokemon))
+            pokemon
+        }
+    }
+}
+
+/// GET /abilities/{name} endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/abilities/{name}",
+    tag = "battle",
+    summary = "Get an ability",
+    params(
+        ("name" = String, Path, description = "The name of the ability"),
+    ),
+    responses(
+        (status = 200, description = "The ability", body = Ability),
+        (status = 404, description = "Ability not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_ability<P, T, B, I>(
+    Path(name): Path<String>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Ability>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .battle_adapter
+        .fetch_ability_by_name(&name)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::battle("Ability", err))
+}
+
+/// GET /items/{name} endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/items/{name}",
+    tag = "items",
+    summary = "Get an item",
+    params(
+        ("name" = String, Path, description = "The name of the item"),
+    ),
+    responses(
+        (status = 200, description = "The item", body = Item),
+        (status = 404, description = "Item not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_item<P, T, B, I>(
+    Path(name): Path<String>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Item>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .item_adapter
+        .fetch_item_by_name(&name)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::item("Item", err))
+}
+
+/// GET /berries/{name} endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/berries/{name}",
+    tag = "items",
+    summary = "Get a berry",
+    params(
+        ("name" = String, Path, description = "The name of the berry"),
+    ),
+    responses(
+        (status = 200, description = "The berry", body = Berry),
+        (status = 404, description = "Berry not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_berry<P, T, B, I>(
+    Path(name): Path<String>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Berry>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .item_adapter
+        .fetch_berry_by_name(&name)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::item("Berry", err))
+}
+
+/// GET /moves/{name} endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/moves/{name}",
+    tag = "battle",
+    summary = "Get a move",
+    params(
+        ("name" = String, Path, description = "The name of the move"),
+    ),
+    responses(
+        (status = 200, description = "The move", body = Move),
+        (status = 404, description = "Move not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_move<P, T, B, I>(
+    Path(name): Path<String>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Move>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .battle_adapter
+        .fetch_move_by_name(&name)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::battle("Move", err))
+}
+
+/// GET /types/{name} endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/types/{name}",
+    tag = "battle",
+    summary = "Get a type",
+    params(
+        ("name" = String, Path, description = "The name of the type"),
+    ),
+    responses(
+        (status = 200, description = "The type", body = PokemonType),
+        (status = 404, description = "Type not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_type<P, T, B, I>(
+    Path(name): Path<String>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<PokemonType>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .battle_adapter
+        .fetch_type_by_name(&name)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::battle("Type", err))
+}
+
+/// Query parameters of the GET /types/effectiveness endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TypeEffectivenessQuery {
+    /// The name of the attacking type.
+    attacker: String,
+    /// Comma separated names of the defending types.
+    defender: String,
+    /// The generation whose type chart should be used [default: latest].
+    generation: Option<u8>,
+}
+
+/// GET /types/effectiveness endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/types/effectiveness",
+    tag = "battle",
+    summary = "Get the effectiveness of a type against other types",
+    params(
+        TypeEffectivenessQuery,
+    ),
+    responses(
+        (status = 200, description = "The damage multiplier", body = TypeEffectiveness),
+        (status = 400, description = "Invalid types or generation", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Type not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_type_effectiveness<P, T, B, I>(
+    Query(query): Query<TypeEffectivenessQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<TypeEffectiveness>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let generation = validate_generation(query.generation)?;
+    let defender: Vec<String> = query
+        .defender
+        .split(',')
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect();
+    if defender.is_empty() {
+        return Err(ApiError::bad_request(
+            "At least one defender type is required",
+        ));
+    }
+    if let Some(unknown) = defender.iter().find(|name| !TYPES.contains(&name.as_str())) {
+        return Err(ApiError::bad_request(format!("Unknown type '{unknown}'")));
+    }
+
+    let multiplier = state
+        .type_chart
+        .effectiveness(
+            &state.battle_adapter,
+            generation,
+            &query.attacker,
+            &defender,
+        )
+        .await
+        .map_err(|err| ApiError::battle("Type", err))?;
+
+    Ok(Json(TypeEffectiveness {
+        attacker: query.attacker,
+        defender,
+        generation,
+        multiplier,
+    }))
+}
+
+/// Query parameters of the endpoints which depend on the type chart.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GenerationQuery {
+    /// The generation whose type chart should be used [default: latest].
+    generation: Option<u8>,
+}
+
+/// GET /pokemon/{name}/weaknesses endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/pokemon/{name}/weaknesses",
+    tag = "pokemon",
+    summary = "Get the weaknesses and resistances of a Pokémon",
+    params(
+        ("name" = String, Path, description = "The name of the Pokémon"),
+        GenerationQuery,
+    ),
+    responses(
+        (status = 200, description = "The type matchups of the Pokémon", body = PokemonWeaknesses),
+        (status = 400, description = "Invalid generation", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Pokémon or type not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_pokemon_weaknesses<P, T, B, I>(
+    Path(name): Path<String>,
+    Query(query): Query<GenerationQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<PokemonWeaknesses>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let generation = validate_generation(query.generation)?;
+    let pokemon = state
+        .pokedex_adapter
+        .fetch_pokemon_by_name(&name)
+        .await
+        .map_err(ApiError::from)?;
+
+    let matchups = state
+        .type_chart
+        .matchups(&state.battle_adapter, generation, &pokemon.types)
+        .await
+        .map_err(|err| ApiError::battle("Type", err))?;
+
+    Ok(Json(PokemonWeaknesses {
+        name: pokemon.name,
+        types: pokemon.types,
+        generation,
+        matchups,
+    }))
+}
+
+/// GET /generations/{id} endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/generations/{id}",
+    tag = "pokedex",
+    summary = "Get a generation",
+    params(
+        ("id" = u8, Path, description = "The number of the generation"),
+    ),
+    responses(
+        (status = 200, description = "The generation", body = Generation),
+        (status = 400, description = "Invalid generation number", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Generation not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_generation<P, T, B, I>(
+    Path(id): Path<u8>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Generation>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .pokedex_adapter
+        .fetch_generation_by_id(id)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::pokedex("Generation", err))
+}
+
+/// GET /pokedexes/{name} endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/pokedexes/{name}",
+    tag = "pokedex",
+    summary = "Get a pokedex",
+    params(
+        ("name" = String, Path, description = "The name of the pokedex"),
+    ),
+    responses(
+        (status = 200, description = "The pokedex", body = Pokedex),
+        (status = 404, description = "Pokedex not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_pokedex<P, T, B, I>(
+    Path(name): Path<String>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Pokedex>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .pokedex_adapter
+        .fetch_pokedex_by_name(&name)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::pokedex("Pokedex", err))
+}
+
+/// The name of the pokedex listing all the species.
+const NATIONAL_POKEDEX: &str = "national";
+/// The maximum amount of candidates fetched to find a random Pokémon matching the filters.
+const MAX_RANDOM_CANDIDATES: usize = 100;
+/// The amount of candidates fetched concurrently to find a random Pokémon matching the filters.
+const RANDOM_CANDIDATES_CONCURRENCY: usize = 10;
+
+/// Query parameters of the `GET /pokemon/random` endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RandomPokemonQuery {
+    /// Whether the Pokémon must be legendary or not [default: any].
+    legendary: Option<bool>,
+    /// The habitat of the Pokémon [default: any].
+    habitat: Option<String>,
+    /// The generation which introduced the Pokémon [default: any].
+    generation: Option<u8>,
+    /// One of the types of the Pokémon [default: any].
+    #[serde(rename = "type")]
+    pokemon_type: Option<String>,
+}
+
+impl RandomPokemonQuery {
+    /// Returns whether the query filters the Pokémon by their data,
+    /// which is known only once fetched.
+    fn has_pokemon_filters(&self) -> bool {
+        self.legendary.is_some() || self.habitat.is_some() || self.pokemon_type.is_some()
+    }
+
+    /// Returns whether the [`Pokemon`] matches the filters of the query.
+    fn matches(&self, pokemon: &Pokemon) -> bool {
+        self.legendary
+            .is_none_or(|legendary| pokemon.is_legendary == legendary)
+            && self
+                .habitat
+                .as_ref()
+                .is_none_or(|habitat| pokemon.habitat.as_ref() == Some(habitat))
+            && self
+                .pokemon_type
+                .as_ref()
+                .is_none_or(|pokemon_type| pokemon.types.contains(pokemon_type))
+    }
+}
+
+/// GET /pokemon/random endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/pokemon/random",
+    tag = "pokemon",
+    summary = "Get a random Pokémon",
+    params(
+        RandomPokemonQuery,
+    ),
+    responses(
+        (status = 200, description = "A random Pokémon matching the filters", body = Pokemon),
+        (status = 400, description = "Invalid generation, or unknown type or habitat", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "No Pokémon matching the filters found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_random_pokemon<P, T, B, I>(
+    Query(query): Query<RandomPokemonQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Pokemon>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    // the candidates are the species of the requested generation, otherwise all of them
+    let species = match query.generation {
+        Some(generation) => {
+            let generation = validate_generation(Some(generation))?;
+            state
+                .pokedex_adapter
+                .fetch_generation_by_id(generation)
+                .await
+                .map_err(|err| ApiError::pokedex("Generation", err))?
+                .species
+        }
+        None => national_pokedex_species(&state).await?,
+    };
+    let mut candidates: Vec<String> = species.into_iter().map(|entry| entry.name).collect();
+
+    // the type and the habitat narrow the candidates up front, which also rejects the unknown ones
+    if let Some(pokemon_type) = &query.pokemon_type {
+        let names = state
+            .pokedex_adapter
+            .fetch_pokemon_names_by_type(pokemon_type)
+            .await
+            .map_err(|err| match err {
+                PokedexAdapterError::NotFound => {
+                    ApiError::bad_request(format!("Unknown type '{pokemon_type}'"))
+                }
+                err => ApiError::pokedex("Type", err),
+            })?;
+        let names: HashSet<String> = names.into_iter().collect();
+        candidates.retain(|name| names.contains(name));
+    }
+    if let Some(habitat) = &query.habitat {
+        let names = state
+            .pokedex_adapter
+            .fetch_species_names_by_habitat(habitat)
+            .await
+            .map_err(|err| match err {
+                PokedexAdapterError::NotFound => {
+                    ApiError::bad_request(format!("Unknown habitat '{habitat}'"))
+                }
+                err => ApiError::pokedex("Habitat", err),
+            })?;
+        let names: HashSet<String> = names.into_iter().collect();
+        candidates.retain(|name| names.contains(name));
+    }
+
+    // whether a Pokémon is legendary is known only once fetched, so a bounded amount of candidates is tried
+    candidates.shuffle(&mut rand::rng());
+    candidates.truncate(MAX_RANDOM_CANDIDATES);
+
+    // without filters on the Pokémon data, the first candidate is always fine;
+    // otherwise the candidates are drawn until one matches or none is left
+    let concurrency = if query.has_pokemon_filters() {
+        RANDOM_CANDIDATES_CONCURRENCY
+    } else {
+        1
+    };
+    let mut results = futures::stream::iter(candidates)
+        .map(|name| {
+            let state = state.clone();
+            async move { state.pokedex_adapter.fetch_pokemon_by_name(&name).await }
+        })
+        .buffer_unordered(concurrency);
+    while let Some(result) = results.next().await {
+        match result {
+            Ok(pokemon) if query.matches(&pokemon) => return Ok(Json(pokemon)),
+            Ok(_) | Err(PokedexAdapterError::NotFound) => {}
+            Err(err) => return Err(ApiError::from(err)),
+        }
+    }
+
+    Err(ApiError::not_found("No Pokemon matching the filters found"))
+}
+
+/// Query parameters of the `GET /pokemon/daily` endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DailyPokemonQuery {
+    /// The day of the Pokémon, formatted as `YYYY-MM-DD` [default: today (UTC)].
+    date: Option<NaiveDate>,
+}
+
+/// GET /pokemon/daily endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/pokemon/daily",
+    tag = "pokemon",
+    summary = "Get the Pokémon of the day",
+    params(
+        DailyPokemonQuery,
+    ),
+    responses(
+        (status = 200, description = "The Pokémon of the day", body = Pokemon),
+        (status = 400, description = "Invalid date", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Pokémon not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "The national pokedex is empty", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_daily_pokemon<P, T, B, I>(
+    Query(query): Query<DailyPokemonQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Pokemon>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let date = query.date.unwrap_or_else(|| Utc::now().date_naive());
+    let species = national_pokedex_species(&state).await?;
+    if species.is_empty() {
+        return Err(ApiError::internal("The national pokedex is empty"));
+    }
+
+    // the choice is a hash of the date, so it is the same for everyone during the day
+    let index = daily_index(date, species.len());
+    debug!("Pokemon of {date} is {}", species[index].name);
+
+    state
+        .pokedex_adapter
+        .fetch_pokemon_by_name(&species[index].name)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+/// Returns the index of the Pokémon of the day among the given amount of species.
+///
+/// The date is hashed with the SplitMix64 finalizer rather than a seeded RNG,
+/// so the pick doesn't change across platforms and versions of the `rand` crate.
+fn daily_index(date: NaiveDate, species: usize) -> usize {
+    let mut hash = (date.num_days_from_ce() as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    hash ^= hash >> 31;
+    (hash % species as u64) as usize
+}
+
+/// Fetches the species of the national pokedex, sorted by their national dex number.
+async fn national_pokedex_species<P, T, B, I>(
+    state: &AppState<P, T, B, I>,
+) -> Result<Vec<PokedexEntry>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .pokedex_adapter
+        .fetch_pokedex_by_name(NATIONAL_POKEDEX)
+        .await
+        .map(|pokedex| pokedex.species)
+        .map_err(|err| ApiError::pokedex("Pokedex", err))
+}
+
+/// Query parameters of the `GET /pokemon/{name}/encounters` endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EncountersQuery {
+    /// The game version whose encounters should be returned [default: all].
+    version: Option<String>,
+}
+
+/// GET /pokemon/{name}/encounters endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/pokemon/{name}/encounters",
+    tag = "pokemon",
+    summary = "Get where a Pokémon can be encountered",
+    params(
+        ("name" = String, Path, description = "The name of the Pokémon"),
+        EncountersQuery,
+    ),
+    responses(
+        (status = 200, description = "The encounters of the Pokémon, by game version", body = PokemonEncounters),
+        (status = 404, description = "Pokémon not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_pokemon_encounters<P, T, B, I>(
+    Path(name): Path<String>,
+    Query(query): Query<EncountersQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<PokemonEncounters>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let mut encounters = state
+        .pokedex_adapter
+        .fetch_encounters_by_name(&name)
+        .await
+        .map_err(ApiError::from)?;
+
+    if let Some(version) = query.version {
+        encounters
+            .versions
+            .retain(|encounters| encounters.version == version);
+    }
+
+    Ok(Json(encounters))
+}
+
+/// How long clients may cache the sprites.
+const SPRITE_CACHE_CONTROL: &str = "public, max-age=86400";
+
+/// Query parameters of the `GET /pokemon/{name}/sprite` endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SpriteQuery {
+    /// The image variant [default: front_default].
+    #[serde(default)]
+    variant: SpriteVariant,
+    /// The size in pixels of the square the image should fit into, rounded up to 16, 24, 32, 48, 64, 96, 128, 192, 256 or 512
+    /// [default: original size].
+    size: Option<u32>,
+}
+
+/// GET /pokemon/{name}/sprite endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/pokemon/{name}/sprite",
+    tag = "pokemon",
+    summary = "Get the image of a Pokémon",
+    params(
+        ("name" = String, Path, description = "The name of the Pokémon"),
+        SpriteQuery,
+    ),
+    responses(
+        (status = 200, description = "The image of the Pokémon", content(("image/png")), headers(("ETag" = String), ("Cache-Control" = String))),
+        (status = 304, description = "The image matches the `If-None-Match` header"),
+        (status = 400, description = "Invalid variant or size", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Pokémon or sprite not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "The image could not be resized", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_pokemon_sprite<P, T, B, I>(
+    Path(name): Path<String>,
+    Query(query): Query<SpriteQuery>,
+    headers: HeaderMap,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Response, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    if let Some(size) = query.size
+        && (size == 0 || size > MAX_SPRITE_SIZE)
+    {
+        return Err(ApiError::bad_request(format!(
+            "Size must be between 1 and {MAX_SPRITE_SIZE}"
+        )));
+    }
+
+    let Some(size) = query.size.and_then(round_sprite_size) else {
+        return get_original_sprite(&state, &name, query.variant, &headers).await;
+    };
+    let sprite = get_resized_sprite(&state, &name, query.variant, size).await?;
+    Ok(sprite_response(sprite, &headers))
+}
+
+/// Gets the original image of the given variant from the sprite cache, otherwise fetches it
+/// through the [`PokedexAdapter`], streaming it to the client and to the sprite cache at once.
+///
+/// The streamed image has no `ETag`, since it is known only once the whole image is received.
+async fn get_original_sprite<P, T, B, I>(
+    state: &AppState<P, T, B, I>,
+    name: &str,
+    variant: SpriteVariant,
+    headers: &HeaderMap,
+) -> Result<Response, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    if let Some(sprite) = get_cached_sprite(state, name, variant, None).await {
+        return Ok(sprite_response(sprite, headers));
+    }
+
+    let sprite = fetch_sprite(state, name, variant).await?;
+    let sprite = state
+        .sprite_cache
+        .put_stream(name, variant, None, sprite)
+        .await;
+    let content_type = HeaderValue::from_str(&sprite.content_type)
+        .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static(SPRITE_CACHE_CONTROL),
+            ),
+        ],
+        Body::from_stream(sprite.data),
+    )
+        .into_response())
+}
+
+/// Gets the [`Sprite`] of the given variant resized to the given size from the sprite cache,
+/// otherwise resizes the original image and caches it.
+async fn get_resized_sprite<P, T, B, I>(
+    state: &AppState<P, T, B, I>,
+    name: &str,
+    variant: SpriteVariant,
+    size: u32,
+) -> Result<Sprite, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    if let Some(sprite) = get_cached_sprite(state, name, variant, Some(size)).await {
+        return Ok(sprite);
+    }
+
+    // get the original image from the cache, otherwise fetch it
+    let sprite = match get_cached_sprite(state, name, variant, None).await {
+        Some(sprite) => sprite,
+        None => {
+            let sprite = fetch_sprite(state, name, variant)
+                .await?
+                .collect()
+                .await
+                .map_err(|err| {
+                    ApiError::from(PokedexAdapterError::NetworkError(err.to_string()))
+                })?;
+            if let Err(err) = state.sprite_cache.put(name, variant, None, &sprite).await {
+                warn!("Failed to write the {variant} sprite of {name} to cache: {err}");
+            }
+            sprite
         }
     };
-    log_response("GET", format!("/pokemon/translated/{name}"), response)
+
+    resize_and_cache(state, name, variant, size, sprite).await
+}
+
+/// Fetches the original image of the given variant through the [`PokedexAdapter`].
+async fn fetch_sprite<P, T, B, I>(
+    state: &AppState<P, T, B, I>,
+    name: &str,
+    variant: SpriteVariant,
+) -> Result<SpriteStream, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .pokedex_adapter
+        .fetch_sprite_by_name(name, variant)
+        .await
+        .map_err(ApiError::from)?
+        .ok_or_else(|| ApiError::not_found("Sprite not found"))
+}
+
+/// Gets the [`Sprite`] from the sprite cache.
+///
+/// Cache errors are only logged, since the sprite can still be fetched.
+async fn get_cached_sprite<P, T, B, I>(
+    state: &AppState<P, T, B, I>,
+    name: &str,
+    variant: SpriteVariant,
+    size: Option<u32>,
+) -> Option<Sprite>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .sprite_cache
+        .get(name, variant, size)
+        .await
+        .unwrap_or_else(|err| {
+            warn!("Failed to read the {variant} sprite of {name} from cache: {err}");
+            None
+        })
+}
+
+/// Resizes the original [`Sprite`] to the given size and caches the resized one.
+async fn resize_and_cache<P, T, B, I>(
+    state: &AppState<P, T, B, I>,
+    name: &str,
+    variant: SpriteVariant,
+    size: u32,
+    sprite: Sprite,
+) -> Result<Sprite, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let sprite = tokio::task::spawn_blocking(move || resize_sprite(sprite, size))
+        .await
+        .map_err(|err| ApiError::internal(err.to_string()))?
+        .map_err(|err| ApiError::internal(err.to_string()))?;
+    if let Err(err) = state
+        .sprite_cache
+        .put(name, variant, Some(size), &sprite)
+        .await
+    {
+        warn!("Failed to write the {variant} sprite of {name} to cache: {err}");
+    }
+
+    Ok(sprite)
+}
+
+/// Computes the ETag of the given sprite data, as their FNV-1a 64 hash.
+///
+/// The hash is stable across builds, so the ETags stay valid when the server is upgraded.
+fn sprite_etag(data: &[u8]) -> String {
+    format!("\"{:016x}\"", fnv1a(data))
+}
+
+/// Builds the response for the given [`Sprite`], with the content and cache headers.
+///
+/// If the `If-None-Match` request header matches the sprite ETag, `304 Not Modified` is returned.
+fn sprite_response(sprite: Sprite, headers: &HeaderMap) -> Response {
+    let etag = sprite_etag(&sprite.data);
+
+    let cache_headers = [
+        (
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(SPRITE_CACHE_CONTROL),
+        ),
+        (
+            header::ETAG,
+            HeaderValue::from_str(&etag).expect("valid etag header"),
+        ),
+    ];
+    let not_modified = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    let content_type = HeaderValue::from_str(&sprite.content_type)
+        .unwrap_or(HeaderValue::from_static("application/octet-stream"));
+    (
+        [(header::CONTENT_TYPE, content_type)],
+        cache_headers,
+        sprite.data,
+    )
+        .into_response()
+}
+
+/// POST /calc/damage endpoint handler.
+#[utoipa::path(
+    post,
+    path = "/calc/damage",
+    tag = "battle",
+    summary = "Calculate the damage of a move",
+    request_body = DamageRequest,
+    responses(
+        (status = 200, description = "The damage range and the KO chance", body = DamageCalculation),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Pokémon, move or type not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn post_damage_calculation<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    Json(request): Json<DamageRequest>,
+) -> Result<Json<DamageCalculation>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let (attacker, defender, pokemon_move) = tokio::join!(
+        state
+            .pokedex_adapter
+            .fetch_pokemon_by_name(&request.attacker.name),
+        state
+            .pokedex_adapter
+            .fetch_pokemon_by_name(&request.defender.name),
+        state.battle_adapter.fetch_move_by_name(&request.move_name),
+    );
+    let attacker = attacker.map_err(ApiError::from)?;
+    let defender = defender.map_err(ApiError::from)?;
+    let pokemon_move = pokemon_move.map_err(|err| ApiError::battle("Move", err))?;
+
+    let effectiveness = state
+        .type_chart
+        .effectiveness(
+            &state.battle_adapter,
+            LATEST_GENERATION,
+            &pokemon_move.move_type,
+            &defender.types,
+        )
+        .await
+        .map_err(|err| ApiError::battle("Type", err))?;
+
+    calculate_damage(&request, &attacker, &defender, &pokemon_move, effectiveness)
+        .map(Json)
+        .map_err(|err| ApiError::bad_request(err.to_string()))
+}
+
+/// Query parameters of the `GET /compare` endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CompareQuery {
+    /// The name of the first Pokémon.
+    a: String,
+    /// The name of the second Pokémon.
+    b: String,
+    /// Whether the descriptions should be translated [default: false].
+    #[serde(default)]
+    translated: bool,
+}
+
+/// GET /compare endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/compare",
+    tag = "battle",
+    summary = "Compare two Pokémon",
+    params(
+        CompareQuery,
+    ),
+    responses(
+        (status = 200, description = "The comparison of the Pokémon", body = PokemonComparison),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Pokémon or type not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_comparison<P, T, B, I>(
+    Query(query): Query<CompareQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    Extension(client): Extension<RateLimitClient>,
+) -> Result<Json<PokemonComparison>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    if query.translated {
+        charge_translations(&state.rate_limiter, &client, 2)?;
+    }
+    let (a, b) = tokio::join!(
+        state.pokedex_adapter.fetch_pokemon_by_name(&query.a),
+        state.pokedex_adapter.fetch_pokemon_by_name(&query.b),
+    );
+    let (mut a, mut b) = (a.map_err(ApiError::from)?, b.map_err(ApiError::from)?);
+    if query.translated {
+        (a, b) = tokio::join!(translate_pokemon(&state, a), translate_pokemon(&state, b));
+    }
+
+    compare_pokemon(&state.type_chart, &state.battle_adapter, a, b)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::battle("Type", err))
+}
+
+/// POST /quiz endpoint handler.
+#[utoipa::path(
+    post,
+    path = "/quiz",
+    tag = "quiz",
+    summary = "Start a quiz",
+    request_body(content = Option<QuizRequest>, description = "The options of the quiz"),
+    responses(
+        (status = 200, description = "The quiz", body = Quiz),
+        (status = 404, description = "Pokedex or Pokémon not found", body = Problem, content_type = "application/problem+json"),
+        (status = 500, description = "The national pokedex is empty", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn post_quiz<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    Extension(client): Extension<RateLimitClient>,
+    request: Option<Json<QuizRequest>>,
+) -> Result<Json<Quiz>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let Json(request) = request.unwrap_or_default();
+    if request.translated {
+        charge_translations(&state.rate_limiter, &client, 1)?;
+    }
+    let species = national_pokedex_species(&state).await?;
+    let Some(entry) = species.choose(&mut rand::rng()) else {
+        return Err(ApiError::internal("The national pokedex is empty"));
+    };
+
+    let mut pokemon = state
+        .pokedex_adapter
+        .fetch_pokemon_by_name(&entry.name)
+        .await
+        .map_err(ApiError::from)?;
+    if request.translated {
+        pokemon = translate_pokemon(&state, pokemon).await;
+    }
+
+    // the name is redacted after the translation, which may keep it as it is
+    let description = redact_name(&pokemon.description, &[&pokemon.name, &pokemon.species]);
+    let id = state.quiz_sessions.start(pokemon);
+
+    Ok(Json(Quiz {
+        id,
+        description,
+        attempts_left: state.quiz_sessions.max_attempts(),
+        expires_in_secs: state.quiz_sessions.ttl().as_secs(),
+    }))
+}
+
+/// POST /quiz/{id}/guess endpoint handler.
+#[utoipa::path(
+    post,
+    path = "/quiz/{id}/guess",
+    tag = "quiz",
+    summary = "Guess the Pokémon of a quiz",
+    params(
+        ("id" = String, Path, description = "The id of the quiz"),
+    ),
+    request_body = QuizGuess,
+    responses(
+        (status = 200, description = "The outcome of the guess", body = QuizGuessResult),
+        (status = 400, description = "Invalid request", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Quiz not found", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn post_quiz_guess<P, T, B, I>(
+    Path(id): Path<String>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    Json(guess): Json<QuizGuess>,
+) -> Result<Json<QuizGuessResult>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    state
+        .quiz_sessions
+        .guess(&id, &guess.name)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Quiz not found"))
+}
+
+/// Default amount of results of a search or of an autocompletion.
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+/// Maximum amount of results of a search or of an autocompletion.
+const MAX_SEARCH_LIMIT: usize = 50;
+
+/// Query parameters of the `GET /search` endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchQuery {
+    /// The text to search.
+    q: String,
+    /// The maximum amount of results [default: 10].
+    limit: Option<usize>,
+}
+
+/// GET /search endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "search",
+    summary = "Search the Pokémon by their texts",
+    params(
+        SearchQuery,
+    ),
+    responses(
+        (status = 200, description = "The matching Pokémon, best first", body = SearchResults),
+        (status = 400, description = "Invalid query or limit", body = Problem, content_type = "application/problem+json"),
+        (status = 503, description = "The search index is not ready yet", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_search<P, T, B, I>(
+    Query(query): Query<SearchQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<SearchResults>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if limit == 0 || limit > MAX_SEARCH_LIMIT {
+        return Err(ApiError::bad_request(format!(
+            "Limit must be between 1 and {MAX_SEARCH_LIMIT}"
+        )));
+    }
+    if query.q.trim().is_empty() {
+        return Err(ApiError::bad_request("The query must not be empty"));
+    }
+    let Some(index) = state.search_index.get() else {
+        return Err(ApiError::service_unavailable(
+            "The search index is not ready yet",
+        ));
+    };
+
+    Ok(Json(SearchResults {
+        results: index.search(&query.q, limit),
+        query: query.q,
+    }))
+}
+
+/// Query parameters of the `GET /autocomplete` endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AutocompleteQuery {
+    /// The prefix of the name.
+    prefix: String,
+    /// The maximum amount of suggestions [default: 10].
+    limit: Option<usize>,
+}
+
+/// GET /autocomplete endpoint handler.
+#[utoipa::path(
+    get,
+    path = "/autocomplete",
+    tag = "search",
+    summary = "Autocomplete the name of a Pokémon",
+    params(
+        AutocompleteQuery,
+    ),
+    responses(
+        (status = 200, description = "The names matching the prefix", body = Suggestions),
+        (status = 400, description = "Invalid prefix or limit", body = Problem, content_type = "application/problem+json"),
+        (status = 503, description = "The autocomplete index is not ready yet", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn get_autocomplete<P, T, B, I>(
+    Query(query): Query<AutocompleteQuery>,
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+) -> Result<Json<Suggestions>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT);
+    if limit == 0 || limit > MAX_SEARCH_LIMIT {
+        return Err(ApiError::bad_request(format!(
+            "Limit must be between 1 and {MAX_SEARCH_LIMIT}"
+        )));
+    }
+    if !query.prefix.chars().any(char::is_alphanumeric) {
+        return Err(ApiError::bad_request(
+            "The prefix must contain at least a letter or a digit",
+        ));
+    }
+
+    let Some(suggestions) = state.autocomplete_index.suggest(&query.prefix, limit) else {
+        return Err(ApiError::service_unavailable(
+            "The autocomplete index is not ready yet",
+        ));
+    };
+
+    Ok(Json(Suggestions {
+        prefix: query.prefix,
+        suggestions,
+    }))
+}
+
+/// POST /teams/analyze endpoint handler.
+#[utoipa::path(
+    post,
+    path = "/teams/analyze",
+    tag = "battle",
+    summary = "Analyze a team",
+    request_body = TeamRequest,
+    responses(
+        (status = 200, description = "The analysis of the team", body = TeamAnalysis),
+        (status = 400, description = "Invalid request or team size", body = Problem, content_type = "application/problem+json"),
+        (status = 404, description = "Type not found", body = Problem, content_type = "application/problem+json"),
+        (status = 502, description = "The PokeAPI failed or returned an invalid response", body = Problem, content_type = "application/problem+json"),
+        (status = 504, description = "The PokeAPI did not respond in time", body = Problem, content_type = "application/problem+json"),
+    )
+)]
+pub async fn post_team_analysis<P, T, B, I>(
+    State(state): State<Arc<AppState<P, T, B, I>>>,
+    Json(request): Json<TeamRequest>,
+) -> Result<Json<TeamAnalysis>, ApiError>
+where
+    P: PokedexAdapter + Send + Sync + 'static,
+    T: TranslationAdapter + Send + Sync + 'static,
+    B: BattleAdapter + Send + Sync + 'static,
+    I: ItemAdapter + Send + Sync + 'static,
+{
+    if request.members.is_empty() || request.members.len() > MAX_TEAM_SIZE {
+        return Err(ApiError::bad_request(format!(
+            "A team must have between 1 and {MAX_TEAM_SIZE} members"
+        )));
+    }
+
+    // fetch all the members concurrently; failures are reported per member
+    let members = futures::future::join_all(request.members.into_iter().map(|name| async {
+        match state.pokedex_adapter.fetch_pokemon_by_name(&name).await {
+            Ok(pokemon) => TeamMember {
+                name,
+                pokemon: Some(pokemon),
+                error: None,
+            },
+            Err(err) => {
+                // upstream failures are already logged when mapped into an error
+                let err = ApiError::from(err);
+                if err.status().is_client_error() {
+                    debug!("failed to fetch team member {name}: {err}");
+                }
+                TeamMember {
+                    name,
+                    pokemon: None,
+                    error: Some(TeamMemberError {
+                        status: err.status().as_u16(),
+                        message: err.detail().to_string(),
+                    }),
+                }
+            }
+        }
+    }))
+    .await;
+
+    analyze_team(&state.type_chart, &state.battle_adapter, members)
+        .await
+        .map(Json)
+        .map_err(|err| ApiError::battle("Type", err))
+}
+
+/// GET /openapi.json endpoint handler.
+pub async fn get_openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
 }
 
-/// Utility function which logs the response status of an endpoint and returns it.
-fn log_response<T>(
-    method: &'static str,
-    endpoint: impl std::fmt::Display,
-    response: Result<Json<T>, (StatusCode, String)>,
-) -> Result<Json<T>, (StatusCode, String)> {
-    match &response {
-        Ok(_) => info!("{method} {endpoint} 200 OK"),
-        Err((status, message)) => error!("{method} {endpoint} {status}: {message}"),
+/// Validates the given generation, falling back to the latest one if not provided.
+fn validate_generation(generation: Option<u8>) -> Result<u8, ApiError> {
+    match generation {
+        None => Ok(LATEST_GENERATION),
+        Some(generation) if (1..=LATEST_GENERATION).contains(&generation) => Ok(generation),
+        Some(generation) => Err(ApiError::bad_request(format!(
+            "Invalid generation: {generation}"
+        ))),
     }
-    response
 }
@@ -0,0 +1,190 @@
+use std::fmt;
+use std::time::Instant;
+
+use axum::extract::{MatchedPath, Request};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Instrument as _;
+use tracing::field::Empty;
+
+use crate::log;
+use crate::monitoring::{self, InFlightRequest};
+use crate::web::error::ApiError;
+use crate::web::rate_limit::UNLIMITED_ROUTES;
+
+/// The header carrying the id of the request.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+/// Maximum length of the request ids taken from the requests; longer ids are replaced by generated ones.
+const MAX_REQUEST_ID_LEN: usize = 128;
+/// The route label of the requests which match no route, so unknown paths don't make up new series.
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// The id of a request, taken from its `X-Request-Id` header or generated.
+///
+/// It is available to the handlers as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Generates a random request id.
+    fn generate() -> Self {
+        Self(format!("{:032x}", rand::random::<u128>()))
+    }
+
+    /// Takes the request id from the `X-Request-Id` header, if set to a valid one.
+    ///
+    /// Ids must be made of visible ASCII characters, so they can't tamper with the logs.
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| {
+                !value.is_empty()
+                    && value.len() <= MAX_REQUEST_ID_LEN
+                    && value.bytes().all(|byte| byte.is_ascii_graphic())
+            })
+            .map(|value| Self(value.to_string()))
+    }
+
+    /// Returns the id as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Middleware recording the count, the latency and the amount in flight of the requests.
+///
+/// Requests are labelled with their route template (e.g. `/pokemon/{name}`) rather than their path.
+pub async fn track_requests(request: Request, next: Next) -> Response {
+    let method = request.method().to_string();
+    let route = matched_route(&request);
+
+    let _in_flight = InFlightRequest::start();
+    let started = Instant::now();
+    let response = next.run(request).await;
+    monitoring::record_http_request(method, route, response.status().as_u16(), started.elapsed());
+
+    response
+}
+
+/// Middleware assigning an id to each request, running it within its own span and logging its outcome.
+///
+/// The id is taken from the `X-Request-Id` header, or generated if missing or invalid, and returned in the response.
+/// The span continues the trace of the W3C `traceparent` header of the request, if any.
+pub async fn trace_requests(mut request: Request, next: Next) -> Response {
+    let request_id = RequestId::from_headers(request.headers()).unwrap_or_else(RequestId::generate);
+    let request_id_header =
+        HeaderValue::from_str(request_id.as_str()).expect("request ids are visible ASCII");
+    request
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, request_id_header.clone());
+    request.extensions_mut().insert(request_id.clone());
+
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let route = matched_route(&request);
+    let span = info_span!(
+        "request",
+        otel.name = format!("{method} {route}"),
+        otel.kind = "server",
+        otel.status_code = Empty,
+        request_id = %request_id,
+        http.request.method = %method,
+        http.route = route,
+        url.path = path,
+        http.response.status_code = Empty,
+        latency_ms = Empty,
+    );
+    log::set_parent_from_headers(&span, request.headers());
+
+    let started = Instant::now();
+    let mut response = next.run(request).instrument(span.clone()).await;
+    let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+    response
+        .headers_mut()
+        .insert(REQUEST_ID_HEADER, request_id_header);
+
+    let status = response.status();
+    span.record("http.response.status_code", status.as_u16());
+    span.record("latency_ms", latency_ms);
+    if status.is_server_error() {
+        span.record("otel.status_code", "error");
+    }
+    span.in_scope(|| {
+        log_access(
+            &method,
+            &path,
+            status,
+            response.extensions().get::<ApiError>(),
+        )
+    });
+
+    response
+}
+
+/// Emits the access log line of a request: an error for the server errors, a warning for the client errors.
+///
+/// The probes and the scrapes are frequent, so they are only logged at debug level.
+/// The status and the latency are carried by the span of the request.
+fn log_access(method: &Method, path: &str, status: StatusCode, error: Option<&ApiError>) {
+    let error = error.map(ApiError::detail);
+    if UNLIMITED_ROUTES.contains(&path) {
+        debug!(error, "{method} {path} {status}");
+    } else if status.is_server_error() {
+        error!(error, "{method} {path} {status}");
+    } else if status.is_client_error() {
+        warn!(error, "{method} {path} {status}");
+    } else {
+        info!("{method} {path} {status}");
+    }
+}
+
+/// Returns the route template matched by the request.
+fn matched_route(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| UNMATCHED_ROUTE.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_should_take_request_id_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("abc-123"));
+        assert_eq!(
+            RequestId::from_headers(&headers),
+            Some(RequestId("abc-123".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_should_reject_invalid_request_ids() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(RequestId::from_headers(&headers), None);
+
+        for invalid in ["", "with space", &"a".repeat(MAX_REQUEST_ID_LEN + 1)] {
+            headers.insert(REQUEST_ID_HEADER, HeaderValue::from_str(invalid).unwrap());
+            assert_eq!(RequestId::from_headers(&headers), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_should_generate_request_id() {
+        let request_id = RequestId::generate();
+        assert_eq!(request_id.as_str().len(), 32);
+        assert_ne!(request_id, RequestId::generate());
+    }
+}
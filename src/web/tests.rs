@@ -1,15 +1,36 @@
 //! Web server tests
 
+use std::collections::HashSet;
+
 use axum_test::TestServer;
+use tracing::level_filters::LevelFilter;
 
 use super::*;
-use crate::adapters::pokedex::MockPokedexAdapter;
+use crate::adapters::battle::MockBattleAdapter;
+use crate::adapters::item::MockItemAdapter;
+use crate::adapters::pokedex::{MockPokedexAdapter, PokedexAdapterError};
 use crate::adapters::translation::MockTranslationAdapter;
-use crate::model::Pokemon;
+use crate::autocomplete::AutocompleteIndex;
+use crate::health::HealthChecker;
+use crate::log::{LogController, LogSettings};
+use crate::mechanics::{LATEST_GENERATION, TYPES};
+use crate::model::{
+    Ability, BatchError, BatchRequest, BatchResponse, Berry, BerryFlavor, DamageCalculation,
+    DamageClass, DamageRelations, Encounter, Generation, HealthStatus, Item, Liveness, LogConfig,
+    Move, Pokedex, PokedexEntry, Pokemon, PokemonComparison, PokemonEncounters, PokemonType,
+    PokemonWeaknesses, Problem, Quiz, QuizGuess, QuizGuessResult, QuizRequest, Readiness,
+    SearchResults, SpeciesTexts, Sprite, SpriteVariant, Stats, Suggestion, Suggestions,
+    TeamAnalysis, TypeEffectiveness, VersionEncounters,
+};
+use crate::quiz::QuizSessions;
+use crate::rate_limit::{ClientIdentifier, IpNetwork, RateLimit};
+use crate::search::SearchIndex;
+use crate::sprites::SpriteCache;
 
 const DEFAULT_DESCRIPTION: &str = "A yellow electric mouse.";
 const SHAKESPEARE_TRANSLATION: &str = "Thee electric mouse.";
 const YODA_TRANSLATION: &str = "mouse, a yellow electric.";
+const ADMIN_TOKEN: &str = "secret";
 
 #[tokio::test]
 async fn test_should_get_pokemon() {
@@ -24,7 +45,25 @@ async fn test_should_get_pokemon() {
     assert_eq!(pokemon.name, "pikachu");
     assert_eq!(pokemon.description, DEFAULT_DESCRIPTION);
     assert_eq!(pokemon.habitat.as_deref(), Some("forest"));
-    assert_eq!(pokemon.is_legendary, false);
+    assert!(!pokemon.is_legendary);
+}
+
+#[tokio::test]
+async fn test_should_get_pokemon_form() {
+    let vulpix_alola = Pokemon {
+        name: "vulpix-alola".to_string(),
+        species: "vulpix".to_string(),
+        forms: vec!["vulpix".to_string(), "vulpix-alola".to_string()],
+        types: vec!["ice".to_string()],
+        ..mock_pokemon()
+    };
+    let server = test_server_with_pokedex_adapter(
+        MockPokedexAdapter::not_found().with_pokemon(vulpix_alola.clone()),
+    );
+
+    let response = server.get("/pokemon/vulpix-alola").await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Pokemon>(), vulpix_alola);
 }
 
 #[tokio::test]
@@ -35,6 +74,482 @@ async fn test_should_not_get_unknown_pokemon() {
     response.assert_status_not_found();
 }
 
+#[tokio::test]
+async fn test_should_return_problem_details_on_error() {
+    let server = test_server_with_pokemon(None);
+
+    let response = server
+        .get("/pokemon/unknown")
+        .add_header("x-request-id", "test-request")
+        .await;
+    response.assert_status_not_found();
+    response.assert_header("content-type", "application/problem+json");
+    response.assert_header("x-request-id", "test-request");
+    assert_eq!(
+        response.json::<Problem>(),
+        Problem {
+            problem_type: "about:blank".to_string(),
+            title: "Not Found".to_string(),
+            status: 404,
+            detail: "Pokemon not found".to_string(),
+            instance: Some("/pokemon/unknown".to_string()),
+            request_id: Some("test-request".to_string()),
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_should_generate_request_id_of_problem_details() {
+    let server = test_server_with_pokemon(None);
+
+    let response = server.get("/pokemon/unknown").await;
+    response.assert_status_not_found();
+    let problem = response.json::<Problem>();
+    let request_id = problem.request_id.expect("request id");
+    assert!(!request_id.is_empty());
+    response.assert_header("x-request-id", request_id);
+}
+
+#[tokio::test]
+async fn test_should_propagate_request_id() {
+    let server = test_server();
+
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-request-id", "test-request")
+        .await;
+    response.assert_status_ok();
+    response.assert_header("x-request-id", "test-request");
+}
+
+#[tokio::test]
+async fn test_should_replace_invalid_request_id() {
+    let server = test_server_with_pokemon(None);
+
+    let response = server
+        .get("/pokemon/unknown")
+        .add_header("x-request-id", "a".repeat(200))
+        .await;
+    response.assert_status_not_found();
+    let request_id = response.json::<Problem>().request_id.expect("request id");
+    assert_eq!(request_id.len(), 32);
+    response.assert_header("x-request-id", request_id);
+}
+
+#[tokio::test]
+async fn test_should_return_problem_details_on_rejection() {
+    let server = test_server();
+
+    let response = server.get("/generations/first").await;
+    response.assert_status_bad_request();
+    response.assert_header("content-type", "application/problem+json");
+    let problem = response.json::<Problem>();
+    assert_eq!(problem.status, 400);
+    assert_eq!(problem.title, "Bad Request");
+    assert!(!problem.detail.is_empty());
+    assert_eq!(problem.instance.as_deref(), Some("/generations/first"));
+}
+
+#[tokio::test]
+async fn test_should_map_upstream_network_error_to_bad_gateway() {
+    let server = test_server_with_pokedex_adapter(MockPokedexAdapter::not_found().with_failure(
+        "pikachu",
+        PokedexAdapterError::NetworkError("connection refused".to_string()),
+    ));
+
+    let response = server.get("/pokemon/pikachu").await;
+    response.assert_status(axum::http::StatusCode::BAD_GATEWAY);
+    let problem = response.json::<Problem>();
+    assert_eq!(problem.status, 502);
+    assert_eq!(problem.title, "Bad Gateway");
+    assert!(!problem.detail.contains("connection refused"));
+}
+
+#[tokio::test]
+async fn test_should_map_upstream_timeout_to_gateway_timeout() {
+    let server = test_server_with_pokedex_adapter(MockPokedexAdapter::not_found().with_failure(
+        "pikachu",
+        PokedexAdapterError::Timeout("operation timed out".to_string()),
+    ));
+
+    let response = server.get("/pokemon/translated/pikachu").await;
+    response.assert_status(axum::http::StatusCode::GATEWAY_TIMEOUT);
+    assert_eq!(response.json::<Problem>().status, 504);
+}
+
+#[tokio::test]
+async fn test_should_get_liveness() {
+    let server = test_server();
+
+    let response = server.get("/healthz").await;
+    response.assert_status_ok();
+    assert_eq!(
+        response.json::<Liveness>(),
+        Liveness {
+            status: HealthStatus::Up
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_should_get_readiness() {
+    let server = test_server();
+
+    let response = server.get("/readyz").await;
+    response.assert_status_ok();
+    let readiness = response.json::<Readiness>();
+    assert!(readiness.ready);
+    assert_eq!(readiness.dependencies[0].name, "pokeapi");
+    assert_eq!(readiness.dependencies[0].status, HealthStatus::Up);
+}
+
+#[tokio::test]
+async fn test_should_not_be_ready_if_pokeapi_is_down() {
+    let server = test_server_with_pokedex_adapter(
+        MockPokedexAdapter::not_found()
+            .with_health_error(PokedexAdapterError::NetworkError("refused".to_string())),
+    );
+
+    let response = server.get("/readyz").await;
+    response.assert_status(axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    response.assert_header("content-type", "application/json");
+    let readiness = response.json::<Readiness>();
+    assert!(!readiness.ready);
+    assert_eq!(readiness.dependencies[0].status, HealthStatus::Down);
+}
+
+#[tokio::test]
+async fn test_should_get_metrics() {
+    let server = test_server();
+    server.get("/pokemon/pikachu").await.assert_status_ok();
+    server.get("/unknown/path").await.assert_status_not_found();
+
+    let response = server.get("/metrics").await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "text/plain; version=0.0.4; charset=utf-8");
+    let metrics = response.text();
+    assert!(
+        metrics
+            .contains(r#"http_requests_total{method="GET",route="/pokemon/{name}",status="200"}"#)
+    );
+    assert!(
+        metrics.contains(r#"http_requests_total{method="GET",route="unmatched",status="404"}"#)
+    );
+    assert!(metrics.contains("http_requests_in_flight"));
+}
+
+#[tokio::test]
+async fn test_should_get_log_config() {
+    let server = test_server();
+
+    let response = server
+        .get("/admin/log")
+        .authorization_bearer(ADMIN_TOKEN)
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        response.json::<LogConfig>(),
+        LogConfig {
+            level: "info".to_string(),
+            filter: None,
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_should_put_log_config() {
+    let server = test_server();
+    let config = LogConfig {
+        level: "debug".to_string(),
+        filter: Some("pokedex_api::adapters=trace,hyper=warn".to_string()),
+    };
+
+    let response = server
+        .put("/admin/log")
+        .authorization_bearer(ADMIN_TOKEN)
+        .json(&config)
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<LogConfig>(), config);
+
+    let response = server
+        .get("/admin/log")
+        .authorization_bearer(ADMIN_TOKEN)
+        .await;
+    assert_eq!(response.json::<LogConfig>(), config);
+}
+
+#[tokio::test]
+async fn test_should_reject_invalid_log_config() {
+    let server = test_server();
+
+    for config in [
+        LogConfig {
+            level: "loud".to_string(),
+            filter: None,
+        },
+        LogConfig {
+            level: "info".to_string(),
+            filter: Some("pokedex_api=loud".to_string()),
+        },
+    ] {
+        let response = server
+            .put("/admin/log")
+            .authorization_bearer(ADMIN_TOKEN)
+            .json(&config)
+            .await;
+        response.assert_status_bad_request();
+    }
+}
+
+#[tokio::test]
+async fn test_should_require_admin_token() {
+    let server = test_server();
+
+    let response = server.get("/admin/log").await;
+    response.assert_status_unauthorized();
+
+    for token in ["wrong", "secreT", "secret2"] {
+        let response = server.get("/admin/log").authorization_bearer(token).await;
+        response.assert_status_unauthorized();
+    }
+}
+
+#[tokio::test]
+async fn test_should_disable_admin_endpoints_without_token() {
+    let state = AppState {
+        admin_token: None,
+        ..mock_state(Some(mock_pokemon()))
+    };
+    let server =
+        TestServer::new(WebServer::router(Arc::new(state))).expect("Failed to create test server");
+
+    let response = server
+        .get("/admin/log")
+        .authorization_bearer(ADMIN_TOKEN)
+        .await;
+    response.assert_status_forbidden();
+}
+
+#[tokio::test]
+async fn test_should_rate_limit_requests() {
+    let server = test_server_with_rate_limiter(RateLimiter::new(Some(RateLimit::new(60, 2)), None));
+
+    for remaining in ["1", "0"] {
+        let response = server.get("/pokemon/pikachu").await;
+        response.assert_status_ok();
+        response.assert_header("ratelimit-limit", "2");
+        response.assert_header("ratelimit-remaining", remaining);
+    }
+
+    let response = server.get("/pokemon/pikachu").await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+    response.assert_header("retry-after", "1");
+    response.assert_header("ratelimit-limit", "2");
+    response.assert_header("ratelimit-remaining", "0");
+    response.assert_header("ratelimit-reset", "2");
+    let problem = response.json::<Problem>();
+    assert_eq!(problem.status, 429);
+    assert_eq!(problem.detail, "Too many requests; retry in 1 seconds");
+    assert_eq!(problem.instance.as_deref(), Some("/pokemon/pikachu"));
+}
+
+#[tokio::test]
+async fn test_should_rate_limit_translated_requests_separately() {
+    let server = test_server_with_rate_limiter(RateLimiter::new(
+        Some(RateLimit::new(60, 5)),
+        Some(RateLimit::new(1, 1)),
+    ));
+
+    let response = server.get("/pokemon/translated/pikachu").await;
+    response.assert_status_ok();
+    response.assert_header("ratelimit-limit", "1");
+
+    let response = server.get("/pokemon/translated/pikachu").await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+    response.assert_header("retry-after", "60");
+
+    let response = server.get("/pokemon/pikachu").await;
+    response.assert_status_ok();
+    response.assert_header("ratelimit-remaining", "4");
+}
+
+#[tokio::test]
+async fn test_should_charge_batch_translations() {
+    let server = test_server_with_pokedex_adapter_and_rate_limiter(
+        mock_national_pokedex_adapter(),
+        RateLimiter::new(None, Some(RateLimit::new(60, 3))),
+    );
+    let batch = |translated: bool, size: usize| BatchRequest {
+        names: vec!["pikachu".to_string(); size],
+        translated,
+    };
+
+    let response = server.post("/pokemon/batch").json(&batch(true, 2)).await;
+    response.assert_status_ok();
+
+    // one translation is left
+    let response = server.post("/pokemon/batch").json(&batch(true, 2)).await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+    response.assert_header("retry-after", "1");
+
+    let response = server.post("/pokemon/batch").json(&batch(true, 4)).await;
+    response.assert_status_bad_request();
+    assert_eq!(
+        response.json::<Problem>().detail,
+        "At most 3 descriptions can be translated at once"
+    );
+
+    let response = server.post("/pokemon/batch").json(&batch(false, 4)).await;
+    response.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_should_charge_quiz_translations() {
+    let server = test_server_with_pokedex_adapter_and_rate_limiter(
+        mock_quiz_pokedex_adapter(),
+        RateLimiter::new(None, Some(RateLimit::new(1, 1))),
+    );
+
+    let response = server
+        .post("/quiz")
+        .json(&QuizRequest { translated: true })
+        .await;
+    response.assert_status_ok();
+
+    let response = server
+        .post("/quiz")
+        .json(&QuizRequest { translated: true })
+        .await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+    response.assert_header("retry-after", "60");
+
+    let response = server.post("/quiz").await;
+    response.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_should_charge_comparison_translations() {
+    let server = test_server_with_rate_limiter(RateLimiter::new(None, Some(RateLimit::new(60, 3))));
+
+    let response = server
+        .get("/compare?a=pikachu&b=pikachu&translated=true")
+        .await;
+    response.assert_status_ok();
+
+    let response = server
+        .get("/compare?a=pikachu&b=pikachu&translated=true")
+        .await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn test_should_not_rate_limit_probes() {
+    let server = test_server_with_rate_limiter(RateLimiter::new(Some(RateLimit::new(60, 1)), None));
+
+    for _ in 0..3 {
+        let response = server.get("/healthz").await;
+        response.assert_status_ok();
+        assert!(response.maybe_header("ratelimit-limit").is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_should_rate_limit_clients_by_api_key() {
+    let server = test_server_with_rate_limiter(
+        RateLimiter::new(Some(RateLimit::new(60, 1)), None).with_clients(ClientIdentifier::new(
+            vec![],
+            HashSet::from(["ash".to_string(), "misty".to_string()]),
+        )),
+    );
+
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-api-key", "ash")
+        .await;
+    response.assert_status_ok();
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-api-key", "ash")
+        .await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-api-key", "misty")
+        .await;
+    response.assert_status_ok();
+
+    // unknown keys share the bucket of the address of the client
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-api-key", "brock")
+        .await;
+    response.assert_status_ok();
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-api-key", "gary")
+        .await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn test_should_rate_limit_clients_behind_trusted_proxy() {
+    let rate_limiter =
+        RateLimiter::new(Some(RateLimit::new(60, 1)), None).with_clients(ClientIdentifier::new(
+            vec!["127.0.0.1".parse::<IpNetwork>().unwrap()],
+            HashSet::new(),
+        ));
+    let state = AppState {
+        rate_limiter,
+        ..mock_state(Some(mock_pokemon()))
+    };
+    // the peer address is only known over a real connection
+    let server = TestServer::builder()
+        .http_transport()
+        .build(
+            WebServer::router(Arc::new(state)).into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .expect("Failed to create test server");
+
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-forwarded-for", "203.0.113.7")
+        .await;
+    response.assert_status_ok();
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-forwarded-for", "203.0.113.7")
+        .await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+
+    let response = server
+        .get("/pokemon/pikachu")
+        .add_header("x-forwarded-for", "198.51.100.1")
+        .await;
+    response.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_should_get_openapi_spec() {
+    let server = test_server();
+
+    let response = server.get("/openapi.json").await;
+    response.assert_status_ok();
+    let spec = response.json::<serde_json::Value>();
+    assert_eq!(spec["openapi"], "3.1.0");
+    assert!(spec["paths"]["/pokemon/translated/{name}"]["get"].is_object());
+}
+
+#[tokio::test]
+async fn test_should_get_docs() {
+    let server = test_server();
+
+    let response = server.get("/docs").await;
+    response.assert_status_ok();
+    assert!(response.text().contains("redoc"));
+}
+
 #[tokio::test]
 async fn test_should_get_translated_pokemon_shakespeare() {
     let server = test_server();
@@ -52,6 +567,13 @@ async fn test_should_get_translated_pokemon_yoda_if_legendary() {
         description: DEFAULT_DESCRIPTION.to_string(),
         habitat: Some("napoli".to_string()),
         is_legendary: true,
+        generation: 1,
+        height: 4,
+        weight: 60,
+        species: "mewtwo".to_string(),
+        forms: vec![],
+        types: vec!["psychic".to_string()],
+        stats: Stats::default(),
     }));
     let response = server.get("/pokemon/translated/mewtwo").await;
     response.assert_status_ok();
@@ -67,6 +589,13 @@ async fn test_should_get_translated_pokemon_yoda_if_cave() {
         description: DEFAULT_DESCRIPTION.to_string(),
         habitat: Some("cave".to_string()),
         is_legendary: false,
+        generation: 1,
+        height: 4,
+        weight: 60,
+        species: "zubat".to_string(),
+        forms: vec![],
+        types: vec!["poison".to_string(), "flying".to_string()],
+        stats: Stats::default(),
     }));
     let response = server.get("/pokemon/translated/zubat").await;
     response.assert_status_ok();
@@ -83,49 +612,1340 @@ async fn test_should_not_get_translated_pokemon_if_unexisting() {
 }
 
 #[tokio::test]
-async fn test_should_run_webserver() {
-    let app_data = mock_state(None);
-    let web_server = WebServer::new(app_data);
-    let addr = "127.0.0.1:0".parse().unwrap();
-    let server_handle = tokio::spawn(async move {
-        web_server.run(addr).await.unwrap();
-    });
-    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-    server_handle.abort();
+async fn test_should_get_ability() {
+    let server = test_server();
+    let response = server.get("/abilities/static").await;
+    response.assert_status_ok();
+    let ability = response.json::<Ability>();
+    assert_eq!(ability, mock_ability());
 }
 
-fn test_server() -> TestServer {
-    let router = mock_router(Some(Pokemon {
-        name: "pikachu".to_string(),
-        description: DEFAULT_DESCRIPTION.to_string(),
-        habitat: Some("forest".to_string()),
-        is_legendary: false,
-    }));
-    TestServer::new(router).expect("Failed to create test server")
+#[tokio::test]
+async fn test_should_not_get_unknown_ability() {
+    let server = test_server();
+    let response = server.get("/abilities/levitate").await;
+    response.assert_status_not_found();
 }
 
-fn test_server_with_pokemon(pokemon: Option<Pokemon>) -> TestServer {
-    let router = mock_router(pokemon);
-    TestServer::new(router).expect("Failed to create test server")
+#[tokio::test]
+async fn test_should_get_move() {
+    let server = test_server();
+    let response = server.get("/moves/thunderbolt").await;
+    response.assert_status_ok();
+    let pokemon_move = response.json::<Move>();
+    assert_eq!(pokemon_move, mock_move());
 }
 
-fn mock_router(pokemon: Option<Pokemon>) -> Router {
-    let app_state = mock_state(pokemon);
-    let app_state = Arc::new(app_state);
-    Router::new()
-        .route("/pokemon/{name}", axum::routing::get(routes::get_pokemon))
-        .route(
-            "/pokemon/translated/{name}",
-            axum::routing::get(routes::get_translated_pokemon),
-        )
-        .with_state(app_state)
+#[tokio::test]
+async fn test_should_not_get_unknown_move() {
+    let server = test_server();
+    let response = server.get("/moves/surf").await;
+    response.assert_status_not_found();
 }
 
-fn mock_state(pokemon: Option<Pokemon>) -> AppState<MockPokedexAdapter, MockTranslationAdapter> {
-    AppState {
-        pokedex_adapter: pokemon
-            .map(MockPokedexAdapter::new)
-            .unwrap_or_else(MockPokedexAdapter::not_found),
-        translation_adapter: MockTranslationAdapter::new(SHAKESPEARE_TRANSLATION, YODA_TRANSLATION),
+#[tokio::test]
+async fn test_should_get_item() {
+    let server = test_server();
+    let response = server.get("/items/potion").await;
+    response.assert_status_ok();
+    let item = response.json::<Item>();
+    assert_eq!(item, mock_item());
+}
+
+#[tokio::test]
+async fn test_should_not_get_unknown_item() {
+    let server = test_server();
+    let response = server.get("/items/elixir").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_get_berry() {
+    let server = test_server();
+    let response = server.get("/berries/cheri").await;
+    response.assert_status_ok();
+    let berry = response.json::<Berry>();
+    assert_eq!(berry, mock_berry());
+}
+
+#[tokio::test]
+async fn test_should_not_get_unknown_berry() {
+    let server = test_server();
+    let response = server.get("/berries/oran").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_get_type() {
+    let server = test_server();
+    let response = server.get("/types/electric").await;
+    response.assert_status_ok();
+    let pokemon_type = response.json::<PokemonType>();
+    assert_eq!(pokemon_type, mock_type());
+}
+
+#[tokio::test]
+async fn test_should_not_get_unknown_type() {
+    let server = test_server();
+    let response = server.get("/types/shadow").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_get_type_effectiveness() {
+    let server = test_server();
+    let response = server
+        .get("/types/effectiveness")
+        .add_query_param("attacker", "electric")
+        .add_query_param("defender", "water,flying")
+        .await;
+    response.assert_status_ok();
+    let effectiveness = response.json::<TypeEffectiveness>();
+    assert_eq!(effectiveness.attacker, "electric");
+    assert_eq!(
+        effectiveness.defender,
+        vec!["water".to_string(), "flying".to_string()]
+    );
+    assert_eq!(effectiveness.generation, LATEST_GENERATION);
+    assert_eq!(effectiveness.multiplier, 4.0);
+}
+
+#[tokio::test]
+async fn test_should_not_get_type_effectiveness_of_unknown_type() {
+    let server = test_server();
+    let response = server
+        .get("/types/effectiveness")
+        .add_query_param("attacker", "shadow")
+        .add_query_param("defender", "water")
+        .await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_not_get_type_effectiveness_against_unknown_type() {
+    let server = test_server();
+    let response = server
+        .get("/types/effectiveness")
+        .add_query_param("attacker", "electric")
+        .add_query_param("defender", "water,grasss")
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_not_get_type_effectiveness_without_defender() {
+    let server = test_server();
+    let response = server
+        .get("/types/effectiveness")
+        .add_query_param("attacker", "electric")
+        .add_query_param("defender", ",")
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_not_get_type_effectiveness_with_invalid_generation() {
+    let server = test_server();
+    let response = server
+        .get("/types/effectiveness")
+        .add_query_param("attacker", "electric")
+        .add_query_param("defender", "water")
+        .add_query_param("generation", 42)
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_get_pokemon_weaknesses() {
+    let server = test_server();
+    let response = server.get("/pokemon/pikachu/weaknesses").await;
+    response.assert_status_ok();
+    let weaknesses = response.json::<PokemonWeaknesses>();
+    assert_eq!(weaknesses.name, "pikachu");
+    assert_eq!(weaknesses.types, vec!["electric".to_string()]);
+    assert_eq!(weaknesses.generation, LATEST_GENERATION);
+    assert_eq!(weaknesses.matchups.weaknesses.get("ground"), Some(&2.0));
+    assert_eq!(weaknesses.matchups.resistances.get("steel"), Some(&0.5));
+    assert!(weaknesses.matchups.immunities.is_empty());
+}
+
+#[tokio::test]
+async fn test_should_not_get_unknown_pokemon_weaknesses() {
+    let server = test_server_with_pokemon(None);
+    let response = server.get("/pokemon/missingno/weaknesses").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_get_random_pokemon() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server.get("/pokemon/random").await;
+    response.assert_status_ok();
+    let pokemon = response.json::<Pokemon>();
+    assert!(["pikachu", "raichu", "mewtwo"].contains(&pokemon.name.as_str()));
+
+    let response = server
+        .get("/pokemon/random")
+        .add_query_param("legendary", true)
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Pokemon>().name, "mewtwo");
+
+    let response = server
+        .get("/pokemon/random")
+        .add_query_param("type", "electric")
+        .add_query_param("habitat", "forest")
+        .await;
+    response.assert_status_ok();
+    let pokemon = response.json::<Pokemon>();
+    assert!(["pikachu", "raichu"].contains(&pokemon.name.as_str()));
+
+    let response = server
+        .get("/pokemon/random")
+        .add_query_param("generation", 1)
+        .add_query_param("type", "psychic")
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Pokemon>().name, "mewtwo");
+}
+
+#[tokio::test]
+async fn test_should_get_random_pokemon_among_many_candidates() {
+    // a single psychic legendary among many species, most of which are unknown
+    let mut species: Vec<PokedexEntry> = (1..=200)
+        .map(|number| PokedexEntry {
+            number,
+            name: format!("missingno-{number}"),
+        })
+        .collect();
+    species.push(PokedexEntry {
+        number: 201,
+        name: "pikachu".to_string(),
+    });
+    species.push(PokedexEntry {
+        number: 202,
+        name: "mewtwo".to_string(),
+    });
+    let adapter = MockPokedexAdapter::not_found()
+        .with_pokemon(mock_pokemon())
+        .with_pokemon(Pokemon {
+            name: "mewtwo".to_string(),
+            is_legendary: true,
+            types: vec!["psychic".to_string()],
+            ..mock_pokemon()
+        })
+        .with_pokedex(Pokedex {
+            name: "national".to_string(),
+            region: None,
+            species,
+        });
+    let server = test_server_with_pokedex_adapter(adapter);
+
+    // the type narrows the candidates before they are fetched
+    for _ in 0..5 {
+        let response = server
+            .get("/pokemon/random")
+            .add_query_param("type", "psychic")
+            .add_query_param("legendary", true)
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Pokemon>().name, "mewtwo");
+    }
+}
+
+#[tokio::test]
+async fn test_should_not_get_random_pokemon_without_matches() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .get("/pokemon/random")
+        .add_query_param("habitat", "sea")
+        .await;
+    response.assert_status_not_found();
+
+    let response = server
+        .get("/pokemon/random")
+        .add_query_param("generation", 42)
+        .await;
+    response.assert_status_bad_request();
+
+    let response = server
+        .get("/pokemon/random")
+        .add_query_param("habitat", "nope")
+        .await;
+    response.assert_status_bad_request();
+
+    let response = server
+        .get("/pokemon/random")
+        .add_query_param("type", "foo")
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_get_daily_pokemon() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .get("/pokemon/daily")
+        .add_query_param("date", "2024-02-29")
+        .await;
+    response.assert_status_ok();
+    let pokemon = response.json::<Pokemon>();
+
+    // the same pokemon is returned for the same day
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+    for _ in 0..5 {
+        let response = server
+            .get("/pokemon/daily")
+            .add_query_param("date", "2024-02-29")
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Pokemon>(), pokemon);
+    }
+
+    let response = server.get("/pokemon/daily").await;
+    response.assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_should_get_same_daily_pokemon_on_every_platform() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    for (date, name) in [
+        ("2024-01-03", "pikachu"),
+        ("2024-02-29", "raichu"),
+        ("2025-01-01", "mewtwo"),
+        ("2026-10-19", "mewtwo"),
+    ] {
+        let response = server
+            .get("/pokemon/daily")
+            .add_query_param("date", date)
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Pokemon>().name, name, "{date}");
+    }
+}
+
+#[tokio::test]
+async fn test_should_not_get_daily_pokemon_with_invalid_date() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .get("/pokemon/daily")
+        .add_query_param("date", "2024-02-30")
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_get_generation() {
+    let generation = Generation {
+        id: 1,
+        name: "generation-i".to_string(),
+        main_region: "kanto".to_string(),
+        species: vec![PokedexEntry {
+            number: 25,
+            name: "pikachu".to_string(),
+        }],
+    };
+    let server = test_server_with_pokedex_adapter(
+        MockPokedexAdapter::not_found().with_generation(generation.clone()),
+    );
+
+    let response = server.get("/generations/1").await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Generation>(), generation);
+
+    let response = server.get("/generations/42").await;
+    response.assert_status_not_found();
+
+    let response = server.get("/generations/first").await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_get_pokedex() {
+    let pokedex = Pokedex {
+        name: "paldea".to_string(),
+        region: Some("paldea".to_string()),
+        species: vec![PokedexEntry {
+            number: 74,
+            name: "pikachu".to_string(),
+        }],
+    };
+    let server = test_server_with_pokedex_adapter(
+        MockPokedexAdapter::not_found().with_pokedex(pokedex.clone()),
+    );
+
+    let response = server.get("/pokedexes/paldea").await;
+    response.assert_status_ok();
+    assert_eq!(response.json::<Pokedex>(), pokedex);
+
+    let response = server.get("/pokedexes/orre").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_get_pokemon_encounters() {
+    let encounter = Encounter {
+        location: "viridian-forest-area".to_string(),
+        method: "walk".to_string(),
+        min_level: 3,
+        max_level: 5,
+        chance: 5,
+        conditions: vec![],
+    };
+    let server = test_server_with_pokedex_adapter(
+        MockPokedexAdapter::new(mock_pokemon()).with_encounters(PokemonEncounters {
+            name: "pikachu".to_string(),
+            versions: ["red", "blue", "yellow"]
+                .into_iter()
+                .map(|version| VersionEncounters {
+                    version: version.to_string(),
+                    encounters: vec![encounter.clone()],
+                })
+                .collect(),
+        }),
+    );
+
+    let response = server.get("/pokemon/pikachu/encounters").await;
+    response.assert_status_ok();
+    let encounters = response.json::<PokemonEncounters>();
+    assert_eq!(encounters.name, "pikachu");
+    assert_eq!(encounters.versions.len(), 3);
+
+    let response = server
+        .get("/pokemon/pikachu/encounters")
+        .add_query_param("version", "yellow")
+        .await;
+    response.assert_status_ok();
+    let encounters = response.json::<PokemonEncounters>();
+    assert_eq!(
+        encounters.versions,
+        vec![VersionEncounters {
+            version: "yellow".to_string(),
+            encounters: vec![encounter],
+        }]
+    );
+
+    let response = server
+        .get("/pokemon/pikachu/encounters")
+        .add_query_param("version", "scarlet")
+        .await;
+    response.assert_status_ok();
+    assert!(response.json::<PokemonEncounters>().versions.is_empty());
+}
+
+#[tokio::test]
+async fn test_should_not_get_unknown_pokemon_encounters() {
+    let server = test_server_with_pokemon(None);
+    let response = server.get("/pokemon/missingno/encounters").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_get_pokemon_sprite() {
+    let sprite = mock_sprite(96, 96);
+    let server =
+        test_server_with_pokedex_adapter(MockPokedexAdapter::new(mock_pokemon()).with_sprite(
+            "pikachu",
+            SpriteVariant::OfficialArtwork,
+            sprite.clone(),
+        ));
+
+    let response = server
+        .get("/pokemon/pikachu/sprite")
+        .add_query_param("variant", "official-artwork")
+        .await;
+    response.assert_status_ok();
+    response.assert_header("content-type", "image/png");
+    response.assert_header("cache-control", "public, max-age=86400");
+    assert_eq!(response.as_bytes().as_ref(), sprite.data.as_slice());
+
+    // the sprite is streamed to the cache too, which serves it with its ETag
+    let response = server
+        .get("/pokemon/pikachu/sprite")
+        .add_query_param("variant", "official-artwork")
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.as_bytes().as_ref(), sprite.data.as_slice());
+
+    // the sprite is not modified
+    let etag = response.header("etag");
+    let response = server
+        .get("/pokemon/pikachu/sprite")
+        .add_query_param("variant", "official-artwork")
+        .add_header("if-none-match", etag)
+        .await;
+    response.assert_status(axum::http::StatusCode::NOT_MODIFIED);
+    assert!(response.as_bytes().is_empty());
+}
+
+#[tokio::test]
+async fn test_should_get_pokemon_sprite_with_stable_etag() {
+    let server =
+        test_server_with_pokedex_adapter(MockPokedexAdapter::new(mock_pokemon()).with_sprite(
+            "pikachu",
+            SpriteVariant::FrontDefault,
+            Sprite {
+                content_type: "image/png".to_string(),
+                data: b"a".to_vec(),
+            },
+        ));
+
+    // the first request streams the sprite, so only the cached one is served with its ETag
+    server
+        .get("/pokemon/pikachu/sprite")
+        .await
+        .assert_status_ok();
+    let response = server.get("/pokemon/pikachu/sprite").await;
+    response.assert_status_ok();
+    // FNV-1a 64 of "a"
+    response.assert_header("etag", "\"af63dc4c8601ec8c\"");
+}
+
+#[tokio::test]
+async fn test_should_get_resized_pokemon_sprite() {
+    let server =
+        test_server_with_pokedex_adapter(MockPokedexAdapter::new(mock_pokemon()).with_sprite(
+            "pikachu",
+            SpriteVariant::FrontDefault,
+            mock_sprite(96, 48),
+        ));
+
+    for _ in 0..2 {
+        let response = server
+            .get("/pokemon/pikachu/sprite")
+            .add_query_param("size", 32)
+            .await;
+        response.assert_status_ok();
+        response.assert_header("content-type", "image/png");
+        let image = image::load_from_memory(response.as_bytes()).unwrap();
+        assert_eq!((image.width(), image.height()), (32, 16));
+    }
+
+    // the size is rounded up to the nearest thumbnail size
+    let response = server
+        .get("/pokemon/pikachu/sprite")
+        .add_query_param("size", 40)
+        .await;
+    response.assert_status_ok();
+    let image = image::load_from_memory(response.as_bytes()).unwrap();
+    assert_eq!((image.width(), image.height()), (48, 24));
+}
+
+#[tokio::test]
+async fn test_should_not_get_missing_pokemon_sprite() {
+    let server = test_server();
+    let response = server
+        .get("/pokemon/pikachu/sprite")
+        .add_query_param("variant", "shiny")
+        .await;
+    response.assert_status_not_found();
+
+    let server = test_server_with_pokemon(None);
+    let response = server.get("/pokemon/missingno/sprite").await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_not_get_pokemon_sprite_with_invalid_query() {
+    let server = test_server();
+    let response = server
+        .get("/pokemon/pikachu/sprite")
+        .add_query_param("variant", "back_default")
+        .await;
+    response.assert_status_bad_request();
+
+    let response = server
+        .get("/pokemon/pikachu/sprite")
+        .add_query_param("size", 0)
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_calculate_damage() {
+    let server = test_server();
+    let response = server
+        .post("/calc/damage")
+        .json(&serde_json::json!({
+            "attacker": { "name": "pikachu", "nature": "modest", "evs": { "hp": 0, "attack": 0, "defense": 0, "specialAttack": 252, "specialDefense": 0, "speed": 252 } },
+            "defender": { "name": "pikachu", "level": 30, "stages": { "specialDefense": -1 } },
+            "move": "thunderbolt",
+            "critical": true,
+        }))
+        .await;
+    response.assert_status_ok();
+    let calculation = response.json::<DamageCalculation>();
+    assert_eq!(calculation.attacker, "pikachu");
+    assert_eq!(calculation.defender, "pikachu");
+    assert_eq!(calculation.move_name, "thunderbolt");
+    assert_eq!(calculation.effectiveness, 0.5);
+    assert_eq!(calculation.rolls.len(), 16);
+    assert!(calculation.min_damage <= calculation.max_damage);
+    assert!(calculation.max_damage > 0);
+    assert_eq!(calculation.ko_chances[0].hits, 1);
+}
+
+#[tokio::test]
+async fn test_should_not_calculate_damage_with_unknown_move() {
+    let server = test_server();
+    let response = server
+        .post("/calc/damage")
+        .json(&serde_json::json!({
+            "attacker": { "name": "pikachu" },
+            "defender": { "name": "pikachu" },
+            "move": "surf",
+        }))
+        .await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_not_calculate_damage_with_invalid_level() {
+    let server = test_server();
+    let response = server
+        .post("/calc/damage")
+        .json(&serde_json::json!({
+            "attacker": { "name": "pikachu", "level": 0 },
+            "defender": { "name": "pikachu" },
+            "move": "thunderbolt",
+        }))
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_compare_pokemon() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .get("/compare")
+        .add_query_param("a", "pikachu")
+        .add_query_param("b", "mewtwo")
+        .await;
+    response.assert_status_ok();
+    let comparison = response.json::<PokemonComparison>();
+    assert_eq!(comparison.a.name, "pikachu");
+    assert_eq!(comparison.a.description, DEFAULT_DESCRIPTION);
+    assert_eq!(comparison.b.name, "mewtwo");
+    assert_eq!(comparison.b.description, DEFAULT_DESCRIPTION);
+    assert_eq!(comparison.stat_deltas.total, 0);
+    assert!(comparison.legendary_differs);
+    assert_eq!(comparison.a_against_b.get("electric"), Some(&1.0));
+    assert_eq!(comparison.b_against_a.get("psychic"), Some(&1.0));
+}
+
+#[tokio::test]
+async fn test_should_compare_translated_pokemon() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .get("/compare")
+        .add_query_param("a", "pikachu")
+        .add_query_param("b", "mewtwo")
+        .add_query_param("translated", true)
+        .await;
+    response.assert_status_ok();
+    let comparison = response.json::<PokemonComparison>();
+    assert_eq!(comparison.a.description, SHAKESPEARE_TRANSLATION);
+    assert_eq!(comparison.b.description, YODA_TRANSLATION);
+}
+
+#[tokio::test]
+async fn test_should_not_compare_unknown_pokemon() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .get("/compare")
+        .add_query_param("a", "pikachu")
+        .add_query_param("b", "missingno")
+        .await;
+    response.assert_status_not_found();
+
+    let response = server.get("/compare").add_query_param("a", "pikachu").await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_start_quiz() {
+    let server = test_server_with_pokedex_adapter(mock_quiz_pokedex_adapter());
+
+    let response = server.post("/quiz").await;
+    response.assert_status_ok();
+    let quiz = response.json::<Quiz>();
+    assert_eq!(quiz.id.len(), 32);
+    assert_eq!(
+        quiz.description,
+        "When several of these ??? gather, their electricity builds."
+    );
+    assert_eq!(quiz.attempts_left, 5);
+    assert_eq!(quiz.expires_in_secs, 600);
+}
+
+#[tokio::test]
+async fn test_should_start_translated_quiz() {
+    let server = test_server_with_pokedex_adapter(mock_quiz_pokedex_adapter());
+
+    let response = server
+        .post("/quiz")
+        .json(&QuizRequest { translated: true })
+        .await;
+    response.assert_status_ok();
+    let quiz = response.json::<Quiz>();
+    assert_eq!(quiz.description, SHAKESPEARE_TRANSLATION);
+}
+
+#[tokio::test]
+async fn test_should_guess_quiz() {
+    let server = test_server_with_pokedex_adapter(mock_quiz_pokedex_adapter());
+    let quiz = server.post("/quiz").await.json::<Quiz>();
+
+    let response = server
+        .post(&format!("/quiz/{}/guess", quiz.id))
+        .json(&QuizGuess {
+            name: "raichu".to_string(),
+        })
+        .await;
+    response.assert_status_ok();
+    let result = response.json::<QuizGuessResult>();
+    assert!(!result.correct);
+    assert_eq!(result.attempts_left, 4);
+    assert_eq!(result.hint.as_deref(), Some("Its type is electric"));
+    assert!(result.answer.is_none());
+
+    let response = server
+        .post(&format!("/quiz/{}/guess", quiz.id))
+        .json(&QuizGuess {
+            name: "Pikachu".to_string(),
+        })
+        .await;
+    response.assert_status_ok();
+    let result = response.json::<QuizGuessResult>();
+    assert!(result.correct);
+    assert_eq!(result.attempts_left, 3);
+    assert_eq!(result.answer.as_deref(), Some("pikachu"));
+
+    // the session is over once the pokemon is guessed
+    server
+        .post(&format!("/quiz/{}/guess", quiz.id))
+        .json(&QuizGuess {
+            name: "pikachu".to_string(),
+        })
+        .await
+        .assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_reveal_quiz_answer_when_attempts_are_over() {
+    let server = test_server_with_pokedex_adapter(mock_quiz_pokedex_adapter());
+    let quiz = server.post("/quiz").await.json::<Quiz>();
+
+    let mut result = None;
+    for _ in 0..quiz.attempts_left {
+        let response = server
+            .post(&format!("/quiz/{}/guess", quiz.id))
+            .json(&QuizGuess {
+                name: "mewtwo".to_string(),
+            })
+            .await;
+        response.assert_status_ok();
+        result = Some(response.json::<QuizGuessResult>());
+    }
+
+    let result = result.unwrap();
+    assert!(!result.correct);
+    assert_eq!(result.attempts_left, 0);
+    assert_eq!(result.answer.as_deref(), Some("pikachu"));
+}
+
+#[tokio::test]
+async fn test_should_not_guess_unknown_quiz() {
+    let server = test_server_with_pokedex_adapter(mock_quiz_pokedex_adapter());
+
+    let response = server
+        .post("/quiz/deadbeef/guess")
+        .json(&QuizGuess {
+            name: "pikachu".to_string(),
+        })
+        .await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_should_search() {
+    let state = mock_state_with_pokedex_adapter(
+        mock_national_pokedex_adapter().with_species_texts(SpeciesTexts {
+            name: "mewtwo".to_string(),
+            genus: Some("Genetic Pokémon".to_string()),
+            flavor_texts: vec!["It was created by a scientist.".to_string()],
+        }),
+    );
+    state
+        .search_index
+        .rebuild(&state.pokedex_adapter)
+        .await
+        .expect("Failed to build search index");
+    let server =
+        TestServer::new(WebServer::router(Arc::new(state))).expect("Failed to create test server");
+
+    let response = server
+        .get("/search")
+        .add_query_param("q", "genetic scien")
+        .await;
+    response.assert_status_ok();
+    let results = response.json::<SearchResults>();
+    assert_eq!(results.query, "genetic scien");
+    assert_eq!(results.results.len(), 1);
+    assert_eq!(results.results[0].name, "mewtwo");
+    assert_eq!(results.results[0].genus.as_deref(), Some("Genetic Pokémon"));
+    assert_eq!(
+        results.results[0].snippet,
+        "It was created by a <em>scientist</em>."
+    );
+
+    let response = server
+        .get("/search")
+        .add_query_param("q", "yellow mouse")
+        .add_query_param("limit", 1)
+        .await;
+    response.assert_status_ok();
+    let results = response.json::<SearchResults>();
+    assert_eq!(results.results.len(), 1);
+    assert_eq!(
+        results.results[0].snippet,
+        "A <em>yellow</em> electric <em>mouse</em>."
+    );
+}
+
+#[tokio::test]
+async fn test_should_not_search_invalid_query() {
+    let state = mock_state_with_pokedex_adapter(mock_national_pokedex_adapter());
+    state
+        .search_index
+        .rebuild(&state.pokedex_adapter)
+        .await
+        .expect("Failed to build search index");
+    let server =
+        TestServer::new(WebServer::router(Arc::new(state))).expect("Failed to create test server");
+
+    server
+        .get("/search")
+        .add_query_param("q", " ")
+        .await
+        .assert_status_bad_request();
+    server
+        .get("/search")
+        .add_query_param("q", "mouse")
+        .add_query_param("limit", 0)
+        .await
+        .assert_status_bad_request();
+    server.get("/search").await.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_not_search_before_index_is_built() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server.get("/search").add_query_param("q", "mouse").await;
+    response.assert_status_service_unavailable();
+}
+
+#[tokio::test]
+async fn test_should_autocomplete() {
+    let state = mock_state_with_pokedex_adapter(mock_national_pokedex_adapter());
+    state
+        .autocomplete_index
+        .refresh(&state.pokedex_adapter)
+        .await
+        .expect("Failed to build autocomplete index");
+    let server =
+        TestServer::new(WebServer::router(Arc::new(state))).expect("Failed to create test server");
+
+    let response = server
+        .get("/autocomplete")
+        .add_query_param("prefix", "Pi")
+        .await;
+    response.assert_status_ok();
+    let suggestions = response.json::<Suggestions>();
+    assert_eq!(suggestions.prefix, "Pi");
+    assert_eq!(
+        suggestions.suggestions,
+        vec![Suggestion {
+            name: "pikachu".to_string(),
+            label: "pikachu".to_string(),
+            language: None,
+        }]
+    );
+
+    let response = server
+        .get("/autocomplete")
+        .add_query_param("prefix", "m")
+        .add_query_param("limit", 1)
+        .await;
+    response.assert_status_ok();
+    let suggestions = response.json::<Suggestions>();
+    assert_eq!(suggestions.suggestions.len(), 1);
+    assert_eq!(suggestions.suggestions[0].name, "mewtwo");
+
+    server
+        .get("/autocomplete")
+        .add_query_param("prefix", "-")
+        .await
+        .assert_status_bad_request();
+    server
+        .get("/autocomplete")
+        .add_query_param("prefix", "pi")
+        .add_query_param("limit", 51)
+        .await
+        .assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_not_autocomplete_before_index_is_loaded() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .get("/autocomplete")
+        .add_query_param("prefix", "pi")
+        .await;
+    response.assert_status_service_unavailable();
+}
+
+#[tokio::test]
+async fn test_should_get_pokemon_batch() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .post("/pokemon/batch")
+        .json(&BatchRequest {
+            names: vec![
+                "pikachu".to_string(),
+                "missingno".to_string(),
+                "mewtwo".to_string(),
+            ],
+            translated: false,
+        })
+        .await;
+    response.assert_status_ok();
+    let batch = response.json::<BatchResponse>();
+    assert_eq!(batch.results.len(), 3);
+    assert_eq!(batch.results[0].name, "pikachu");
+    assert_eq!(batch.results[0].pokemon, Some(mock_pokemon()));
+    assert!(batch.results[0].error.is_none());
+    assert_eq!(batch.results[1].name, "missingno");
+    assert!(batch.results[1].pokemon.is_none());
+    assert_eq!(
+        batch.results[1].error,
+        Some(BatchError {
+            status: 404,
+            message: "Pokemon not found".to_string(),
+        })
+    );
+    assert_eq!(
+        batch.results[2]
+            .pokemon
+            .as_ref()
+            .map(|pokemon| pokemon.name.as_str()),
+        Some("mewtwo")
+    );
+}
+
+#[tokio::test]
+async fn test_should_get_translated_pokemon_batch() {
+    let server = test_server_with_pokedex_adapter(mock_national_pokedex_adapter());
+
+    let response = server
+        .post("/pokemon/batch")
+        .json(&BatchRequest {
+            names: vec!["pikachu".to_string(), "mewtwo".to_string()],
+            translated: true,
+        })
+        .await;
+    response.assert_status_ok();
+    let batch = response.json::<BatchResponse>();
+    let descriptions: Vec<&str> = batch
+        .results
+        .iter()
+        .filter_map(|result| result.pokemon.as_ref())
+        .map(|pokemon| pokemon.description.as_str())
+        .collect();
+    assert_eq!(
+        descriptions,
+        vec![SHAKESPEARE_TRANSLATION, YODA_TRANSLATION]
+    );
+}
+
+#[tokio::test]
+async fn test_should_not_get_invalid_pokemon_batch() {
+    let server = test_server();
+
+    server
+        .post("/pokemon/batch")
+        .json(&BatchRequest {
+            names: vec![],
+            translated: false,
+        })
+        .await
+        .assert_status_bad_request();
+    server
+        .post("/pokemon/batch")
+        .json(&BatchRequest {
+            names: vec!["pikachu".to_string(); 101],
+            translated: false,
+        })
+        .await
+        .assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_analyze_team() {
+    let raichu = Pokemon {
+        name: "raichu".to_string(),
+        description: DEFAULT_DESCRIPTION.to_string(),
+        habitat: Some("forest".to_string()),
+        is_legendary: false,
+        generation: 1,
+        height: 4,
+        weight: 60,
+        species: "raichu".to_string(),
+        forms: vec![],
+        types: vec!["electric".to_string()],
+        stats: Stats {
+            hp: 60,
+            ..Default::default()
+        },
+    };
+    let pikachu = Pokemon {
+        name: "pikachu".to_string(),
+        stats: Stats {
+            hp: 35,
+            ..Default::default()
+        },
+        ..raichu.clone()
+    };
+    let server = test_server_with_pokedex_adapter(
+        MockPokedexAdapter::not_found()
+            .with_pokemon(pikachu)
+            .with_pokemon(raichu),
+    );
+
+    let response = server
+        .post("/teams/analyze")
+        .json(&serde_json::json!({ "members": ["pikachu", "raichu", "missingno"] }))
+        .await;
+    response.assert_status_ok();
+    let analysis = response.json::<TeamAnalysis>();
+    assert_eq!(analysis.members.len(), 3);
+    assert!(analysis.members[0].pokemon.is_some());
+    assert!(analysis.members[1].pokemon.is_some());
+    assert_eq!(analysis.members[2].name, "missingno");
+    assert!(analysis.members[2].pokemon.is_none());
+    assert_eq!(analysis.members[2].error.as_ref().unwrap().status, 404);
+    assert_eq!(
+        analysis.coverage.covered,
+        vec!["water".to_string(), "flying".to_string()]
+    );
+    assert_eq!(analysis.shared_weaknesses.get("ground"), Some(&2));
+    assert_eq!(analysis.resistances.get("steel"), Some(&2));
+    assert_eq!(analysis.stats.total.hp, 95);
+}
+
+#[tokio::test]
+async fn test_should_not_analyze_team_with_too_many_members() {
+    let server = test_server();
+    let response = server
+        .post("/teams/analyze")
+        .json(&serde_json::json!({ "members": ["a", "b", "c", "d", "e", "f", "g"] }))
+        .await;
+    response.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_should_run_webserver() {
+    let app_data = mock_state(None);
+    let web_server = WebServer::new(app_data);
+    let addr = "127.0.0.1:0".parse().unwrap();
+    let server_handle = tokio::spawn(async move {
+        web_server.run(addr).await.unwrap();
+    });
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    server_handle.abort();
+}
+
+fn test_server() -> TestServer {
+    let router = mock_router(Some(mock_pokemon()));
+    TestServer::new(router).expect("Failed to create test server")
+}
+
+/// Builds a [`MockPokedexAdapter`] with a national pokedex and a generation made up of pikachu, raichu and mewtwo.
+fn mock_national_pokedex_adapter() -> MockPokedexAdapter {
+    let pikachu = mock_pokemon();
+    let raichu = Pokemon {
+        name: "raichu".to_string(),
+        ..mock_pokemon()
+    };
+    let mewtwo = Pokemon {
+        name: "mewtwo".to_string(),
+        habitat: Some("rare".to_string()),
+        is_legendary: true,
+        types: vec!["psychic".to_string()],
+        ..mock_pokemon()
+    };
+    let species: Vec<PokedexEntry> = [(25, "pikachu"), (26, "raichu"), (150, "mewtwo")]
+        .into_iter()
+        .map(|(number, name)| PokedexEntry {
+            number,
+            name: name.to_string(),
+        })
+        .collect();
+
+    MockPokedexAdapter::not_found()
+        .with_pokemon(pikachu)
+        .with_pokemon(raichu)
+        .with_pokemon(mewtwo)
+        .with_pokedex(Pokedex {
+            name: "national".to_string(),
+            region: None,
+            species: species.clone(),
+        })
+        .with_generation(Generation {
+            id: 1,
+            name: "generation-i".to_string(),
+            main_region: "kanto".to_string(),
+            species,
+        })
+}
+
+fn mock_quiz_pokedex_adapter() -> MockPokedexAdapter {
+    MockPokedexAdapter::not_found()
+        .with_pokemon(Pokemon {
+            description: "When several of these PIKACHU gather, their electricity builds."
+                .to_string(),
+            ..mock_pokemon()
+        })
+        .with_pokedex(Pokedex {
+            name: "national".to_string(),
+            region: None,
+            species: vec![PokedexEntry {
+                number: 25,
+                name: "pikachu".to_string(),
+            }],
+        })
+}
+
+fn mock_pokemon() -> Pokemon {
+    Pokemon {
+        name: "pikachu".to_string(),
+        description: DEFAULT_DESCRIPTION.to_string(),
+        habitat: Some("forest".to_string()),
+        is_legendary: false,
+        generation: 1,
+        height: 4,
+        weight: 60,
+        species: "pikachu".to_string(),
+        forms: vec![],
+        types: vec!["electric".to_string()],
+        stats: Stats {
+            hp: 35,
+            attack: 55,
+            defense: 40,
+            special_attack: 50,
+            special_defense: 50,
+            speed: 90,
+        },
+    }
+}
+
+fn test_server_with_pokemon(pokemon: Option<Pokemon>) -> TestServer {
+    let router = mock_router(pokemon);
+    TestServer::new(router).expect("Failed to create test server")
+}
+
+fn test_server_with_pokedex_adapter(pokedex_adapter: MockPokedexAdapter) -> TestServer {
+    let router = WebServer::router(Arc::new(mock_state_with_pokedex_adapter(pokedex_adapter)));
+    TestServer::new(router).expect("Failed to create test server")
+}
+
+fn test_server_with_pokedex_adapter_and_rate_limiter(
+    pokedex_adapter: MockPokedexAdapter,
+    rate_limiter: RateLimiter,
+) -> TestServer {
+    let state = AppState {
+        rate_limiter,
+        ..mock_state_with_pokedex_adapter(pokedex_adapter)
+    };
+    TestServer::new(WebServer::router(Arc::new(state))).expect("Failed to create test server")
+}
+
+fn test_server_with_rate_limiter(rate_limiter: RateLimiter) -> TestServer {
+    let state = AppState {
+        rate_limiter,
+        ..mock_state(Some(mock_pokemon()))
+    };
+    TestServer::new(WebServer::router(Arc::new(state))).expect("Failed to create test server")
+}
+
+fn mock_router(pokemon: Option<Pokemon>) -> Router {
+    WebServer::router(Arc::new(mock_state(pokemon)))
+}
+
+fn mock_state(
+    pokemon: Option<Pokemon>,
+) -> AppState<MockPokedexAdapter, MockTranslationAdapter, MockBattleAdapter, MockItemAdapter> {
+    mock_state_with_pokedex_adapter(
+        pokemon
+            .map(MockPokedexAdapter::new)
+            .unwrap_or_else(MockPokedexAdapter::not_found),
+    )
+}
+
+fn mock_state_with_pokedex_adapter(
+    pokedex_adapter: MockPokedexAdapter,
+) -> AppState<MockPokedexAdapter, MockTranslationAdapter, MockBattleAdapter, MockItemAdapter> {
+    // register all the types, with neutral damage relations except for the electric type
+    let battle_adapter = TYPES
+        .into_iter()
+        .filter(|name| *name != "electric")
+        .map(|name| PokemonType {
+            name: name.to_string(),
+            damage_relations: DamageRelations::default(),
+            past_damage_relations: vec![],
+        })
+        .fold(
+            MockBattleAdapter::new()
+                .with_ability(mock_ability())
+                .with_move(mock_move())
+                .with_type(mock_type()),
+            MockBattleAdapter::with_type,
+        );
+
+    AppState {
+        pokedex_adapter,
+        translation_adapter: MockTranslationAdapter::new(SHAKESPEARE_TRANSLATION, YODA_TRANSLATION),
+        battle_adapter,
+        item_adapter: MockItemAdapter::new()
+            .with_item(mock_item())
+            .with_berry(mock_berry()),
+        type_chart: TypeChartCache::default(),
+        sprite_cache: SpriteCache::new(
+            tempfile::tempdir()
+                .expect("Failed to create sprite cache dir")
+                .keep(),
+        ),
+        quiz_sessions: QuizSessions::default(),
+        search_index: SearchIndex::default(),
+        autocomplete_index: AutocompleteIndex::default(),
+        health_checker: HealthChecker::default(),
+        metrics: crate::monitoring::install(),
+        log_controller: LogController::detached(LogSettings {
+            level: LevelFilter::INFO,
+            filter: None,
+        }),
+        admin_token: Some(ADMIN_TOKEN.to_string()),
+        rate_limiter: RateLimiter::default(),
+    }
+}
+
+fn mock_sprite(width: u32, height: u32) -> Sprite {
+    let mut data = Vec::new();
+    image::RgbaImage::new(width, height)
+        .write_to(
+            &mut std::io::Cursor::new(&mut data),
+            image::ImageFormat::Png,
+        )
+        .expect("Failed to encode sprite");
+
+    Sprite {
+        content_type: "image/png".to_string(),
+        data,
+    }
+}
+
+fn mock_ability() -> Ability {
+    Ability {
+        name: "static".to_string(),
+        effect: "Whenever a move makes contact with this Pokémon, the move's user has a 30% chance of being paralyzed.".to_string(),
+        short_effect: "Has a 30% chance of paralyzing attacking Pokémon on contact.".to_string(),
+    }
+}
+
+fn mock_move() -> Move {
+    Move {
+        name: "thunderbolt".to_string(),
+        move_type: "electric".to_string(),
+        damage_class: DamageClass::Special,
+        power: Some(90),
+        accuracy: Some(100),
+        pp: Some(15),
+        priority: 0,
+        effect: "Inflicts regular damage. Has a 10% chance to paralyze the target.".to_string(),
+        short_effect: "Has a 10% chance to paralyze the target.".to_string(),
+    }
+}
+
+fn mock_item() -> Item {
+    Item {
+        name: "potion".to_string(),
+        category: "healing".to_string(),
+        cost: 200,
+        fling_power: Some(30),
+        fling_effect: None,
+        attributes: vec![
+            "countable".to_string(),
+            "consumable".to_string(),
+            "usable-overworld".to_string(),
+        ],
+        effect: "Used on a party Pokémon: Restores 20 HP.".to_string(),
+        short_effect: "Restores 20 HP.".to_string(),
+    }
+}
+
+fn mock_berry() -> Berry {
+    Berry {
+        name: "cheri".to_string(),
+        item: "cheri-berry".to_string(),
+        firmness: "soft".to_string(),
+        flavors: ["spicy", "dry", "sweet", "bitter", "sour"]
+            .into_iter()
+            .map(|flavor| BerryFlavor {
+                name: flavor.to_string(),
+                potency: if flavor == "spicy" { 10 } else { 0 },
+            })
+            .collect(),
+        natural_gift_type: "fire".to_string(),
+        natural_gift_power: 60,
+        size: 20,
+        smoothness: 25,
+        growth_time: 3,
+        max_harvest: 5,
+        effect: "Held in battle: When the holder is paralyzed, it consumes this item to cure the paralysis.".to_string(),
+        short_effect: "Holder cures paralysis.".to_string(),
+    }
+}
+
+fn mock_type() -> PokemonType {
+    PokemonType {
+        name: "electric".to_string(),
+        damage_relations: DamageRelations {
+            no_damage_from: vec![],
+            half_damage_from: vec![
+                "flying".to_string(),
+                "steel".to_string(),
+                "electric".to_string(),
+            ],
+            double_damage_from: vec!["ground".to_string()],
+            no_damage_to: vec!["ground".to_string()],
+            half_damage_to: vec![
+                "grass".to_string(),
+                "electric".to_string(),
+                "dragon".to_string(),
+            ],
+            double_damage_to: vec!["flying".to_string(), "water".to_string()],
+        },
+        past_damage_relations: vec![],
     }
 }